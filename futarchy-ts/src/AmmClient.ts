@@ -4,14 +4,27 @@ import { AddressLookupTableAccount, Keypair, PublicKey } from "@solana/web3.js";
 import { Amm as AmmIDLType, IDL as AmmIDL } from "./types/amm";
 
 import BN from "bn.js";
-import { AMM_PROGRAM_ID } from "./constants";
-import { Amm } from "./types";
-import { getATA, getAmmLpMintAddr, getAmmAddr } from "./utils/pda";
+import { AMM_PROGRAM_ID, BPS_SCALE, DEFAULT_SWAP_FEE_BPS } from "./constants";
+import {
+  AddLiquidityQuote,
+  Amm,
+  RemoveLiquidityQuote,
+  SwapQuote,
+  TwapCheckpoints,
+} from "./types";
+import {
+  getATA,
+  getAmmLpMintAddr,
+  getAmmAddr,
+  getAmmTwapCheckpointsAddr,
+  getEventAuthorityAddr,
+} from "./utils/pda";
 import { MethodsBuilder } from "@coral-xyz/anchor/dist/cjs/program/namespace/methods";
-import { MintLayout, unpackMint } from "@solana/spl-token";
+import { MintLayout, TOKEN_PROGRAM_ID, unpackMint } from "@solana/spl-token";
 import { PriceMath } from "./utils/priceMath";
 
 export type SwapType = IdlTypes<AmmIDLType>["SwapType"];
+export type ObservationClampMode = IdlTypes<AmmIDLType>["ObservationClampMode"];
 
 export type CreateAmmClientParams = {
   provider: AnchorProvider;
@@ -30,6 +43,12 @@ export type SwapSimulation = {
   newQuoteReserves: BN;
 };
 
+export type SwapExactOutputSimulation = {
+  expectedIn: BN;
+  newBaseReserves: BN;
+  newQuoteReserves: BN;
+};
+
 export class AmmClient {
   public readonly provider: AnchorProvider;
   public readonly program: Program<AmmIDLType>;
@@ -59,26 +78,35 @@ export class AmmClient {
     return this.program.programId;
   }
 
+  // every instruction that changes an amm emits an event through a self-CPI
+  eventCpiAccounts() {
+    return {
+      eventAuthority: getEventAuthorityAddr(this.getProgramId())[0],
+      program: this.getProgramId(),
+    };
+  }
+
   async createAmm(
     proposal: PublicKey,
     baseMint: PublicKey,
     quoteMint: PublicKey,
     twapInitialObservation: number,
-    twapMaxObservationChangePerUpdate?: number
+    twapMaxObservationChangePerUpdate?: number,
+    swapFeeBps: number = DEFAULT_SWAP_FEE_BPS,
+    protocolFeeBps: number = 0,
+    protocolFeeRecipient: PublicKey = this.provider.publicKey,
+    twapObservationClampMode: ObservationClampMode = { absolute: {} }
   ): Promise<PublicKey> {
     if (!twapMaxObservationChangePerUpdate) {
-      twapMaxObservationChangePerUpdate = twapInitialObservation * 0.02;
+      // 2%, either way you count it
+      twapMaxObservationChangePerUpdate = twapObservationClampMode.bps
+        ? 200
+        : twapInitialObservation * 0.02;
     }
     let [amm] = getAmmAddr(this.getProgramId(), baseMint, quoteMint, proposal);
 
-    let baseDecimals = unpackMint(
-      baseMint,
-      await this.provider.connection.getAccountInfo(baseMint)
-    ).decimals;
-    let quoteDecimals = unpackMint(
-      quoteMint,
-      await this.provider.connection.getAccountInfo(quoteMint)
-    ).decimals;
+    let baseDecimals = await this.getDecimals(baseMint);
+    let quoteDecimals = await this.getDecimals(quoteMint);
 
     let [twapFirstObservationScaled, twapMaxObservationChangePerUpdateScaled] =
       PriceMath.getAmmPrices(
@@ -88,12 +116,25 @@ export class AmmClient {
         twapMaxObservationChangePerUpdate
       );
 
+    // a bps clamp is already in basis points, so it doesn't get scaled
+    if (twapObservationClampMode.bps) {
+      twapMaxObservationChangePerUpdateScaled = new BN(
+        twapMaxObservationChangePerUpdate
+      );
+    }
+
     await this.createAmmIx(
       baseMint,
       quoteMint,
       twapFirstObservationScaled,
       twapMaxObservationChangePerUpdateScaled,
-      proposal
+      proposal,
+      new BN(swapFeeBps),
+      new BN(protocolFeeBps),
+      protocolFeeRecipient,
+      twapObservationClampMode,
+      await this.getTokenProgram(baseMint),
+      await this.getTokenProgram(quoteMint)
     ).rpc();
 
     return amm;
@@ -105,18 +146,28 @@ export class AmmClient {
     quoteMint: PublicKey,
     twapInitialObservation: BN,
     twapMaxObservationChangePerUpdate: BN,
-    proposal: PublicKey
+    proposal: PublicKey,
+    swapFeeBps: BN = new BN(DEFAULT_SWAP_FEE_BPS),
+    protocolFeeBps: BN = new BN(0),
+    protocolFeeRecipient: PublicKey = this.provider.publicKey,
+    twapObservationClampMode: ObservationClampMode = { absolute: {} },
+    baseTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    quoteTokenProgram: PublicKey = TOKEN_PROGRAM_ID
   ): MethodsBuilder<AmmIDLType, any> {
     let [amm] = getAmmAddr(this.getProgramId(), baseMint, quoteMint, proposal);
     let [lpMint] = getAmmLpMintAddr(this.getProgramId(), amm);
 
-    let [vaultAtaBase] = getATA(baseMint, amm);
-    let [vaultAtaQuote] = getATA(quoteMint, amm);
+    let [vaultAtaBase] = getATA(baseMint, amm, baseTokenProgram);
+    let [vaultAtaQuote] = getATA(quoteMint, amm, quoteTokenProgram);
 
     return this.program.methods
       .createAmm({
+        swapFeeBps,
+        protocolFeeBps,
+        protocolFeeRecipient,
         twapInitialObservation,
         twapMaxObservationChangePerUpdate,
+        twapObservationClampMode,
         proposal,
       })
       .accounts({
@@ -127,6 +178,10 @@ export class AmmClient {
         quoteMint,
         vaultAtaBase,
         vaultAtaQuote,
+        vaultAtaLp: getATA(lpMint, amm)[0],
+        baseTokenProgram,
+        quoteTokenProgram,
+        ...this.eventCpiAccounts(),
       });
   }
 
@@ -169,20 +224,14 @@ export class AmmClient {
     let baseAmountCasted: BN | undefined;
 
     if (quoteAmount != undefined) {
-      let quoteDecimals = unpackMint(
-        storedAmm.quoteMint,
-        await this.provider.connection.getAccountInfo(storedAmm.quoteMint)
-      ).decimals;
+      let quoteDecimals = await this.getDecimals(storedAmm.quoteMint);
       quoteAmountCasted = new BN(quoteAmount).mul(
         new BN(10).pow(new BN(quoteDecimals))
       );
     }
 
     if (baseAmount != undefined) {
-      let baseDecimals = unpackMint(
-        storedAmm.baseMint,
-        await this.provider.connection.getAccountInfo(storedAmm.baseMint)
-      ).decimals;
+      let baseDecimals = await this.getDecimals(storedAmm.baseMint);
       baseAmountCasted = new BN(baseAmount).mul(
         new BN(10).pow(new BN(baseDecimals))
       );
@@ -204,7 +253,10 @@ export class AmmClient {
         storedAmm.quoteMint,
         quoteAmountCasted as BN,
         baseAmountCasted as BN,
-        new BN(0)
+        new BN(0),
+        undefined,
+        await this.getTokenProgram(storedAmm.baseMint),
+        await this.getTokenProgram(storedAmm.quoteMint)
       ).rpc();
     }
 
@@ -226,7 +278,10 @@ export class AmmClient {
       storedAmm.quoteMint,
       sim.quoteAmount,
       sim.baseAmount,
-      sim.expectedLpTokens
+      sim.expectedLpTokens,
+      undefined,
+      await this.getTokenProgram(storedAmm.baseMint),
+      await this.getTokenProgram(storedAmm.quoteMint)
     ).rpc();
   }
 
@@ -237,7 +292,9 @@ export class AmmClient {
     quoteAmount: BN,
     maxBaseAmount: BN,
     minLpTokens: BN,
-    user: PublicKey = this.provider.publicKey
+    user: PublicKey = this.provider.publicKey,
+    baseTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    quoteTokenProgram: PublicKey = TOKEN_PROGRAM_ID
   ) {
     const [lpMint] = getAmmLpMintAddr(this.program.programId, amm);
 
@@ -254,10 +311,14 @@ export class AmmClient {
         quoteMint,
         lpMint,
         userAtaLp: getATA(lpMint, user)[0],
-        userAtaBase: getATA(baseMint, user)[0],
-        userAtaQuote: getATA(quoteMint, user)[0],
-        vaultAtaBase: getATA(baseMint, amm)[0],
-        vaultAtaQuote: getATA(quoteMint, amm)[0],
+        userAtaBase: getATA(baseMint, user, baseTokenProgram)[0],
+        userAtaQuote: getATA(quoteMint, user, quoteTokenProgram)[0],
+        vaultAtaBase: getATA(baseMint, amm, baseTokenProgram)[0],
+        vaultAtaQuote: getATA(quoteMint, amm, quoteTokenProgram)[0],
+        vaultAtaLp: getATA(lpMint, amm)[0],
+        baseTokenProgram,
+        quoteTokenProgram,
+        ...this.eventCpiAccounts(),
       });
   }
 
@@ -267,7 +328,9 @@ export class AmmClient {
     quoteMint: PublicKey,
     lpTokensToBurn: BN,
     minBaseAmount: BN,
-    minQuoteAmount: BN
+    minQuoteAmount: BN,
+    baseTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    quoteTokenProgram: PublicKey = TOKEN_PROGRAM_ID
   ) {
     const [lpMint] = getAmmLpMintAddr(this.program.programId, ammAddr);
 
@@ -284,10 +347,22 @@ export class AmmClient {
         baseMint,
         quoteMint,
         userAtaLp: getATA(lpMint, this.provider.publicKey)[0],
-        userAtaBase: getATA(baseMint, this.provider.publicKey)[0],
-        userAtaQuote: getATA(quoteMint, this.provider.publicKey)[0],
-        vaultAtaBase: getATA(baseMint, ammAddr)[0],
-        vaultAtaQuote: getATA(quoteMint, ammAddr)[0],
+        userAtaBase: getATA(
+          baseMint,
+          this.provider.publicKey,
+          baseTokenProgram
+        )[0],
+        userAtaQuote: getATA(
+          quoteMint,
+          this.provider.publicKey,
+          quoteTokenProgram
+        )[0],
+        vaultAtaBase: getATA(baseMint, ammAddr, baseTokenProgram)[0],
+        vaultAtaQuote: getATA(quoteMint, ammAddr, quoteTokenProgram)[0],
+        vaultAtaLp: getATA(lpMint, ammAddr)[0],
+        baseTokenProgram,
+        quoteTokenProgram,
+        ...this.eventCpiAccounts(),
       });
   }
 
@@ -318,7 +393,9 @@ export class AmmClient {
       storedAmm.quoteMint,
       swapType,
       inputAmountScaled,
-      outputAmountMinScaled
+      outputAmountMinScaled,
+      await this.getTokenProgram(storedAmm.baseMint),
+      await this.getTokenProgram(storedAmm.quoteMint)
     ).rpc();
   }
 
@@ -328,7 +405,9 @@ export class AmmClient {
     quoteMint: PublicKey,
     swapType: SwapType,
     inputAmount: BN,
-    outputAmountMin: BN
+    outputAmountMin: BN,
+    baseTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    quoteTokenProgram: PublicKey = TOKEN_PROGRAM_ID
   ) {
     return this.program.methods
       .swap({
@@ -341,23 +420,245 @@ export class AmmClient {
         amm: amm,
         baseMint,
         quoteMint,
-        userAtaBase: getATA(baseMint, this.provider.publicKey)[0],
-        userAtaQuote: getATA(quoteMint, this.provider.publicKey)[0],
-        vaultAtaBase: getATA(baseMint, amm)[0],
-        vaultAtaQuote: getATA(quoteMint, amm)[0],
+        userAtaBase: getATA(
+          baseMint,
+          this.provider.publicKey,
+          baseTokenProgram
+        )[0],
+        userAtaQuote: getATA(
+          quoteMint,
+          this.provider.publicKey,
+          quoteTokenProgram
+        )[0],
+        vaultAtaBase: getATA(baseMint, amm, baseTokenProgram)[0],
+        vaultAtaQuote: getATA(quoteMint, amm, quoteTokenProgram)[0],
+        baseTokenProgram,
+        quoteTokenProgram,
+        ...this.eventCpiAccounts(),
+      });
+  }
+
+  swapExactOutputIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    swapType: SwapType,
+    outputAmount: BN,
+    inputAmountMax: BN,
+    baseTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    quoteTokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ) {
+    return this.program.methods
+      .swapExactOutput({
+        swapType,
+        outputAmount,
+        inputAmountMax,
+      })
+      .accounts({
+        user: this.provider.publicKey,
+        amm: amm,
+        baseMint,
+        quoteMint,
+        userAtaBase: getATA(
+          baseMint,
+          this.provider.publicKey,
+          baseTokenProgram
+        )[0],
+        userAtaQuote: getATA(
+          quoteMint,
+          this.provider.publicKey,
+          quoteTokenProgram
+        )[0],
+        vaultAtaBase: getATA(baseMint, amm, baseTokenProgram)[0],
+        vaultAtaQuote: getATA(quoteMint, amm, quoteTokenProgram)[0],
+        baseTokenProgram,
+        quoteTokenProgram,
+        ...this.eventCpiAccounts(),
       });
   }
 
   async crankThatTwap(amm: PublicKey) {
-    return this.crankThatTwapIx(amm).rpc();
+    const storedAmm = await this.getAmm(amm);
+
+    return this.crankThatTwapIx(amm, storedAmm.hasTwapCheckpoints).rpc();
   }
 
-  crankThatTwapIx(amm: PublicKey) {
+  // `withCheckpoints` must be set once the amm has a `TwapCheckpoints`
+  // account, which then records every crank
+  crankThatTwapIx(amm: PublicKey, withCheckpoints: boolean = false) {
     return this.program.methods.crankThatTwap().accounts({
       amm,
+      twapCheckpoints: withCheckpoints
+        ? getAmmTwapCheckpointsAddr(this.getProgramId(), amm)[0]
+        : null,
+      ...this.eventCpiAccounts(),
+    });
+  }
+
+  async createTwapCheckpoints(amm: PublicKey): Promise<PublicKey> {
+    await this.createTwapCheckpointsIx(amm).rpc();
+
+    return getAmmTwapCheckpointsAddr(this.getProgramId(), amm)[0];
+  }
+
+  createTwapCheckpointsIx(amm: PublicKey) {
+    return this.program.methods.createTwapCheckpoints().accounts({
+      payer: this.provider.publicKey,
+      amm,
+      twapCheckpoints: getAmmTwapCheckpointsAddr(this.getProgramId(), amm)[0],
+    });
+  }
+
+  async collectProtocolFees(amm: PublicKey) {
+    const storedAmm = await this.getAmm(amm);
+
+    return this.collectProtocolFeesIx(
+      amm,
+      storedAmm.baseMint,
+      storedAmm.quoteMint,
+      storedAmm.protocolFeeRecipient,
+      await this.getTokenProgram(storedAmm.baseMint),
+      await this.getTokenProgram(storedAmm.quoteMint)
+    ).rpc();
+  }
+
+  collectProtocolFeesIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    protocolFeeRecipient: PublicKey,
+    baseTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    quoteTokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ) {
+    return this.program.methods.collectProtocolFees().accounts({
+      amm,
+      baseMint,
+      quoteMint,
+      recipientAtaBase: getATA(
+        baseMint,
+        protocolFeeRecipient,
+        baseTokenProgram
+      )[0],
+      recipientAtaQuote: getATA(
+        quoteMint,
+        protocolFeeRecipient,
+        quoteTokenProgram
+      )[0],
+      vaultAtaBase: getATA(baseMint, amm, baseTokenProgram)[0],
+      vaultAtaQuote: getATA(quoteMint, amm, quoteTokenProgram)[0],
+      baseTokenProgram,
+      quoteTokenProgram,
+      ...this.eventCpiAccounts(),
+    });
+  }
+
+  async closeAmm(amm: PublicKey) {
+    const storedAmm = await this.getAmm(amm);
+
+    return this.closeAmmIx(
+      amm,
+      storedAmm.baseMint,
+      storedAmm.quoteMint,
+      storedAmm.rentPayer,
+      await this.getTokenProgram(storedAmm.baseMint),
+      await this.getTokenProgram(storedAmm.quoteMint),
+      storedAmm.hasTwapCheckpoints
+        ? (await this.getTwapCheckpoints(amm)).rentPayer
+        : null
+    ).rpc();
+  }
+
+  // `twapCheckpointsRentPayer` must be set if the amm has a `TwapCheckpoints`
+  // account, which then gets closed along with it
+  closeAmmIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    rentPayer: PublicKey,
+    baseTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    quoteTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    twapCheckpointsRentPayer: PublicKey | null = null
+  ) {
+    const [lpMint] = getAmmLpMintAddr(this.getProgramId(), amm);
+
+    return this.program.methods.closeAmm().accounts({
+      amm,
+      rentPayer,
+      lpMint,
+      baseMint,
+      quoteMint,
+      vaultAtaBase: getATA(baseMint, amm, baseTokenProgram)[0],
+      vaultAtaQuote: getATA(quoteMint, amm, quoteTokenProgram)[0],
+      vaultAtaLp: getATA(lpMint, amm)[0],
+      twapCheckpoints: twapCheckpointsRentPayer
+        ? getAmmTwapCheckpointsAddr(this.getProgramId(), amm)[0]
+        : null,
+      twapCheckpointsRentPayer,
+      baseTokenProgram,
+      quoteTokenProgram,
+      ...this.eventCpiAccounts(),
     });
   }
 
+  // runs the program's own math against `amm` without changing it
+  async quoteSwap(
+    amm: PublicKey,
+    swapType: SwapType,
+    inputAmount: BN
+  ): Promise<SwapQuote> {
+    return this.quoteSwapIx(amm, swapType, inputAmount).view();
+  }
+
+  quoteSwapIx(amm: PublicKey, swapType: SwapType, inputAmount: BN) {
+    return this.program.methods
+      .quoteSwap({
+        swapType,
+        inputAmount,
+      })
+      .accounts({
+        amm,
+        lpMint: getAmmLpMintAddr(this.getProgramId(), amm)[0],
+      });
+  }
+
+  async quoteAddLiquidity(
+    amm: PublicKey,
+    quoteAmount: BN,
+    maxBaseAmount: BN
+  ): Promise<AddLiquidityQuote> {
+    return this.quoteAddLiquidityIx(amm, quoteAmount, maxBaseAmount).view();
+  }
+
+  quoteAddLiquidityIx(amm: PublicKey, quoteAmount: BN, maxBaseAmount: BN) {
+    return this.program.methods
+      .quoteAddLiquidity({
+        quoteAmount,
+        maxBaseAmount,
+      })
+      .accounts({
+        amm,
+        lpMint: getAmmLpMintAddr(this.getProgramId(), amm)[0],
+      });
+  }
+
+  async quoteRemoveLiquidity(
+    amm: PublicKey,
+    lpTokensToBurn: BN
+  ): Promise<RemoveLiquidityQuote> {
+    return this.quoteRemoveLiquidityIx(amm, lpTokensToBurn).view();
+  }
+
+  quoteRemoveLiquidityIx(amm: PublicKey, lpTokensToBurn: BN) {
+    return this.program.methods
+      .quoteRemoveLiquidity({
+        lpTokensToBurn,
+      })
+      .accounts({
+        amm,
+        lpMint: getAmmLpMintAddr(this.getProgramId(), amm)[0],
+      });
+  }
+
   // getter functions
 
   // async getLTWAP(ammAddr: PublicKey): Promise<number> {
@@ -371,6 +672,12 @@ export class AmmClient {
     return await this.program.account.amm.fetch(ammAddr);
   }
 
+  async getTwapCheckpoints(amm: PublicKey): Promise<TwapCheckpoints> {
+    return await this.program.account.twapCheckpoints.fetch(
+      getAmmTwapCheckpointsAddr(this.getProgramId(), amm)[0]
+    );
+  }
+
   getTwap(amm: Amm): BN {
     return amm.oracle.aggregator.div(
       amm.oracle.lastUpdatedSlot.sub(amm.createdAtSlot)
@@ -416,7 +723,9 @@ export class AmmClient {
     inputAmount: BN,
     swapType: SwapType,
     baseReserves: BN,
-    quoteReserves: BN
+    quoteReserves: BN,
+    swapFeeBps: BN = new BN(DEFAULT_SWAP_FEE_BPS),
+    protocolFeeBps: BN = new BN(0)
   ): SwapSimulation {
     if (baseReserves.eqn(0) || quoteReserves.eqn(0)) {
       throw new Error("reserves must be non-zero");
@@ -431,19 +740,29 @@ export class AmmClient {
       outputReserves = quoteReserves;
     }
 
-    let inputAmountWithFee: BN = inputAmount.muln(990);
+    let inputAmountWithFee: BN = inputAmount.mul(
+      new BN(BPS_SCALE).sub(swapFeeBps)
+    );
 
     let numerator: BN = inputAmountWithFee.mul(outputReserves);
-    let denominator: BN = inputReserves.muln(1000).add(inputAmountWithFee);
+    let denominator: BN = inputReserves.muln(BPS_SCALE).add(inputAmountWithFee);
 
     let expectedOut = numerator.div(denominator);
 
+    // the protocol's cut of the fee doesn't stay in the reserves
+    let protocolFee = inputAmount
+      .mul(swapFeeBps)
+      .divn(BPS_SCALE)
+      .mul(protocolFeeBps)
+      .divn(BPS_SCALE);
+    let inputToReserves = inputAmount.sub(protocolFee);
+
     let newBaseReserves, newQuoteReserves: BN;
     if (swapType.buy) {
       newBaseReserves = baseReserves.sub(expectedOut);
-      newQuoteReserves = quoteReserves.add(inputAmount);
+      newQuoteReserves = quoteReserves.add(inputToReserves);
     } else {
-      newBaseReserves = baseReserves.add(inputAmount);
+      newBaseReserves = baseReserves.add(inputToReserves);
       newQuoteReserves = quoteReserves.sub(expectedOut);
     }
 
@@ -454,8 +773,76 @@ export class AmmClient {
     };
   }
 
+  simulateSwapExactOutput(
+    outputAmount: BN,
+    swapType: SwapType,
+    baseReserves: BN,
+    quoteReserves: BN,
+    swapFeeBps: BN = new BN(DEFAULT_SWAP_FEE_BPS),
+    protocolFeeBps: BN = new BN(0)
+  ): SwapExactOutputSimulation {
+    if (baseReserves.eqn(0) || quoteReserves.eqn(0)) {
+      throw new Error("reserves must be non-zero");
+    }
+
+    let inputReserves, outputReserves: BN;
+    if (swapType.buy) {
+      inputReserves = quoteReserves;
+      outputReserves = baseReserves;
+    } else {
+      inputReserves = baseReserves;
+      outputReserves = quoteReserves;
+    }
+
+    if (outputAmount.gte(outputReserves)) {
+      throw new Error("can't swap for the whole pool");
+    }
+
+    let numerator: BN = inputReserves.mul(outputAmount).muln(BPS_SCALE);
+    let denominator: BN = outputReserves
+      .sub(outputAmount)
+      .mul(new BN(BPS_SCALE).sub(swapFeeBps));
+
+    // round up, like the program does
+    let expectedIn = numerator.add(denominator).subn(1).div(denominator);
+
+    let simulation = this.simulateSwap(
+      expectedIn,
+      swapType,
+      baseReserves,
+      quoteReserves,
+      swapFeeBps,
+      protocolFeeBps
+    );
+
+    // the program pays out exactly `outputAmount`, not `expectedOut`
+    let newBaseReserves, newQuoteReserves: BN;
+    if (swapType.buy) {
+      newBaseReserves = baseReserves.sub(outputAmount);
+      newQuoteReserves = simulation.newQuoteReserves;
+    } else {
+      newBaseReserves = simulation.newBaseReserves;
+      newQuoteReserves = quoteReserves.sub(outputAmount);
+    }
+
+    return {
+      expectedIn,
+      newBaseReserves,
+      newQuoteReserves,
+    };
+  }
+
   async getDecimals(mint: PublicKey): Promise<number> {
-    return unpackMint(mint, await this.provider.connection.getAccountInfo(mint))
-      .decimals;
+    const mintInfo = await this.provider.connection.getAccountInfo(mint);
+    return unpackMint(mint, mintInfo, mintInfo?.owner).decimals;
+  }
+
+  // either the legacy token program or token-2022, whichever owns `mint`
+  async getTokenProgram(mint: PublicKey): Promise<PublicKey> {
+    const mintInfo = await this.provider.connection.getAccountInfo(mint);
+    if (!mintInfo) {
+      throw new Error(`mint ${mint.toBase58()} doesn't exist`);
+    }
+    return mintInfo.owner;
   }
 }
@@ -21,6 +21,7 @@ import {
   AUTOCRAT_PROGRAM_ID,
   CONDITIONAL_VAULT_PROGRAM_ID,
   MAINNET_USDC,
+  MINIMUM_LIQUIDITY,
   USDC_DECIMALS,
 } from "./constants";
 import {
@@ -28,6 +29,7 @@ import {
   getAmmAddr,
   getAmmLpMintAddr,
   getDaoTreasuryAddr,
+  getEventAuthorityAddr,
   getVaultAddr,
   getVaultFinalizeMintAddr,
   getVaultRevertMintAddr,
@@ -35,6 +37,7 @@ import {
 import { ConditionalVaultClient } from "./ConditionalVaultClient";
 import { AmmClient } from "./AmmClient";
 import {
+  TOKEN_PROGRAM_ID,
   createAssociatedTokenAccountIdempotentInstruction,
   unpackMint,
 } from "@solana/spl-token";
@@ -217,6 +220,13 @@ export class AutocratClient {
         ),
         passThresholdBps: null,
         slotsPerProposal: null,
+        swapFeeBps: null,
+        protocolFeeBps: null,
+        twapObservationClampMode: null,
+        minSettleGraceSlots: null,
+        executionDelaySlots: null,
+        executionWindowSlots: null,
+        withdrawalWindowSlots: null,
       },
       usdcMint
     ).rpc();
@@ -243,9 +253,10 @@ export class AutocratClient {
   async initializeProposal(
     dao: PublicKey,
     descriptionUrl: string,
-    instruction: ProposalInstruction,
+    instructions: ProposalInstruction[],
     baseTokensToLP: BN,
-    quoteTokensToLP: BN
+    quoteTokensToLP: BN,
+    settleDeadline: BN | null = null
   ): Promise<PublicKey> {
     let vaultProgramId = this.vaultClient.vaultProgram.programId;
     const proposalKP = Keypair.generate();
@@ -255,10 +266,20 @@ export class AutocratClient {
     const daoTreasury = storedDao.treasury;
 
     await this.vaultClient
-      .initializeVaultIx(storedDao.treasury, storedDao.tokenMint, proposal)
+      .initializeVaultIx(
+        storedDao.treasury,
+        storedDao.tokenMint,
+        proposal,
+        settleDeadline
+      )
       .rpc();
     await this.vaultClient
-      .initializeVaultIx(storedDao.treasury, storedDao.usdcMint, proposal)
+      .initializeVaultIx(
+        storedDao.treasury,
+        storedDao.usdcMint,
+        proposal,
+        settleDeadline
+      )
       .rpc();
 
     const [baseVault] = getVaultAddr(
@@ -307,7 +328,11 @@ export class AutocratClient {
         passQuote,
         storedDao.twapInitialObservation,
         storedDao.twapMaxObservationChangePerUpdate,
-        proposal
+        proposal,
+        storedDao.swapFeeBps,
+        storedDao.protocolFeeBps,
+        storedDao.treasury,
+        storedDao.twapObservationClampMode
       )
       .postInstructions([
         await this.ammClient
@@ -326,7 +351,11 @@ export class AutocratClient {
             failQuote,
             storedDao.twapInitialObservation,
             storedDao.twapMaxObservationChangePerUpdate,
-            proposal
+            proposal,
+            storedDao.swapFeeBps,
+            storedDao.protocolFeeBps,
+            storedDao.treasury,
+            storedDao.twapObservationClampMode
           )
           .instruction(),
         await this.ammClient
@@ -356,8 +385,9 @@ export class AutocratClient {
 
     // .rpc();
 
-    // this is how many original tokens are created
-    const lpTokens = quoteTokensToLP;
+    // this is how many original tokens are created, less what each amm locks
+    // on its first deposit
+    const lpTokens = quoteTokensToLP.subn(MINIMUM_LIQUIDITY);
 
     // let tx = await this.initializeProposalIx(
     //   proposalKP,
@@ -391,7 +421,7 @@ export class AutocratClient {
     await this.initializeProposalIx(
       proposalKP,
       descriptionUrl,
-      instruction,
+      instructions,
       dao,
       storedDao.tokenMint,
       storedDao.usdcMint,
@@ -412,7 +442,7 @@ export class AutocratClient {
   initializeProposalIx(
     proposalKeypair: Keypair,
     descriptionUrl: string,
-    instruction: ProposalInstruction,
+    instructions: ProposalInstruction[],
     dao: PublicKey,
     baseMint: PublicKey,
     quoteMint: PublicKey,
@@ -469,7 +499,7 @@ export class AutocratClient {
       this.autocrat.methods
         .initializeProposal({
           descriptionUrl,
-          instruction,
+          instructions,
           passLpTokensToLock,
           failLpTokensToLock,
         })
@@ -515,7 +545,7 @@ export class AutocratClient {
 
     return this.finalizeProposalIx(
       proposal,
-      storedProposal.instruction,
+      storedProposal.instructions,
       storedProposal.dao,
       storedDao.tokenMint,
       storedDao.usdcMint
@@ -524,7 +554,7 @@ export class AutocratClient {
 
   finalizeProposalIx(
     proposal: PublicKey,
-    instruction: any,
+    instructions: any,
     dao: PublicKey,
     daoToken: PublicKey,
     usdc: PublicKey
@@ -589,32 +619,305 @@ export class AutocratClient {
     });
   }
 
-  async executeProposal(proposal: PublicKey) {
+  async withdrawProposal(proposal: PublicKey) {
+    let storedProposal = await this.getProposal(proposal);
+    let storedDao = await this.getDao(storedProposal.dao);
+
+    return this.withdrawProposalIx(
+      proposal,
+      storedProposal.dao,
+      storedDao.tokenMint,
+      storedDao.usdcMint
+    ).rpc();
+  }
+
+  // gives the proposer back their locked LP tokens and reverts both vaults
+  withdrawProposalIx(
+    proposal: PublicKey,
+    dao: PublicKey,
+    daoToken: PublicKey,
+    usdc: PublicKey,
+    proposer: PublicKey = this.provider.publicKey
+  ) {
+    const [daoTreasury] = getDaoTreasuryAddr(this.autocrat.programId, dao);
+    const { baseVault, quoteVault, passAmm, failAmm, passLp, failLp } =
+      this.getProposalPdas(proposal, daoToken, usdc, dao);
+
+    return this.autocrat.methods.withdrawProposal().accounts({
+      proposal,
+      proposer,
+      passAmm,
+      failAmm,
+      dao,
+      baseVault,
+      quoteVault,
+      treasury: daoTreasury,
+      passLpUserAccount: getATA(passLp, proposer)[0],
+      failLpUserAccount: getATA(failLp, proposer)[0],
+      passLpVaultAccount: getATA(passLp, daoTreasury)[0],
+      failLpVaultAccount: getATA(failLp, daoTreasury)[0],
+      vaultProgram: this.vaultClient.vaultProgram.programId,
+    });
+  }
+
+  // deposits `underlyingAmount` into `vault` and swaps the side of it that
+  // trades in `amm` into the other side of that market
+  async splitAndSwap(
+    proposal: PublicKey,
+    vault: PublicKey,
+    amm: PublicKey,
+    underlyingAmount: BN,
+    minOutputAmount: BN
+  ) {
+    const storedVault = await this.vaultClient.getVault(vault);
+    const storedAmm = await this.ammClient.getAmm(amm);
+
+    return this.splitAndSwapIx(
+      proposal,
+      vault,
+      amm,
+      storedVault.underlyingTokenMint,
+      storedAmm.baseMint,
+      storedAmm.quoteMint,
+      underlyingAmount,
+      minOutputAmount,
+      await this.ammClient.getTokenProgram(storedAmm.baseMint),
+      await this.ammClient.getTokenProgram(storedAmm.quoteMint)
+    ).rpc();
+  }
+
+  splitAndSwapIx(
+    proposal: PublicKey,
+    vault: PublicKey,
+    amm: PublicKey,
+    underlyingMint: PublicKey,
+    ammBaseMint: PublicKey,
+    ammQuoteMint: PublicKey,
+    underlyingAmount: BN,
+    minOutputAmount: BN,
+    baseTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    quoteTokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ) {
+    const { accounts, preInstructions } = this.routeThroughVaultAccounts(
+      proposal,
+      vault,
+      amm,
+      underlyingMint,
+      ammBaseMint,
+      ammQuoteMint,
+      baseTokenProgram,
+      quoteTokenProgram
+    );
+
+    return this.autocrat.methods
+      .splitAndSwap({
+        underlyingAmount,
+        minOutputAmount,
+      })
+      .accounts(accounts)
+      .preInstructions(preInstructions);
+  }
+
+  // swaps `counterpartAmount` back into `vault`'s conditional tokens through
+  // `amm` and merges what that produced, plus `extraMergeAmount`, back into
+  // underlying tokens
+  async mergeAndExit(
+    proposal: PublicKey,
+    vault: PublicKey,
+    amm: PublicKey,
+    counterpartAmount: BN,
+    extraMergeAmount: BN,
+    minUnderlyingAmount: BN
+  ) {
+    const storedVault = await this.vaultClient.getVault(vault);
+    const storedAmm = await this.ammClient.getAmm(amm);
+
+    return this.mergeAndExitIx(
+      proposal,
+      vault,
+      amm,
+      storedVault.underlyingTokenMint,
+      storedAmm.baseMint,
+      storedAmm.quoteMint,
+      counterpartAmount,
+      extraMergeAmount,
+      minUnderlyingAmount,
+      await this.ammClient.getTokenProgram(storedAmm.baseMint),
+      await this.ammClient.getTokenProgram(storedAmm.quoteMint)
+    ).rpc();
+  }
+
+  mergeAndExitIx(
+    proposal: PublicKey,
+    vault: PublicKey,
+    amm: PublicKey,
+    underlyingMint: PublicKey,
+    ammBaseMint: PublicKey,
+    ammQuoteMint: PublicKey,
+    counterpartAmount: BN,
+    extraMergeAmount: BN,
+    minUnderlyingAmount: BN,
+    baseTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    quoteTokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ) {
+    const { accounts, preInstructions } = this.routeThroughVaultAccounts(
+      proposal,
+      vault,
+      amm,
+      underlyingMint,
+      ammBaseMint,
+      ammQuoteMint,
+      baseTokenProgram,
+      quoteTokenProgram
+    );
+
+    return this.autocrat.methods
+      .mergeAndExit({
+        counterpartAmount,
+        extraMergeAmount,
+        minUnderlyingAmount,
+      })
+      .accounts(accounts)
+      .preInstructions(preInstructions);
+  }
+
+  // the accounts shared by the routers, along with instructions creating
+  // whichever of the user's token accounts the route may need
+  routeThroughVaultAccounts(
+    proposal: PublicKey,
+    vault: PublicKey,
+    amm: PublicKey,
+    underlyingMint: PublicKey,
+    ammBaseMint: PublicKey,
+    ammQuoteMint: PublicKey,
+    baseTokenProgram: PublicKey,
+    quoteTokenProgram: PublicKey
+  ) {
+    const user = this.provider.publicKey;
+    const vaultProgramId = this.vaultClient.vaultProgram.programId;
+    const ammProgramId = this.ammClient.getProgramId();
+
+    const [conditionalOnFinalizeTokenMint] = getVaultFinalizeMintAddr(
+      vaultProgramId,
+      vault
+    );
+    const [conditionalOnRevertTokenMint] = getVaultRevertMintAddr(
+      vaultProgramId,
+      vault
+    );
+
+    // the vault's conditional tokens are the quote side of the market iff
+    // the vault is the quote vault
+    const vaultIsQuote =
+      ammQuoteMint.equals(conditionalOnFinalizeTokenMint) ||
+      ammQuoteMint.equals(conditionalOnRevertTokenMint);
+    const [counterpartMint, counterpartTokenProgram] = vaultIsQuote
+      ? [ammBaseMint, baseTokenProgram]
+      : [ammQuoteMint, quoteTokenProgram];
+
+    const userConditionalOnFinalizeTokenAccount = getATA(
+      conditionalOnFinalizeTokenMint,
+      user
+    )[0];
+    const userConditionalOnRevertTokenAccount = getATA(
+      conditionalOnRevertTokenMint,
+      user
+    )[0];
+    const userCounterpartTokenAccount = getATA(
+      counterpartMint,
+      user,
+      counterpartTokenProgram
+    )[0];
+
+    return {
+      accounts: {
+        user,
+        proposal,
+        vault,
+        amm,
+        conditionalOnFinalizeTokenMint,
+        conditionalOnRevertTokenMint,
+        counterpartMint,
+        vaultUnderlyingTokenAccount: getATA(underlyingMint, vault)[0],
+        userUnderlyingTokenAccount: getATA(underlyingMint, user)[0],
+        userConditionalOnFinalizeTokenAccount,
+        userConditionalOnRevertTokenAccount,
+        userCounterpartTokenAccount,
+        ammVaultAtaBase: getATA(ammBaseMint, amm, baseTokenProgram)[0],
+        ammVaultAtaQuote: getATA(ammQuoteMint, amm, quoteTokenProgram)[0],
+        ammEventAuthority: getEventAuthorityAddr(ammProgramId)[0],
+        counterpartTokenProgram,
+        vaultProgram: vaultProgramId,
+        ammProgram: ammProgramId,
+      },
+      preInstructions: [
+        createAssociatedTokenAccountIdempotentInstruction(
+          user,
+          userConditionalOnFinalizeTokenAccount,
+          user,
+          conditionalOnFinalizeTokenMint
+        ),
+        createAssociatedTokenAccountIdempotentInstruction(
+          user,
+          userConditionalOnRevertTokenAccount,
+          user,
+          conditionalOnRevertTokenMint
+        ),
+        createAssociatedTokenAccountIdempotentInstruction(
+          user,
+          userCounterpartTokenAccount,
+          user,
+          counterpartMint,
+          counterpartTokenProgram
+        ),
+      ],
+    };
+  }
+
+  // executes up to `maxInstructions` of the remaining instructions, or all of
+  // them if it isn't set
+  async executeProposal(proposal: PublicKey, maxInstructions?: number) {
     let storedProposal = await this.getProposal(proposal);
 
+    const remainingInstructions = storedProposal.instructions.slice(
+      storedProposal.executedInstructionIndex,
+      maxInstructions === undefined
+        ? undefined
+        : storedProposal.executedInstructionIndex + maxInstructions
+    );
+
     return this.executeProposalIx(
       proposal,
       storedProposal.dao,
-      storedProposal.instruction
+      remainingInstructions
     ).rpc();
   }
 
-  executeProposalIx(proposal: PublicKey, dao: PublicKey, instruction: any) {
+  // `instructions` are the ones this call executes, starting from the
+  // proposal's `executedInstructionIndex`
+  executeProposalIx(
+    proposal: PublicKey,
+    dao: PublicKey,
+    instructions: ProposalInstruction[],
+    maxInstructions: number = instructions.length
+  ) {
     const [daoTreasury] = getDaoTreasuryAddr(this.autocrat.programId, dao);
     return this.autocrat.methods
-      .executeProposal()
+      .executeProposal(maxInstructions)
       .accounts({
         proposal,
         dao,
         // daoTreasury,
       })
       .remainingAccounts(
-        instruction.accounts
-          .concat({
-            pubkey: instruction.programId,
-            isWritable: false,
-            isSigner: false,
-          })
+        instructions
+          .flatMap((instruction) =>
+            instruction.accounts.concat({
+              pubkey: instruction.programId,
+              isWritable: false,
+              isSigner: false,
+            })
+          )
           .map((meta: AccountMeta) =>
             meta.pubkey.equals(daoTreasury)
               ? { ...meta, isSigner: false }
@@ -622,4 +925,47 @@ export class AutocratClient {
           )
       );
   }
+
+  async expireProposal(proposal: PublicKey) {
+    return this.expireProposalIx(proposal).rpc();
+  }
+
+  expireProposalIx(proposal: PublicKey) {
+    return this.autocrat.methods.expireProposal().accounts({
+      proposal,
+    });
+  }
+
+  // appends to a proposal's instructions, for when they don't all fit in the
+  // `initializeProposal` transaction. Only works before anyone uses the
+  // proposal's markets.
+  async extendProposalInstructions(
+    proposal: PublicKey,
+    instructions: ProposalInstruction[]
+  ) {
+    const storedProposal = await this.getProposal(proposal);
+
+    return this.extendProposalInstructionsIx(
+      proposal,
+      storedProposal.passAmm,
+      storedProposal.failAmm,
+      instructions
+    ).rpc();
+  }
+
+  extendProposalInstructionsIx(
+    proposal: PublicKey,
+    passAmm: PublicKey,
+    failAmm: PublicKey,
+    instructions: ProposalInstruction[]
+  ) {
+    return this.autocrat.methods
+      .extendProposalInstructions(instructions)
+      .accounts({
+        proposal,
+        passAmm,
+        failAmm,
+        proposer: this.provider.publicKey,
+      });
+  }
 }
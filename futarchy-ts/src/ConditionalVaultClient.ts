@@ -1,10 +1,16 @@
 import { AnchorProvider, Program } from "@coral-xyz/anchor";
-import { AddressLookupTableAccount, Keypair, PublicKey } from "@solana/web3.js";
+import {
+  AccountMeta,
+  AddressLookupTableAccount,
+  Keypair,
+  PublicKey,
+} from "@solana/web3.js";
 
 import {
   ConditionalVault,
   IDL as ConditionalVaultIDL,
 } from "./types/conditional_vault";
+import { SettlementOracle } from "./types";
 
 import BN from "bn.js";
 import { CONDITIONAL_VAULT_PROGRAM_ID } from "./constants";
@@ -12,6 +18,7 @@ import {
   getATA,
   getVaultAddr,
   getVaultFinalizeMintAddr,
+  getVaultOutcomeMintAddr,
   getVaultRevertMintAddr,
 } from "./utils";
 import { MethodsBuilder } from "@coral-xyz/anchor/dist/cjs/program/namespace/methods";
@@ -150,10 +157,218 @@ export class ConditionalVaultClient {
     return ix;
   }
 
+  mintConditionalTokensToRecipientIx(
+    vault: PublicKey,
+    underlyingTokenMint: PublicKey,
+    amount: BN,
+    recipient: PublicKey,
+    user: PublicKey = this.provider.publicKey
+  ) {
+    const { conditionalOnFinalizeTokenMint, conditionalOnRevertTokenMint } =
+      this.getConditionalTokenMints(vault);
+
+    const recipientConditionalOnFinalizeTokenAccount = getATA(
+      conditionalOnFinalizeTokenMint,
+      recipient
+    )[0];
+    const recipientConditionalOnRevertTokenAccount = getATA(
+      conditionalOnRevertTokenMint,
+      recipient
+    )[0];
+
+    return this.vaultProgram.methods
+      .mintConditionalTokensToRecipient(amount)
+      .accounts({
+        vault,
+        conditionalOnFinalizeTokenMint,
+        conditionalOnRevertTokenMint,
+        vaultUnderlyingTokenAccount: getATA(underlyingTokenMint, vault)[0],
+        authority: user,
+        userUnderlyingTokenAccount: getATA(underlyingTokenMint, user)[0],
+        recipientConditionalOnFinalizeTokenAccount,
+        recipientConditionalOnRevertTokenAccount,
+      })
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(
+          this.provider.publicKey,
+          recipientConditionalOnFinalizeTokenAccount,
+          recipient,
+          conditionalOnFinalizeTokenMint
+        ),
+        createAssociatedTokenAccountIdempotentInstruction(
+          this.provider.publicKey,
+          recipientConditionalOnRevertTokenAccount,
+          recipient,
+          conditionalOnRevertTokenMint
+        ),
+      ]);
+  }
+
+  mergeConditionalTokensToRecipientIx(
+    vault: PublicKey,
+    underlyingTokenMint: PublicKey,
+    amount: BN,
+    recipient: PublicKey,
+    user: PublicKey = this.provider.publicKey
+  ) {
+    return this.vaultProgram.methods
+      .mergeConditionalTokensToRecipient(amount)
+      .accounts(
+        this.withdrawToRecipientAccounts(
+          vault,
+          underlyingTokenMint,
+          recipient,
+          user
+        )
+      )
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(
+          this.provider.publicKey,
+          getATA(underlyingTokenMint, recipient)[0],
+          recipient,
+          underlyingTokenMint
+        ),
+      ]);
+  }
+
+  redeemConditionalTokensToRecipientIx(
+    vault: PublicKey,
+    underlyingTokenMint: PublicKey,
+    recipient: PublicKey,
+    user: PublicKey = this.provider.publicKey
+  ) {
+    return this.vaultProgram.methods
+      .redeemConditionalTokensToRecipient()
+      .accounts(
+        this.withdrawToRecipientAccounts(
+          vault,
+          underlyingTokenMint,
+          recipient,
+          user
+        )
+      )
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(
+          this.provider.publicKey,
+          getATA(underlyingTokenMint, recipient)[0],
+          recipient,
+          underlyingTokenMint
+        ),
+      ]);
+  }
+
+  // `parentConditionalTokenMint` is the parent's conditional token that the
+  // nested `vault` holds, and `underlyingTokenMint` is the parent's underlying
+  redeemConditionalTokensThroughParentIx(
+    vault: PublicKey,
+    parentVault: PublicKey,
+    parentConditionalTokenMint: PublicKey,
+    underlyingTokenMint: PublicKey,
+    user: PublicKey = this.provider.publicKey
+  ) {
+    const { conditionalOnFinalizeTokenMint, conditionalOnRevertTokenMint } =
+      this.getConditionalTokenMints(vault);
+
+    const userParentConditionalTokenAccount = getATA(
+      parentConditionalTokenMint,
+      user
+    )[0];
+    const userUnderlyingTokenAccount = getATA(underlyingTokenMint, user)[0];
+
+    return this.vaultProgram.methods
+      .redeemConditionalTokensThroughParent()
+      .accounts({
+        vault,
+        conditionalOnFinalizeTokenMint,
+        conditionalOnRevertTokenMint,
+        vaultUnderlyingTokenAccount: getATA(
+          parentConditionalTokenMint,
+          vault
+        )[0],
+        parentVault,
+        parentConditionalTokenMint,
+        parentVaultUnderlyingTokenAccount: getATA(
+          underlyingTokenMint,
+          parentVault
+        )[0],
+        authority: user,
+        userConditionalOnFinalizeTokenAccount: getATA(
+          conditionalOnFinalizeTokenMint,
+          user
+        )[0],
+        userConditionalOnRevertTokenAccount: getATA(
+          conditionalOnRevertTokenMint,
+          user
+        )[0],
+        userParentConditionalTokenAccount,
+        userUnderlyingTokenAccount,
+      })
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(
+          this.provider.publicKey,
+          userParentConditionalTokenAccount,
+          user,
+          parentConditionalTokenMint
+        ),
+        createAssociatedTokenAccountIdempotentInstruction(
+          this.provider.publicKey,
+          userUnderlyingTokenAccount,
+          user,
+          underlyingTokenMint
+        ),
+      ]);
+  }
+
+  getConditionalTokenMints(vault: PublicKey) {
+    return {
+      conditionalOnFinalizeTokenMint: getVaultFinalizeMintAddr(
+        this.vaultProgram.programId,
+        vault
+      )[0],
+      conditionalOnRevertTokenMint: getVaultRevertMintAddr(
+        this.vaultProgram.programId,
+        vault
+      )[0],
+    };
+  }
+
+  withdrawToRecipientAccounts(
+    vault: PublicKey,
+    underlyingTokenMint: PublicKey,
+    recipient: PublicKey,
+    user: PublicKey
+  ) {
+    const { conditionalOnFinalizeTokenMint, conditionalOnRevertTokenMint } =
+      this.getConditionalTokenMints(vault);
+
+    return {
+      vault,
+      conditionalOnFinalizeTokenMint,
+      conditionalOnRevertTokenMint,
+      vaultUnderlyingTokenAccount: getATA(underlyingTokenMint, vault)[0],
+      authority: user,
+      userConditionalOnFinalizeTokenAccount: getATA(
+        conditionalOnFinalizeTokenMint,
+        user
+      )[0],
+      userConditionalOnRevertTokenAccount: getATA(
+        conditionalOnRevertTokenMint,
+        user
+      )[0],
+      recipientUnderlyingTokenAccount: getATA(
+        underlyingTokenMint,
+        recipient
+      )[0],
+    };
+  }
+
   initializeVaultIx(
     settlementAuthority: PublicKey,
     underlyingTokenMint: PublicKey,
-    proposal: PublicKey
+    proposal: PublicKey,
+    settleDeadline: BN | null = null,
+    parentVault: PublicKey | null = null,
+    settlementOracle: SettlementOracle | null = null
   ): MethodsBuilder<ConditionalVault, any> {
     const [vault] = getVaultAddr(
       this.vaultProgram.programId,
@@ -174,10 +389,16 @@ export class ConditionalVaultClient {
     const vaultUnderlyingTokenAccount = getATA(underlyingTokenMint, vault)[0];
 
     return this.vaultProgram.methods
-      .initializeConditionalVault({ settlementAuthority, proposal })
+      .initializeConditionalVault({
+        settlementAuthority,
+        proposal,
+        settleDeadline,
+        settlementOracle,
+      })
       .accounts({
         vault,
         underlyingTokenMint,
+        parentVault,
         vaultUnderlyingTokenAccount,
         conditionalOnFinalizeTokenMint,
         conditionalOnRevertTokenMint,
@@ -192,6 +413,240 @@ export class ConditionalVaultClient {
       ]);
   }
 
+  initializeMultiOutcomeVaultIx(
+    settlementAuthority: PublicKey,
+    underlyingTokenMint: PublicKey,
+    proposal: PublicKey,
+    numOutcomes: number,
+    settleDeadline: BN | null = null,
+    settlementOracle: SettlementOracle | null = null
+  ) {
+    const [vault] = getVaultAddr(
+      this.vaultProgram.programId,
+      settlementAuthority,
+      underlyingTokenMint,
+      proposal
+    );
+
+    const vaultUnderlyingTokenAccount = getATA(underlyingTokenMint, vault)[0];
+
+    return this.vaultProgram.methods
+      .initializeMultiOutcomeVault({
+        settlementAuthority,
+        proposal,
+        numOutcomes,
+        settleDeadline,
+        settlementOracle,
+      })
+      .accounts({
+        vault,
+        underlyingTokenMint,
+        vaultUnderlyingTokenAccount,
+      })
+      .remainingAccounts(
+        this.getOutcomeMints(vault, numOutcomes).map((pubkey) => ({
+          pubkey,
+          isWritable: true,
+          isSigner: false,
+        }))
+      )
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(
+          this.provider.publicKey,
+          vaultUnderlyingTokenAccount,
+          vault,
+          underlyingTokenMint
+        ),
+      ]);
+  }
+
+  settleMultiOutcomeVaultIx(
+    vault: PublicKey,
+    winningOutcome: number,
+    settlementAuthority: PublicKey = this.provider.publicKey
+  ) {
+    return this.vaultProgram.methods
+      .settleMultiOutcomeVault(winningOutcome)
+      .accounts({
+        settlementAuthority,
+        vault,
+      });
+  }
+
+  settleConditionalVaultWithPayoutsIx(
+    vault: PublicKey,
+    payoutNumerators: BN[],
+    settlementAuthority: PublicKey = this.provider.publicKey
+  ) {
+    return this.vaultProgram.methods
+      .settleConditionalVaultWithPayouts(payoutNumerators)
+      .accounts({
+        settlementAuthority,
+        vault,
+      });
+  }
+
+  mintMultiOutcomeTokensIx(
+    vault: PublicKey,
+    underlyingTokenMint: PublicKey,
+    numOutcomes: number,
+    amount: BN,
+    user: PublicKey = this.provider.publicKey
+  ) {
+    return this.vaultProgram.methods
+      .mintMultiOutcomeTokens(amount)
+      .accounts(this.multiOutcomeAccounts(vault, underlyingTokenMint, user))
+      .remainingAccounts(
+        this.multiOutcomeRemainingAccounts(vault, numOutcomes, user)
+      )
+      .preInstructions(
+        this.getOutcomeMints(vault, numOutcomes).map((mint) =>
+          createAssociatedTokenAccountIdempotentInstruction(
+            this.provider.publicKey,
+            getATA(mint, user)[0],
+            user,
+            mint
+          )
+        )
+      );
+  }
+
+  mergeMultiOutcomeTokensIx(
+    vault: PublicKey,
+    underlyingTokenMint: PublicKey,
+    numOutcomes: number,
+    amount: BN,
+    user: PublicKey = this.provider.publicKey
+  ) {
+    return this.vaultProgram.methods
+      .mergeMultiOutcomeTokens(amount)
+      .accounts(this.multiOutcomeAccounts(vault, underlyingTokenMint, user))
+      .remainingAccounts(
+        this.multiOutcomeRemainingAccounts(vault, numOutcomes, user)
+      );
+  }
+
+  redeemMultiOutcomeTokensIx(
+    vault: PublicKey,
+    underlyingTokenMint: PublicKey,
+    numOutcomes: number,
+    user: PublicKey = this.provider.publicKey
+  ) {
+    return this.vaultProgram.methods
+      .redeemMultiOutcomeTokens()
+      .accounts(this.multiOutcomeAccounts(vault, underlyingTokenMint, user))
+      .remainingAccounts(
+        this.multiOutcomeRemainingAccounts(vault, numOutcomes, user)
+      );
+  }
+
+  redeemPartialConditionalTokensIx(
+    vault: PublicKey,
+    underlyingTokenMint: PublicKey,
+    outcomeMint: PublicKey,
+    outcome: number,
+    amount: BN,
+    user: PublicKey = this.provider.publicKey
+  ) {
+    return this.vaultProgram.methods
+      .redeemPartialConditionalTokensForUnderlyingTokens(outcome, amount)
+      .accounts(this.multiOutcomeAccounts(vault, underlyingTokenMint, user))
+      .remainingAccounts(
+        [outcomeMint, getATA(outcomeMint, user)[0]].map((pubkey) => ({
+          pubkey,
+          isWritable: true,
+          isSigner: false,
+        }))
+      );
+  }
+
+  // anyone can settle a vault from the account its `settlementOracle` names
+  settleConditionalVaultFromOracleIx(
+    vault: PublicKey,
+    settlementOracle: PublicKey
+  ) {
+    return this.vaultProgram.methods
+      .settleConditionalVaultFromOracle()
+      .accounts({
+        vault,
+        settlementOracle,
+      });
+  }
+
+  revertExpiredVaultIx(vault: PublicKey) {
+    return this.vaultProgram.methods.revertExpiredVault().accounts({ vault });
+  }
+
+  async closeConditionalVault(vault: PublicKey) {
+    const storedVault = await this.getVault(vault);
+
+    return this.closeConditionalVaultIx(
+      vault,
+      storedVault.underlyingTokenMint,
+      storedVault.outcomeMints,
+      storedVault.rentPayer
+    ).rpc();
+  }
+
+  closeConditionalVaultIx(
+    vault: PublicKey,
+    underlyingTokenMint: PublicKey,
+    outcomeMints: PublicKey[],
+    rentPayer: PublicKey
+  ) {
+    return this.vaultProgram.methods
+      .closeConditionalVault()
+      .accounts({
+        vault,
+        rentPayer,
+        vaultUnderlyingTokenAccount: getATA(underlyingTokenMint, vault)[0],
+        rentPayerUnderlyingTokenAccount: getATA(
+          underlyingTokenMint,
+          rentPayer
+        )[0],
+      })
+      .remainingAccounts(
+        outcomeMints.map((pubkey) => ({
+          pubkey,
+          isWritable: false,
+          isSigner: false,
+        }))
+      );
+  }
+
+  getOutcomeMints(vault: PublicKey, numOutcomes: number): PublicKey[] {
+    return [...Array(numOutcomes).keys()].map(
+      (outcome) =>
+        getVaultOutcomeMintAddr(this.vaultProgram.programId, vault, outcome)[0]
+    );
+  }
+
+  multiOutcomeAccounts(
+    vault: PublicKey,
+    underlyingTokenMint: PublicKey,
+    user: PublicKey
+  ) {
+    return {
+      vault,
+      vaultUnderlyingTokenAccount: getATA(underlyingTokenMint, vault)[0],
+      authority: user,
+      userUnderlyingTokenAccount: getATA(underlyingTokenMint, user)[0],
+    };
+  }
+
+  // every outcome mint in order, then the user's account for each of them
+  multiOutcomeRemainingAccounts(
+    vault: PublicKey,
+    numOutcomes: number,
+    user: PublicKey
+  ): AccountMeta[] {
+    const outcomeMints = this.getOutcomeMints(vault, numOutcomes);
+
+    return outcomeMints
+      .concat(outcomeMints.map((mint) => getATA(mint, user)[0]))
+      .map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }));
+  }
+
   async initializeVault(
     settlementAuthority: PublicKey,
     underlyingTokenMint: PublicKey,
@@ -19,4 +19,9 @@ export const MAINNET_USDC = new PublicKey(
 
 export const USDC_DECIMALS = 6;
 
+export const BPS_SCALE = 10_000;
+export const DEFAULT_SWAP_FEE_BPS = 100;
+// LP tokens locked in the amm on its first deposit
+export const MINIMUM_LIQUIDITY = 1_000;
+
 export const AUTOCRAT_LUTS: PublicKey[] = [];
@@ -40,6 +40,14 @@ export type Amm = {
           isMut: true;
           isSigner: false;
         },
+        {
+          name: "vaultAtaLp";
+          isMut: true;
+          isSigner: false;
+          docs: [
+            "Holds the LP tokens that get permanently locked on the first deposit."
+          ];
+        },
         {
           name: "associatedTokenProgram";
           isMut: false;
@@ -49,11 +57,35 @@ export type Amm = {
           name: "tokenProgram";
           isMut: false;
           isSigner: false;
+          docs: [
+            "The program that owns the LP mint. LP mints are always legacy SPL mints,",
+            "but the base and quote mints can be owned by either token program."
+          ];
+        },
+        {
+          name: "baseTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteTokenProgram";
+          isMut: false;
+          isSigner: false;
         },
         {
           name: "systemProgram";
           isMut: false;
           isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
         }
       ];
       args: [
@@ -118,6 +150,12 @@ export type Amm = {
           isMut: true;
           isSigner: false;
         },
+        {
+          name: "vaultAtaLp";
+          isMut: true;
+          isSigner: false;
+          docs: ["Holds the `MINIMUM_LIQUIDITY` locked on the first deposit."];
+        },
         {
           name: "associatedTokenProgram";
           isMut: false;
@@ -127,11 +165,34 @@ export type Amm = {
           name: "tokenProgram";
           isMut: false;
           isSigner: false;
+          docs: [
+            "The program that owns the LP mint, which is always a legacy SPL mint."
+          ];
+        },
+        {
+          name: "baseTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteTokenProgram";
+          isMut: false;
+          isSigner: false;
         },
         {
           name: "systemProgram";
           isMut: false;
           isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
         }
       ];
       args: [
@@ -196,6 +257,12 @@ export type Amm = {
           isMut: true;
           isSigner: false;
         },
+        {
+          name: "vaultAtaLp";
+          isMut: true;
+          isSigner: false;
+          docs: ["Holds the `MINIMUM_LIQUIDITY` locked on the first deposit."];
+        },
         {
           name: "associatedTokenProgram";
           isMut: false;
@@ -205,11 +272,34 @@ export type Amm = {
           name: "tokenProgram";
           isMut: false;
           isSigner: false;
+          docs: [
+            "The program that owns the LP mint, which is always a legacy SPL mint."
+          ];
+        },
+        {
+          name: "baseTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteTokenProgram";
+          isMut: false;
+          isSigner: false;
         },
         {
           name: "systemProgram";
           isMut: false;
           isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
         }
       ];
       args: [
@@ -270,7 +360,12 @@ export type Amm = {
           isSigner: false;
         },
         {
-          name: "tokenProgram";
+          name: "baseTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteTokenProgram";
           isMut: false;
           isSigner: false;
         },
@@ -278,6 +373,16 @@ export type Amm = {
           name: "systemProgram";
           isMut: false;
           isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
         }
       ];
       args: [
@@ -289,6 +394,89 @@ export type Amm = {
         }
       ];
     },
+    {
+      name: "swapExactOutput";
+      accounts: [
+        {
+          name: "user";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "amm";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "baseMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "userAtaBase";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userAtaQuote";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultAtaBase";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultAtaQuote";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "associatedTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "baseTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: "SwapExactOutputArgs";
+          };
+        }
+      ];
+    },
     {
       name: "crankThatTwap";
       accounts: [
@@ -296,111 +484,596 @@ export type Amm = {
           name: "amm";
           isMut: true;
           isSigner: false;
+        },
+        {
+          name: "twapCheckpoints";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: [
+            "The oracle's new state gets checkpointed here. Required once the AMM",
+            "has checkpoints."
+          ];
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
         }
       ];
       args: [];
-    }
-  ];
-  accounts: [
-    {
-      name: "amm";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "bump";
-            type: "u8";
-          },
-          {
-            name: "proposal";
-            docs: [
-              "We need to create multiple AMMs for a single asset pair, but AMMs are PDAs.",
-              "So we can use proposal as a PDA seed."
-            ];
-            type: "publicKey";
-          },
-          {
-            name: "createdAtSlot";
-            type: "u64";
-          },
-          {
-            name: "lpMint";
-            type: "publicKey";
-          },
-          {
-            name: "baseMint";
-            type: "publicKey";
-          },
-          {
-            name: "quoteMint";
-            type: "publicKey";
-          },
-          {
-            name: "baseMintDecimals";
-            type: "u8";
-          },
-          {
-            name: "quoteMintDecimals";
-            type: "u8";
-          },
-          {
-            name: "baseAmount";
-            type: "u64";
-          },
-          {
-            name: "quoteAmount";
-            type: "u64";
-          },
-          {
-            name: "oracle";
-            type: {
-              defined: "TwapOracle";
-            };
-          }
-        ];
-      };
-    }
-  ];
-  types: [
-    {
-      name: "AddLiquidityArgs";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "quoteAmount";
-            docs: ["How much quote token you will deposit to the pool"];
-            type: "u64";
-          },
-          {
-            name: "maxBaseAmount";
-            docs: ["The maximum base token you will deposit to the pool"];
-            type: "u64";
-          },
-          {
-            name: "minLpTokens";
-            docs: ["The minimum LP token you will get back"];
-            type: "u64";
-          }
-        ];
-      };
     },
     {
-      name: "CreateAmmArgs";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "twapInitialObservation";
-            type: "u128";
-          },
-          {
+      name: "createTwapCheckpoints";
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "amm";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "twapCheckpoints";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "collectProtocolFees";
+      accounts: [
+        {
+          name: "amm";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "baseMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "recipientAtaBase";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "recipientAtaQuote";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultAtaBase";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultAtaQuote";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "baseTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "closeAmm";
+      accounts: [
+        {
+          name: "amm";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "rentPayer";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "lpMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "baseMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "quoteMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultAtaBase";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultAtaQuote";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultAtaLp";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "twapCheckpoints";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ["Required if the AMM has TWAP checkpoints."];
+        },
+        {
+          name: "twapCheckpointsRentPayer";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "baseTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "quoteSwap";
+      accounts: [
+        {
+          name: "amm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "lpMint";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: "QuoteSwapArgs";
+          };
+        }
+      ];
+      returns: {
+        defined: "SwapQuote";
+      };
+    },
+    {
+      name: "quoteAddLiquidity";
+      accounts: [
+        {
+          name: "amm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "lpMint";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: "QuoteAddLiquidityArgs";
+          };
+        }
+      ];
+      returns: {
+        defined: "AddLiquidityQuote";
+      };
+    },
+    {
+      name: "quoteRemoveLiquidity";
+      accounts: [
+        {
+          name: "amm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "lpMint";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: "QuoteRemoveLiquidityArgs";
+          };
+        }
+      ];
+      returns: {
+        defined: "RemoveLiquidityQuote";
+      };
+    }
+  ];
+  accounts: [
+    {
+      name: "amm";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "proposal";
+            docs: [
+              "We need to create multiple AMMs for a single asset pair, but AMMs are PDAs.",
+              "So we can use proposal as a PDA seed."
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "createdAtSlot";
+            type: "u64";
+          },
+          {
+            name: "lpMint";
+            type: "publicKey";
+          },
+          {
+            name: "baseMint";
+            type: "publicKey";
+          },
+          {
+            name: "quoteMint";
+            type: "publicKey";
+          },
+          {
+            name: "baseMintDecimals";
+            type: "u8";
+          },
+          {
+            name: "quoteMintDecimals";
+            type: "u8";
+          },
+          {
+            name: "baseAmount";
+            type: "u64";
+          },
+          {
+            name: "quoteAmount";
+            type: "u64";
+          },
+          {
+            name: "swapFeeBps";
+            docs: [
+              "The fee charged on the input side of every swap, in basis points. It",
+              "stays in the pool and accrues to LPs."
+            ];
+            type: "u64";
+          },
+          {
+            name: "protocolFeeBps";
+            docs: [
+              "The share of every swap fee, in basis points, that goes to the protocol",
+              "instead of to LPs."
+            ];
+            type: "u64";
+          },
+          {
+            name: "protocolFeeRecipient";
+            docs: ["Who `collect_protocol_fees` pays out to."];
+            type: "publicKey";
+          },
+          {
+            name: "baseProtocolFees";
+            docs: [
+              "Protocol fees sit in the vaults alongside the reserves, but they aren't",
+              "part of `base_amount` / `quote_amount` so they don't affect pricing."
+            ];
+            type: "u64";
+          },
+          {
+            name: "quoteProtocolFees";
+            type: "u64";
+          },
+          {
+            name: "oracle";
+            type: {
+              defined: "TwapOracle";
+            };
+          },
+          {
+            name: "hasTwapCheckpoints";
+            docs: [
+              "Set by `create_twap_checkpoints`. From then on, every `crank_that_twap`",
+              "has to pass the checkpoints so that none of the cranks get skipped."
+            ];
+            type: "bool";
+          },
+          {
+            name: "seqNum";
+            docs: [
+              "Incremented on every state change and included in every event."
+            ];
+            type: "u64";
+          },
+          {
+            name: "rentPayer";
+            docs: [
+              "Who paid for this AMM's accounts, and gets the rent back on `close_amm`."
+            ];
+            type: "publicKey";
+          }
+        ];
+      };
+    },
+    {
+      name: "twapCheckpoints";
+      docs: [
+        "A ring buffer of `TwapOracle` checkpoints that lives alongside an AMM, so",
+        "that the TWAP between any two recorded slots can be computed on-chain.",
+        "",
+        "A checkpoint is recorded at most once per `TWAP_CHECKPOINT_INTERVAL_SLOTS`,",
+        "so the buffer covers `MAX_TWAP_CHECKPOINTS * TWAP_CHECKPOINT_INTERVAL_SLOTS`",
+        "slots (~4 days) before the oldest checkpoints start getting overwritten."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "amm";
+            type: "publicKey";
+          },
+          {
+            name: "rentPayer";
+            docs: [
+              "Who paid for this account, and gets the rent back when the AMM is closed."
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "nextIndex";
+            docs: [
+              "Where the next checkpoint will be written once `checkpoints` is full."
+            ];
+            type: "u16";
+          },
+          {
+            name: "checkpoints";
+            type: {
+              vec: {
+                defined: "TwapCheckpoint";
+              };
+            };
+          }
+        ];
+      };
+    }
+  ];
+  types: [
+    {
+      name: "CommonFields";
+      docs: [
+        "Fields shared by every AMM event. `seq_num` is bumped on every state change,",
+        "so indexers can tell if they've missed an event."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "slot";
+            type: "u64";
+          },
+          {
+            name: "unixTimestamp";
+            type: "i64";
+          },
+          {
+            name: "amm";
+            type: "publicKey";
+          },
+          {
+            name: "postBaseReserves";
+            type: "u64";
+          },
+          {
+            name: "postQuoteReserves";
+            type: "u64";
+          },
+          {
+            name: "oracleLastPrice";
+            type: "u128";
+          },
+          {
+            name: "oracleLastObservation";
+            type: "u128";
+          },
+          {
+            name: "oracleAggregator";
+            type: "u128";
+          },
+          {
+            name: "seqNum";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "AddLiquidityArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "quoteAmount";
+            docs: ["How much quote token you will deposit to the pool"];
+            type: "u64";
+          },
+          {
+            name: "maxBaseAmount";
+            docs: ["The maximum base token you will deposit to the pool"];
+            type: "u64";
+          },
+          {
+            name: "minLpTokens";
+            docs: ["The minimum LP token you will get back"];
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "CreateAmmArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "swapFeeBps";
+            docs: [
+              "Must be strictly less than `BPS_SCALE`, so 100 is a 1% fee."
+            ];
+            type: "u64";
+          },
+          {
+            name: "protocolFeeBps";
+            docs: [
+              "The share of the swap fee that goes to `protocol_fee_recipient`, at",
+              "most `BPS_SCALE`."
+            ];
+            type: "u64";
+          },
+          {
+            name: "protocolFeeRecipient";
+            type: "publicKey";
+          },
+          {
+            name: "twapInitialObservation";
+            type: "u128";
+          },
+          {
             name: "twapMaxObservationChangePerUpdate";
             type: "u128";
           },
           {
-            name: "proposal";
-            type: "publicKey";
+            name: "twapObservationClampMode";
+            type: {
+              defined: "ObservationClampMode";
+            };
+          },
+          {
+            name: "proposal";
+            type: "publicKey";
+          }
+        ];
+      };
+    },
+    {
+      name: "QuoteSwapArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "swapType";
+            type: {
+              defined: "SwapType";
+            };
+          },
+          {
+            name: "inputAmount";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "QuoteAddLiquidityArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "quoteAmount";
+            type: "u64";
+          },
+          {
+            name: "maxBaseAmount";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "QuoteRemoveLiquidityArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "lpTokensToBurn";
+            type: "u64";
           }
         ];
       };
@@ -447,6 +1120,120 @@ export type Amm = {
         ];
       };
     },
+    {
+      name: "SwapExactOutputArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "swapType";
+            type: {
+              defined: "SwapType";
+            };
+          },
+          {
+            name: "outputAmount";
+            type: "u64";
+          },
+          {
+            name: "inputAmountMax";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "SwapQuote";
+      docs: ["What a swap would do to a pool, as returned by `quote_swap`."];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "inputAmount";
+            type: "u64";
+          },
+          {
+            name: "outputAmount";
+            type: "u64";
+          },
+          {
+            name: "feeAmount";
+            docs: [
+              "The whole swap fee taken from `input_amount`, including the protocol's cut."
+            ];
+            type: "u64";
+          },
+          {
+            name: "protocolFeeAmount";
+            type: "u64";
+          },
+          {
+            name: "priceImpactBps";
+            docs: [
+              "How far the swap moves the pool price, in basis points of the pre-trade",
+              "price."
+            ];
+            type: "u64";
+          },
+          {
+            name: "postTradePrice";
+            docs: [
+              "The pool price after the swap, scaled the same way as `TwapOracle` prices."
+            ];
+            type: "u128";
+          }
+        ];
+      };
+    },
+    {
+      name: "AddLiquidityQuote";
+      docs: [
+        "What adding liquidity would do, as returned by `quote_add_liquidity`."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "quoteAmount";
+            type: "u64";
+          },
+          {
+            name: "baseAmount";
+            type: "u64";
+          },
+          {
+            name: "lpTokensMinted";
+            docs: [
+              "The LP tokens the depositor gets, net of any locked `MINIMUM_LIQUIDITY`."
+            ];
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "RemoveLiquidityQuote";
+      docs: [
+        "What removing liquidity would do, as returned by `quote_remove_liquidity`."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "lpTokensBurned";
+            type: "u64";
+          },
+          {
+            name: "baseAmount";
+            type: "u64";
+          },
+          {
+            name: "quoteAmount";
+            type: "u64";
+          }
+        ];
+      };
+    },
     {
       name: "TwapOracle";
       type: {
@@ -497,13 +1284,39 @@ export type Amm = {
           },
           {
             name: "maxObservationChangePerUpdate";
-            docs: ["The most that an observation can change per update."];
+            docs: [
+              "The most that an observation can change per update, interpreted",
+              "according to `observation_clamp_mode`."
+            ];
             type: "u128";
           },
           {
             name: "initialObservation";
             docs: ["What the initial `latest_observation` is set to."];
             type: "u128";
+          },
+          {
+            name: "observationClampMode";
+            type: {
+              defined: "ObservationClampMode";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "TwapCheckpoint";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "slot";
+            type: "u64";
+          },
+          {
+            name: "aggregator";
+            docs: ["The oracle's `aggregator` as of `slot`."];
+            type: "u128";
           }
         ];
       };
@@ -521,6 +1334,250 @@ export type Amm = {
           }
         ];
       };
+    },
+    {
+      name: "ObservationClampMode";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Absolute";
+          },
+          {
+            name: "Bps";
+          }
+        ];
+      };
+    }
+  ];
+  events: [
+    {
+      name: "CreateAmmEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "user";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "lpMint";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "baseMint";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "quoteMint";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "swapFeeBps";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "protocolFeeBps";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "twapInitialObservation";
+          type: "u128";
+          index: false;
+        },
+        {
+          name: "twapMaxObservationChangePerUpdate";
+          type: "u128";
+          index: false;
+        },
+        {
+          name: "twapObservationClampMode";
+          type: {
+            defined: "ObservationClampMode";
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "SwapEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "user";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "swapType";
+          type: {
+            defined: "SwapType";
+          };
+          index: false;
+        },
+        {
+          name: "inputAmount";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "outputAmount";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "AddLiquidityEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "user";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "quoteAmount";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "baseAmount";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "lpTokensMinted";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "RemoveLiquidityEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "user";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "lpTokensBurned";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "baseAmount";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "quoteAmount";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "CrankThatTwapEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "CloseAmmEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "rentPayer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "baseAmountBurned";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "quoteAmountBurned";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "CollectProtocolFeesEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "recipient";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "baseAmount";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "quoteAmount";
+          type: "u64";
+          index: false;
+        }
+      ];
     }
   ];
   errors: [
@@ -592,7 +1649,77 @@ export type Amm = {
     {
       code: 6013;
       name: "InsufficientQuoteAmount";
-      msg: "`quote_amount` must be greater than 100000000 when initializing a pool";
+      msg: "`quote_amount` must be at least `MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT` when initializing a pool";
+    },
+    {
+      code: 6014;
+      name: "InvalidSwapFee";
+      msg: "`swap_fee_bps` must be less than `BPS_SCALE`";
+    },
+    {
+      code: 6015;
+      name: "InvalidProtocolFee";
+      msg: "`protocol_fee_bps` can't be more than `BPS_SCALE`";
+    },
+    {
+      code: 6016;
+      name: "OutputAmountExceedsReserves";
+      msg: "Can't swap for as many or more tokens than a pool holds";
+    },
+    {
+      code: 6017;
+      name: "OutputAmountOverflow";
+      msg: "Output token amount is too large for a swap, causes overflow";
+    },
+    {
+      code: 6018;
+      name: "SwapInputMaxExceeded";
+      msg: "A user would have spent more than their `input_amount_max`, reverting";
+    },
+    {
+      code: 6019;
+      name: "InvalidTwapWindow";
+      msg: "A TWAP window must end after it starts";
+    },
+    {
+      code: 6020;
+      name: "TwapCheckpointNotFound";
+      msg: "There's no TWAP checkpoint recorded at that slot";
+    },
+    {
+      code: 6021;
+      name: "MissingTwapCheckpoints";
+      msg: "This AMM has TWAP checkpoints, so cranks must pass them";
+    },
+    {
+      code: 6022;
+      name: "InvalidObservationClamp";
+      msg: "A bps observation clamp needs a non-zero initial observation and a max change between 1 and `BPS_SCALE`";
+    },
+    {
+      code: 6023;
+      name: "TransferFeeCalculationError";
+      msg: "Couldn't calculate a Token-2022 transfer fee";
+    },
+    {
+      code: 6024;
+      name: "AmmNotEmpty";
+      msg: "Can't close an AMM while anyone besides the AMM itself holds its LP tokens";
+    },
+    {
+      code: 6025;
+      name: "UncollectedProtocolFees";
+      msg: "Protocol fees must be collected before an AMM can be closed";
+    },
+    {
+      code: 6026;
+      name: "AmmNeverFunded";
+      msg: "Can't close an AMM that has never had liquidity";
+    },
+    {
+      code: 6027;
+      name: "InvalidTwapCheckpointsRentPayer";
+      msg: "TWAP checkpoints' rent can only go back to whoever paid for them";
     }
   ];
 };
@@ -605,52 +1732,298 @@ export const IDL: Amm = {
       name: "createAmm",
       accounts: [
         {
-          name: "user",
+          name: "user",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "lpMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "baseMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaBase",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaQuote",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaLp",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Holds the LP tokens that get permanently locked on the first deposit.",
+          ],
+        },
+        {
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "The program that owns the LP mint. LP mints are always legacy SPL mints,",
+            "but the base and quote mints can be owned by either token program.",
+          ],
+        },
+        {
+          name: "baseTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "args",
+          type: {
+            defined: "CreateAmmArgs",
+          },
+        },
+      ],
+    },
+    {
+      name: "addLiquidity",
+      accounts: [
+        {
+          name: "user",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "lpMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "baseMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "userAtaLp",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userAtaBase",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userAtaQuote",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaBase",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaQuote",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaLp",
+          isMut: true,
+          isSigner: false,
+          docs: ["Holds the `MINIMUM_LIQUIDITY` locked on the first deposit."],
+        },
+        {
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "The program that owns the LP mint, which is always a legacy SPL mint.",
+          ],
+        },
+        {
+          name: "baseTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "args",
+          type: {
+            defined: "AddLiquidityArgs",
+          },
+        },
+      ],
+    },
+    {
+      name: "removeLiquidity",
+      accounts: [
+        {
+          name: "user",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "lpMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "baseMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "userAtaLp",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userAtaBase",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userAtaQuote",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaBase",
           isMut: true,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: "amm",
+          name: "vaultAtaQuote",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "lpMint",
+          name: "vaultAtaLp",
           isMut: true,
           isSigner: false,
+          docs: ["Holds the `MINIMUM_LIQUIDITY` locked on the first deposit."],
         },
         {
-          name: "baseMint",
+          name: "associatedTokenProgram",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "quoteMint",
+          name: "tokenProgram",
           isMut: false,
           isSigner: false,
+          docs: [
+            "The program that owns the LP mint, which is always a legacy SPL mint.",
+          ],
         },
         {
-          name: "vaultAtaBase",
-          isMut: true,
+          name: "baseTokenProgram",
+          isMut: false,
           isSigner: false,
         },
         {
-          name: "vaultAtaQuote",
-          isMut: true,
+          name: "quoteTokenProgram",
+          isMut: false,
           isSigner: false,
         },
         {
-          name: "associatedTokenProgram",
+          name: "systemProgram",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "tokenProgram",
+          name: "eventAuthority",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "systemProgram",
+          name: "program",
           isMut: false,
           isSigner: false,
         },
@@ -659,13 +2032,13 @@ export const IDL: Amm = {
         {
           name: "args",
           type: {
-            defined: "CreateAmmArgs",
+            defined: "RemoveLiquidityArgs",
           },
         },
       ],
     },
     {
-      name: "addLiquidity",
+      name: "swap",
       accounts: [
         {
           name: "user",
@@ -677,11 +2050,6 @@ export const IDL: Amm = {
           isMut: true,
           isSigner: false,
         },
-        {
-          name: "lpMint",
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: "baseMint",
           isMut: false,
@@ -692,11 +2060,6 @@ export const IDL: Amm = {
           isMut: false,
           isSigner: false,
         },
-        {
-          name: "userAtaLp",
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: "userAtaBase",
           isMut: true,
@@ -723,7 +2086,12 @@ export const IDL: Amm = {
           isSigner: false,
         },
         {
-          name: "tokenProgram",
+          name: "baseTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteTokenProgram",
           isMut: false,
           isSigner: false,
         },
@@ -732,18 +2100,28 @@ export const IDL: Amm = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
       ],
       args: [
         {
           name: "args",
           type: {
-            defined: "AddLiquidityArgs",
+            defined: "SwapArgs",
           },
         },
       ],
     },
     {
-      name: "removeLiquidity",
+      name: "swapExactOutput",
       accounts: [
         {
           name: "user",
@@ -755,11 +2133,6 @@ export const IDL: Amm = {
           isMut: true,
           isSigner: false,
         },
-        {
-          name: "lpMint",
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: "baseMint",
           isMut: false,
@@ -770,11 +2143,6 @@ export const IDL: Amm = {
           isMut: false,
           isSigner: false,
         },
-        {
-          name: "userAtaLp",
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: "userAtaBase",
           isMut: true,
@@ -801,7 +2169,12 @@ export const IDL: Amm = {
           isSigner: false,
         },
         {
-          name: "tokenProgram",
+          name: "baseTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteTokenProgram",
           isMut: false,
           isSigner: false,
         },
@@ -810,24 +2183,86 @@ export const IDL: Amm = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
       ],
       args: [
         {
           name: "args",
           type: {
-            defined: "RemoveLiquidityArgs",
+            defined: "SwapExactOutputArgs",
           },
         },
       ],
     },
     {
-      name: "swap",
+      name: "crankThatTwap",
       accounts: [
         {
-          name: "user",
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "twapCheckpoints",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            "The oracle's new state gets checkpointed here. Required once the AMM",
+            "has checkpoints.",
+          ],
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "createTwapCheckpoints",
+      accounts: [
+        {
+          name: "payer",
           isMut: true,
           isSigner: true,
         },
+        {
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "twapCheckpoints",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "collectProtocolFees",
+      accounts: [
         {
           name: "amm",
           isMut: true,
@@ -844,12 +2279,12 @@ export const IDL: Amm = {
           isSigner: false,
         },
         {
-          name: "userAtaBase",
+          name: "recipientAtaBase",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "userAtaQuote",
+          name: "recipientAtaQuote",
           isMut: true,
           isSigner: false,
         },
@@ -864,17 +2299,174 @@ export const IDL: Amm = {
           isSigner: false,
         },
         {
-          name: "associatedTokenProgram",
+          name: "baseTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteTokenProgram",
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "closeAmm",
+      accounts: [
+        {
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "rentPayer",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "lpMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "baseMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "quoteMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaBase",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaQuote",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaLp",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "twapCheckpoints",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: ["Required if the AMM has TWAP checkpoints."],
+        },
+        {
+          name: "twapCheckpointsRentPayer",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
         {
           name: "tokenProgram",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "systemProgram",
+          name: "baseTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "quoteSwap",
+      accounts: [
+        {
+          name: "amm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "lpMint",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "args",
+          type: {
+            defined: "QuoteSwapArgs",
+          },
+        },
+      ],
+      returns: {
+        defined: "SwapQuote",
+      },
+    },
+    {
+      name: "quoteAddLiquidity",
+      accounts: [
+        {
+          name: "amm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "lpMint",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "args",
+          type: {
+            defined: "QuoteAddLiquidityArgs",
+          },
+        },
+      ],
+      returns: {
+        defined: "AddLiquidityQuote",
+      },
+    },
+    {
+      name: "quoteRemoveLiquidity",
+      accounts: [
+        {
+          name: "amm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "lpMint",
           isMut: false,
           isSigner: false,
         },
@@ -883,21 +2475,13 @@ export const IDL: Amm = {
         {
           name: "args",
           type: {
-            defined: "SwapArgs",
+            defined: "QuoteRemoveLiquidityArgs",
           },
         },
       ],
-    },
-    {
-      name: "crankThatTwap",
-      accounts: [
-        {
-          name: "amm",
-          isMut: true,
-          isSigner: false,
-        },
-      ],
-      args: [],
+      returns: {
+        defined: "RemoveLiquidityQuote",
+      },
     },
   ],
   accounts: [
@@ -939,108 +2523,438 @@ export const IDL: Amm = {
             type: "u8",
           },
           {
-            name: "quoteMintDecimals",
-            type: "u8",
+            name: "quoteMintDecimals",
+            type: "u8",
+          },
+          {
+            name: "baseAmount",
+            type: "u64",
+          },
+          {
+            name: "quoteAmount",
+            type: "u64",
+          },
+          {
+            name: "swapFeeBps",
+            docs: [
+              "The fee charged on the input side of every swap, in basis points. It",
+              "stays in the pool and accrues to LPs.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "protocolFeeBps",
+            docs: [
+              "The share of every swap fee, in basis points, that goes to the protocol",
+              "instead of to LPs.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "protocolFeeRecipient",
+            docs: ["Who `collect_protocol_fees` pays out to."],
+            type: "publicKey",
+          },
+          {
+            name: "baseProtocolFees",
+            docs: [
+              "Protocol fees sit in the vaults alongside the reserves, but they aren't",
+              "part of `base_amount` / `quote_amount` so they don't affect pricing.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "quoteProtocolFees",
+            type: "u64",
+          },
+          {
+            name: "oracle",
+            type: {
+              defined: "TwapOracle",
+            },
+          },
+          {
+            name: "hasTwapCheckpoints",
+            docs: [
+              "Set by `create_twap_checkpoints`. From then on, every `crank_that_twap`",
+              "has to pass the checkpoints so that none of the cranks get skipped.",
+            ],
+            type: "bool",
+          },
+          {
+            name: "seqNum",
+            docs: [
+              "Incremented on every state change and included in every event.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "rentPayer",
+            docs: [
+              "Who paid for this AMM's accounts, and gets the rent back on `close_amm`.",
+            ],
+            type: "publicKey",
+          },
+        ],
+      },
+    },
+    {
+      name: "twapCheckpoints",
+      docs: [
+        "A ring buffer of `TwapOracle` checkpoints that lives alongside an AMM, so",
+        "that the TWAP between any two recorded slots can be computed on-chain.",
+        "",
+        "A checkpoint is recorded at most once per `TWAP_CHECKPOINT_INTERVAL_SLOTS`,",
+        "so the buffer covers `MAX_TWAP_CHECKPOINTS * TWAP_CHECKPOINT_INTERVAL_SLOTS`",
+        "slots (~4 days) before the oldest checkpoints start getting overwritten.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "amm",
+            type: "publicKey",
+          },
+          {
+            name: "rentPayer",
+            docs: [
+              "Who paid for this account, and gets the rent back when the AMM is closed.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "nextIndex",
+            docs: [
+              "Where the next checkpoint will be written once `checkpoints` is full.",
+            ],
+            type: "u16",
+          },
+          {
+            name: "checkpoints",
+            type: {
+              vec: {
+                defined: "TwapCheckpoint",
+              },
+            },
+          },
+        ],
+      },
+    },
+  ],
+  types: [
+    {
+      name: "CommonFields",
+      docs: [
+        "Fields shared by every AMM event. `seq_num` is bumped on every state change,",
+        "so indexers can tell if they've missed an event.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "slot",
+            type: "u64",
+          },
+          {
+            name: "unixTimestamp",
+            type: "i64",
+          },
+          {
+            name: "amm",
+            type: "publicKey",
+          },
+          {
+            name: "postBaseReserves",
+            type: "u64",
+          },
+          {
+            name: "postQuoteReserves",
+            type: "u64",
+          },
+          {
+            name: "oracleLastPrice",
+            type: "u128",
+          },
+          {
+            name: "oracleLastObservation",
+            type: "u128",
+          },
+          {
+            name: "oracleAggregator",
+            type: "u128",
+          },
+          {
+            name: "seqNum",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "AddLiquidityArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "quoteAmount",
+            docs: ["How much quote token you will deposit to the pool"],
+            type: "u64",
+          },
+          {
+            name: "maxBaseAmount",
+            docs: ["The maximum base token you will deposit to the pool"],
+            type: "u64",
+          },
+          {
+            name: "minLpTokens",
+            docs: ["The minimum LP token you will get back"],
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "CreateAmmArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "swapFeeBps",
+            docs: [
+              "Must be strictly less than `BPS_SCALE`, so 100 is a 1% fee.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "protocolFeeBps",
+            docs: [
+              "The share of the swap fee that goes to `protocol_fee_recipient`, at",
+              "most `BPS_SCALE`.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "protocolFeeRecipient",
+            type: "publicKey",
+          },
+          {
+            name: "twapInitialObservation",
+            type: "u128",
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate",
+            type: "u128",
+          },
+          {
+            name: "twapObservationClampMode",
+            type: {
+              defined: "ObservationClampMode",
+            },
+          },
+          {
+            name: "proposal",
+            type: "publicKey",
+          },
+        ],
+      },
+    },
+    {
+      name: "QuoteSwapArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "swapType",
+            type: {
+              defined: "SwapType",
+            },
+          },
+          {
+            name: "inputAmount",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "QuoteAddLiquidityArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "quoteAmount",
+            type: "u64",
+          },
+          {
+            name: "maxBaseAmount",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "QuoteRemoveLiquidityArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "lpTokensToBurn",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "RemoveLiquidityArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "lpTokensToBurn",
+            type: "u64",
+          },
+          {
+            name: "minQuoteAmount",
+            type: "u64",
+          },
+          {
+            name: "minBaseAmount",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "SwapArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "swapType",
+            type: {
+              defined: "SwapType",
+            },
           },
           {
-            name: "baseAmount",
+            name: "inputAmount",
             type: "u64",
           },
           {
-            name: "quoteAmount",
+            name: "outputAmountMin",
             type: "u64",
           },
-          {
-            name: "oracle",
-            type: {
-              defined: "TwapOracle",
-            },
-          },
         ],
       },
     },
-  ],
-  types: [
     {
-      name: "AddLiquidityArgs",
+      name: "SwapExactOutputArgs",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "quoteAmount",
-            docs: ["How much quote token you will deposit to the pool"],
-            type: "u64",
+            name: "swapType",
+            type: {
+              defined: "SwapType",
+            },
           },
           {
-            name: "maxBaseAmount",
-            docs: ["The maximum base token you will deposit to the pool"],
+            name: "outputAmount",
             type: "u64",
           },
           {
-            name: "minLpTokens",
-            docs: ["The minimum LP token you will get back"],
+            name: "inputAmountMax",
             type: "u64",
           },
         ],
       },
     },
     {
-      name: "CreateAmmArgs",
+      name: "SwapQuote",
+      docs: ["What a swap would do to a pool, as returned by `quote_swap`."],
       type: {
         kind: "struct",
         fields: [
           {
-            name: "twapInitialObservation",
-            type: "u128",
+            name: "inputAmount",
+            type: "u64",
           },
           {
-            name: "twapMaxObservationChangePerUpdate",
-            type: "u128",
+            name: "outputAmount",
+            type: "u64",
           },
           {
-            name: "proposal",
-            type: "publicKey",
+            name: "feeAmount",
+            docs: [
+              "The whole swap fee taken from `input_amount`, including the protocol's cut.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "protocolFeeAmount",
+            type: "u64",
+          },
+          {
+            name: "priceImpactBps",
+            docs: [
+              "How far the swap moves the pool price, in basis points of the pre-trade",
+              "price.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "postTradePrice",
+            docs: [
+              "The pool price after the swap, scaled the same way as `TwapOracle` prices.",
+            ],
+            type: "u128",
           },
         ],
       },
     },
     {
-      name: "RemoveLiquidityArgs",
+      name: "AddLiquidityQuote",
+      docs: [
+        "What adding liquidity would do, as returned by `quote_add_liquidity`.",
+      ],
       type: {
         kind: "struct",
         fields: [
           {
-            name: "lpTokensToBurn",
+            name: "quoteAmount",
             type: "u64",
           },
           {
-            name: "minQuoteAmount",
+            name: "baseAmount",
             type: "u64",
           },
           {
-            name: "minBaseAmount",
+            name: "lpTokensMinted",
+            docs: [
+              "The LP tokens the depositor gets, net of any locked `MINIMUM_LIQUIDITY`.",
+            ],
             type: "u64",
           },
         ],
       },
     },
     {
-      name: "SwapArgs",
+      name: "RemoveLiquidityQuote",
+      docs: [
+        "What removing liquidity would do, as returned by `quote_remove_liquidity`.",
+      ],
       type: {
         kind: "struct",
         fields: [
           {
-            name: "swapType",
-            type: {
-              defined: "SwapType",
-            },
+            name: "lpTokensBurned",
+            type: "u64",
           },
           {
-            name: "inputAmount",
+            name: "baseAmount",
             type: "u64",
           },
           {
-            name: "outputAmountMin",
+            name: "quoteAmount",
             type: "u64",
           },
         ],
@@ -1096,7 +3010,10 @@ export const IDL: Amm = {
           },
           {
             name: "maxObservationChangePerUpdate",
-            docs: ["The most that an observation can change per update."],
+            docs: [
+              "The most that an observation can change per update, interpreted",
+              "according to `observation_clamp_mode`.",
+            ],
             type: "u128",
           },
           {
@@ -1104,6 +3021,29 @@ export const IDL: Amm = {
             docs: ["What the initial `latest_observation` is set to."],
             type: "u128",
           },
+          {
+            name: "observationClampMode",
+            type: {
+              defined: "ObservationClampMode",
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "TwapCheckpoint",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "slot",
+            type: "u64",
+          },
+          {
+            name: "aggregator",
+            docs: ["The oracle's `aggregator` as of `slot`."],
+            type: "u128",
+          },
         ],
       },
     },
@@ -1121,6 +3061,250 @@ export const IDL: Amm = {
         ],
       },
     },
+    {
+      name: "ObservationClampMode",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Absolute",
+          },
+          {
+            name: "Bps",
+          },
+        ],
+      },
+    },
+  ],
+  events: [
+    {
+      name: "CreateAmmEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
+          },
+          index: false,
+        },
+        {
+          name: "user",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "lpMint",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "baseMint",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "quoteMint",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "swapFeeBps",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "protocolFeeBps",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "twapInitialObservation",
+          type: "u128",
+          index: false,
+        },
+        {
+          name: "twapMaxObservationChangePerUpdate",
+          type: "u128",
+          index: false,
+        },
+        {
+          name: "twapObservationClampMode",
+          type: {
+            defined: "ObservationClampMode",
+          },
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "SwapEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
+          },
+          index: false,
+        },
+        {
+          name: "user",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "swapType",
+          type: {
+            defined: "SwapType",
+          },
+          index: false,
+        },
+        {
+          name: "inputAmount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "outputAmount",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "AddLiquidityEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
+          },
+          index: false,
+        },
+        {
+          name: "user",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "quoteAmount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "baseAmount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "lpTokensMinted",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "RemoveLiquidityEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
+          },
+          index: false,
+        },
+        {
+          name: "user",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "lpTokensBurned",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "baseAmount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "quoteAmount",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "CrankThatTwapEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
+          },
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "CloseAmmEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
+          },
+          index: false,
+        },
+        {
+          name: "rentPayer",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "baseAmountBurned",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "quoteAmountBurned",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "CollectProtocolFeesEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
+          },
+          index: false,
+        },
+        {
+          name: "recipient",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "baseAmount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "quoteAmount",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
   ],
   errors: [
     {
@@ -1191,7 +3375,77 @@ export const IDL: Amm = {
     {
       code: 6013,
       name: "InsufficientQuoteAmount",
-      msg: "`quote_amount` must be greater than 100000000 when initializing a pool",
+      msg: "`quote_amount` must be at least `MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT` when initializing a pool",
+    },
+    {
+      code: 6014,
+      name: "InvalidSwapFee",
+      msg: "`swap_fee_bps` must be less than `BPS_SCALE`",
+    },
+    {
+      code: 6015,
+      name: "InvalidProtocolFee",
+      msg: "`protocol_fee_bps` can't be more than `BPS_SCALE`",
+    },
+    {
+      code: 6016,
+      name: "OutputAmountExceedsReserves",
+      msg: "Can't swap for as many or more tokens than a pool holds",
+    },
+    {
+      code: 6017,
+      name: "OutputAmountOverflow",
+      msg: "Output token amount is too large for a swap, causes overflow",
+    },
+    {
+      code: 6018,
+      name: "SwapInputMaxExceeded",
+      msg: "A user would have spent more than their `input_amount_max`, reverting",
+    },
+    {
+      code: 6019,
+      name: "InvalidTwapWindow",
+      msg: "A TWAP window must end after it starts",
+    },
+    {
+      code: 6020,
+      name: "TwapCheckpointNotFound",
+      msg: "There's no TWAP checkpoint recorded at that slot",
+    },
+    {
+      code: 6021,
+      name: "MissingTwapCheckpoints",
+      msg: "This AMM has TWAP checkpoints, so cranks must pass them",
+    },
+    {
+      code: 6022,
+      name: "InvalidObservationClamp",
+      msg: "A bps observation clamp needs a non-zero initial observation and a max change between 1 and `BPS_SCALE`",
+    },
+    {
+      code: 6023,
+      name: "TransferFeeCalculationError",
+      msg: "Couldn't calculate a Token-2022 transfer fee",
+    },
+    {
+      code: 6024,
+      name: "AmmNotEmpty",
+      msg: "Can't close an AMM while anyone besides the AMM itself holds its LP tokens",
+    },
+    {
+      code: 6025,
+      name: "UncollectedProtocolFees",
+      msg: "Protocol fees must be collected before an AMM can be closed",
+    },
+    {
+      code: 6026,
+      name: "AmmNeverFunded",
+      msg: "Can't close an AMM that has never had liquidity",
+    },
+    {
+      code: 6027,
+      name: "InvalidTwapCheckpointsRentPayer",
+      msg: "TWAP checkpoints' rent can only go back to whoever paid for them",
     },
   ],
 };
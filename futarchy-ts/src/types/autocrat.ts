@@ -208,8 +208,376 @@ export type Autocrat = {
           isSigner: false;
         }
       ];
+      args: [
+        {
+          name: "maxInstructions";
+          type: "u32";
+        }
+      ];
+    },
+    {
+      name: "expireProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "extendProposalInstructions";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "passAmm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "failAmm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "proposer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "instructions";
+          type: {
+            vec: {
+              defined: "ProposalInstruction";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "withdrawProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposer";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "passAmm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "failAmm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "baseVault";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "quoteVault";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "passLpUserAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "failLpUserAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "passLpVaultAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "failLpVaultAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
       args: [];
     },
+    {
+      name: "splitAndSwap";
+      accounts: [
+        {
+          name: "user";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "proposal";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "amm";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "conditionalOnRevertTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "counterpartMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userCounterpartTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammVaultAtaBase";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammVaultAtaQuote";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammEventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "counterpartTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "associatedTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "ammProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: "SplitAndSwapArgs";
+          };
+        }
+      ];
+    },
+    {
+      name: "mergeAndExit";
+      accounts: [
+        {
+          name: "user";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "proposal";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "amm";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "conditionalOnRevertTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "counterpartMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userCounterpartTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammVaultAtaBase";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammVaultAtaQuote";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammEventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "counterpartTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "associatedTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "ammProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: "MergeAndExitArgs";
+          };
+        }
+      ];
+    },
     {
       name: "updateDao";
       accounts: [
@@ -292,6 +660,18 @@ export type Autocrat = {
             name: "twapMaxObservationChangePerUpdate";
             type: "u128";
           },
+          {
+            name: "twapObservationClampMode";
+            docs: [
+              "Whether `twap_max_observation_change_per_update` is an absolute change or",
+              "is in basis points of the last observation. In bps mode, 200 lets the",
+              "observation move 2% per update without having to be re-tuned as the",
+              "token's price moves."
+            ];
+            type: {
+              defined: "ObservationClampMode";
+            };
+          },
           {
             name: "minQuoteFutarchicLiquidity";
             docs: [
@@ -307,6 +687,56 @@ export type Autocrat = {
           {
             name: "minBaseFutarchicLiquidity";
             type: "u64";
+          },
+          {
+            name: "swapFeeBps";
+            docs: [
+              "The swap fee, in basis points, that both of a proposal's AMMs must charge."
+            ];
+            type: "u64";
+          },
+          {
+            name: "protocolFeeBps";
+            docs: [
+              "The share of the swap fee, in basis points, that both of a proposal's AMMs",
+              "must send to the treasury."
+            ];
+            type: "u64";
+          },
+          {
+            name: "minSettleGraceSlots";
+            docs: [
+              "If a proposal's vaults have a `settle_deadline`, it has to leave at least",
+              "this long after the proposal can be finalized for someone to finalize it.",
+              "Otherwise, anyone could revert the vaults and fail the proposal."
+            ];
+            type: "u64";
+          },
+          {
+            name: "executionDelaySlots";
+            docs: [
+              "How many slots a proposal has to wait after passing before it can be",
+              "executed, so that holders have time to react to a malicious proposal",
+              "that passed anyway."
+            ];
+            type: "u64";
+          },
+          {
+            name: "executionWindowSlots";
+            docs: [
+              "How many slots a passed proposal can be executed in once its execution",
+              "delay is over. After that, it can only be expired. `u64::MAX` means",
+              "that passed proposals never expire."
+            ];
+            type: "u64";
+          },
+          {
+            name: "withdrawalWindowSlots";
+            docs: [
+              "How many slots a proposer has to withdraw their proposal after creating",
+              "it. 0 means that proposals can't be withdrawn."
+            ];
+            type: "u64";
           }
         ];
       };
@@ -339,11 +769,45 @@ export type Autocrat = {
             };
           },
           {
-            name: "instruction";
+            name: "passedAtSlot";
+            docs: [
+              "The slot that `finalize_proposal` passed the proposal in, or 0 if it",
+              "hasn't passed."
+            ];
+            type: "u64";
+          },
+          {
+            name: "executableAtSlot";
+            docs: [
+              "The first slot that a passed proposal can be executed in, fixed from",
+              "the DAO's `execution_delay_slots` when it passes."
+            ];
+            type: "u64";
+          },
+          {
+            name: "expiresAtSlot";
+            docs: [
+              "The first slot that a passed proposal can no longer be executed in,",
+              "fixed from the DAO's `execution_window_slots` when it passes."
+            ];
+            type: "u64";
+          },
+          {
+            name: "instructions";
+            docs: [
+              "Executed in order by `execute_proposal`, over as many calls as it takes."
+            ];
             type: {
-              defined: "ProposalInstruction";
+              vec: {
+                defined: "ProposalInstruction";
+              };
             };
           },
+          {
+            name: "executedInstructionIndex";
+            docs: ["How many of `instructions` have been executed so far."];
+            type: "u32";
+          },
           {
             name: "passAmm";
             type: "publicKey";
@@ -371,6 +835,18 @@ export type Autocrat = {
           {
             name: "failLpTokensLocked";
             type: "u64";
+          },
+          {
+            name: "passAmmSeqNum";
+            docs: [
+              "The AMMs' `seq_num`s when the proposal was created. Instructions can",
+              "only be appended while both are unchanged, i.e. before anyone uses them."
+            ];
+            type: "u64";
+          },
+          {
+            name: "failAmmSeqNum";
+            type: "u64";
           }
         ];
       };
@@ -390,6 +866,14 @@ export type Autocrat = {
             name: "twapMaxObservationChangePerUpdate";
             type: "u128";
           },
+          {
+            name: "twapObservationClampMode";
+            type: {
+              option: {
+                defined: "ObservationClampMode";
+              };
+            };
+          },
           {
             name: "minQuoteFutarchicLiquidity";
             type: "u64";
@@ -409,6 +893,42 @@ export type Autocrat = {
             type: {
               option: "u64";
             };
+          },
+          {
+            name: "swapFeeBps";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "protocolFeeBps";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "minSettleGraceSlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "executionDelaySlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "executionWindowSlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "withdrawalWindowSlots";
+            type: {
+              option: "u64";
+            };
           }
         ];
       };
@@ -423,9 +943,11 @@ export type Autocrat = {
             type: "string";
           },
           {
-            name: "instruction";
+            name: "instructions";
             type: {
-              defined: "ProposalInstruction";
+              vec: {
+                defined: "ProposalInstruction";
+              };
             };
           },
           {
@@ -433,39 +955,132 @@ export type Autocrat = {
             type: "u64";
           },
           {
-            name: "failLpTokensToLock";
-            type: "u64";
-          }
-        ];
-      };
-    },
-    {
-      name: "UpdateDaoParams";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "passThresholdBps";
+            name: "failLpTokensToLock";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "MergeAndExitArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "counterpartAmount";
+            docs: [
+              "How many `counterpart_mint` tokens to swap back into the vault's",
+              "conditional tokens before merging, can be 0"
+            ];
+            type: "u64";
+          },
+          {
+            name: "extraMergeAmount";
+            docs: [
+              "How many pairs of conditional tokens the user already holds to merge",
+              "along with whatever the swap produces, can be 0"
+            ];
+            type: "u64";
+          },
+          {
+            name: "minUnderlyingAmount";
+            docs: ["The minimum underlying tokens the user must get back"];
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "SplitAndSwapArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "underlyingAmount";
+            docs: ["How many underlying tokens to deposit into the vault"];
+            type: "u64";
+          },
+          {
+            name: "minOutputAmount";
+            docs: [
+              "The minimum `counterpart_mint` tokens the user must end up with"
+            ];
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "UpdateDaoParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "passThresholdBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "slotsPerProposal";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "twapInitialObservation";
+            type: {
+              option: "u128";
+            };
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate";
+            type: {
+              option: "u128";
+            };
+          },
+          {
+            name: "swapFeeBps";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "protocolFeeBps";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "minSettleGraceSlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "twapObservationClampMode";
             type: {
-              option: "u16";
+              option: {
+                defined: "ObservationClampMode";
+              };
             };
           },
           {
-            name: "slotsPerProposal";
+            name: "executionDelaySlots";
             type: {
               option: "u64";
             };
           },
           {
-            name: "twapInitialObservation";
+            name: "executionWindowSlots";
             type: {
-              option: "u128";
+              option: "u64";
             };
           },
           {
-            name: "twapMaxObservationChangePerUpdate";
+            name: "withdrawalWindowSlots";
             type: {
-              option: "u128";
+              option: "u64";
             };
           }
         ];
@@ -515,6 +1130,24 @@ export type Autocrat = {
         ];
       };
     },
+    {
+      name: "ObservationClampMode";
+      docs: [
+        "Mirrors the amm's `ObservationClampMode`, so that autocrat's IDL defines",
+        "the type that its DAOs store."
+      ];
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Absolute";
+          },
+          {
+            name: "Bps";
+          }
+        ];
+      };
+    },
     {
       name: "ProposalState";
       type: {
@@ -531,6 +1164,12 @@ export type Autocrat = {
           },
           {
             name: "Executed";
+          },
+          {
+            name: "Expired";
+          },
+          {
+            name: "Withdrawn";
           }
         ];
       };
@@ -583,203 +1222,675 @@ export type Autocrat = {
       msg: "This proposal can't be executed because it isn't in the passed state";
     },
     {
-      code: 6009;
-      name: "InsufficientLpTokenBalance";
-      msg: "The proposer has fewer pass or fail LP tokens than they requested to lock";
+      code: 6009;
+      name: "InsufficientLpTokenBalance";
+      msg: "The proposer has fewer pass or fail LP tokens than they requested to lock";
+    },
+    {
+      code: 6010;
+      name: "InsufficientLpTokenLock";
+      msg: "The LP tokens passed in have less liquidity than the DAO's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`";
+    },
+    {
+      code: 6011;
+      name: "SwapFeeTooHigh";
+      msg: "`swap_fee_bps` must be less than `BPS_SCALE`";
+    },
+    {
+      code: 6012;
+      name: "InvalidSwapFee";
+      msg: "An amm has a `swap_fee_bps` that doesn't match the `dao`'s config";
+    },
+    {
+      code: 6013;
+      name: "ProtocolFeeTooHigh";
+      msg: "`protocol_fee_bps` must be at most `BPS_SCALE`";
+    },
+    {
+      code: 6014;
+      name: "InvalidProtocolFee";
+      msg: "An amm has a `protocol_fee_bps` that doesn't match the `dao`'s config";
+    },
+    {
+      code: 6015;
+      name: "InvalidProtocolFeeRecipient";
+      msg: "An amm's `protocol_fee_recipient` isn't the `dao`'s treasury";
+    },
+    {
+      code: 6016;
+      name: "InvalidObservationClampMode";
+      msg: "An amm has an `observation_clamp_mode` that doesn't match the `dao`'s config";
+    },
+    {
+      code: 6017;
+      name: "InvalidObservationClamp";
+      msg: "A bps observation clamp needs a non-zero initial observation and a max change between 1 and `BPS_SCALE`";
+    },
+    {
+      code: 6018;
+      name: "InvalidRouteVault";
+      msg: "A route's vault must be one of the proposal's vaults";
+    },
+    {
+      code: 6019;
+      name: "InvalidRouteAmm";
+      msg: "A route's amm must be one of the proposal's amms";
+    },
+    {
+      code: 6020;
+      name: "ZeroRouteAmount";
+      msg: "Must route a non-zero amount";
+    },
+    {
+      code: 6021;
+      name: "RouteSlippageExceeded";
+      msg: "A route wouldn't have given the user their minimum output, reverting";
+    },
+    {
+      code: 6022;
+      name: "SettleDeadlineTooEarly";
+      msg: "A vault's `settle_deadline` doesn't leave the DAO's `min_settle_grace_slots` to finalize the proposal";
+    },
+    {
+      code: 6023;
+      name: "NoProposalInstructions";
+      msg: "A proposal needs at least one instruction";
+    },
+    {
+      code: 6024;
+      name: "ZeroInstructionsToExecute";
+      msg: "Must execute at least one instruction";
+    },
+    {
+      code: 6025;
+      name: "ProposalMarketsAlreadyActive";
+      msg: "Instructions can only be added to a proposal before anyone uses its markets";
+    },
+    {
+      code: 6026;
+      name: "ExecutionDelayNotElapsed";
+      msg: "A passed proposal can't be executed until the DAO's `execution_delay_slots` have elapsed";
+    },
+    {
+      code: 6027;
+      name: "ProposalExpired";
+      msg: "This proposal wasn't executed within the DAO's `execution_window_slots`";
+    },
+    {
+      code: 6028;
+      name: "ProposalNotExpired";
+      msg: "This proposal can still be executed, so it can't be expired";
+    },
+    {
+      code: 6029;
+      name: "ProposalWithdrawalWindowOver";
+      msg: "A proposal can only be withdrawn by its proposer, within the DAO's `withdrawal_window_slots` of being created";
+    },
+    {
+      code: 6030;
+      name: "WithdrawalWindowTooLong";
+      msg: "A DAO's `withdrawal_window_slots` must be shorter than its `slots_per_proposal`";
+    }
+  ];
+};
+
+export const IDL: Autocrat = {
+  version: "1.0.0",
+  name: "autocrat",
+  instructions: [
+    {
+      name: "initializeDao",
+      accounts: [
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "usdcMint",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "InitializeDaoParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "initializeProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "quoteVault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "baseVault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "passAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "passLpMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "failLpMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "failAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "passLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "passLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "InitializeProposalParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "finalizeProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "passAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "failAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "baseVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "quoteVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "passLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "passLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "executeProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "maxInstructions",
+          type: "u32",
+        },
+      ],
     },
     {
-      code: 6010;
-      name: "InsufficientLpTokenLock";
-      msg: "The LP tokens passed in have less liquidity than the DAO's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`";
-    }
-  ];
-};
-
-export const IDL: Autocrat = {
-  version: "1.0.0",
-  name: "autocrat",
-  instructions: [
-    {
-      name: "initializeDao",
+      name: "expireProposal",
       accounts: [
         {
-          name: "dao",
+          name: "proposal",
           isMut: true,
-          isSigner: true,
+          isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: "extendProposalInstructions",
+      accounts: [
         {
-          name: "payer",
+          name: "proposal",
           isMut: true,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: "systemProgram",
+          name: "passAmm",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "tokenMint",
+          name: "failAmm",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "usdcMint",
+          name: "proposer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
           isMut: false,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: "params",
+          name: "instructions",
           type: {
-            defined: "InitializeDaoParams",
+            vec: {
+              defined: "ProposalInstruction",
+            },
           },
         },
       ],
     },
     {
-      name: "initializeProposal",
+      name: "withdrawProposal",
       accounts: [
         {
           name: "proposal",
           isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposer",
+          isMut: false,
           isSigner: true,
         },
+        {
+          name: "passAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "failAmm",
+          isMut: false,
+          isSigner: false,
+        },
         {
           name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "baseVault",
           isMut: true,
           isSigner: false,
         },
         {
           name: "quoteVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "baseVault",
+          name: "passLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "passLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "passAmm",
+          name: "vaultProgram",
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: "splitAndSwap",
+      accounts: [
         {
-          name: "passLpMint",
+          name: "user",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "proposal",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "failLpMint",
+          name: "vault",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "failAmm",
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "counterpartMint",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "passLpUserAccount",
+          name: "vaultUnderlyingTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "failLpUserAccount",
+          name: "userUnderlyingTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "passLpVaultAccount",
+          name: "userConditionalOnFinalizeTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "failLpVaultAccount",
+          name: "userConditionalOnRevertTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "proposer",
+          name: "userCounterpartTokenAccount",
           isMut: true,
-          isSigner: true,
+          isSigner: false,
+        },
+        {
+          name: "ammVaultAtaBase",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "ammVaultAtaQuote",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "ammEventAuthority",
+          isMut: false,
+          isSigner: false,
         },
         {
           name: "tokenProgram",
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "counterpartTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "ammProgram",
+          isMut: false,
+          isSigner: false,
+        },
       ],
       args: [
         {
-          name: "params",
+          name: "args",
           type: {
-            defined: "InitializeProposalParams",
+            defined: "SplitAndSwapArgs",
           },
         },
       ],
     },
     {
-      name: "finalizeProposal",
+      name: "mergeAndExit",
       accounts: [
         {
-          name: "proposal",
+          name: "user",
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: "passAmm",
+          name: "proposal",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "failAmm",
+          name: "vault",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "dao",
-          isMut: false,
+          name: "amm",
+          isMut: true,
           isSigner: false,
         },
         {
-          name: "baseVault",
+          name: "conditionalOnFinalizeTokenMint",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "quoteVault",
+          name: "conditionalOnRevertTokenMint",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "treasury",
+          name: "counterpartMint",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "passLpUserAccount",
+          name: "vaultUnderlyingTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "failLpUserAccount",
+          name: "userUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userCounterpartTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "ammVaultAtaBase",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "ammVaultAtaQuote",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "passLpVaultAccount",
-          isMut: true,
+          name: "ammEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "counterpartTokenProgram",
+          isMut: false,
           isSigner: false,
         },
         {
-          name: "failLpVaultAccount",
-          isMut: true,
+          name: "associatedTokenProgram",
+          isMut: false,
           isSigner: false,
         },
         {
-          name: "tokenProgram",
+          name: "systemProgram",
           isMut: false,
           isSigner: false,
         },
@@ -788,24 +1899,20 @@ export const IDL: Autocrat = {
           isMut: false,
           isSigner: false,
         },
-      ],
-      args: [],
-    },
-    {
-      name: "executeProposal",
-      accounts: [
         {
-          name: "proposal",
-          isMut: true,
+          name: "ammProgram",
+          isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: "dao",
-          isMut: false,
-          isSigner: false,
+          name: "args",
+          type: {
+            defined: "MergeAndExitArgs",
+          },
         },
       ],
-      args: [],
     },
     {
       name: "updateDao",
@@ -889,6 +1996,18 @@ export const IDL: Autocrat = {
             name: "twapMaxObservationChangePerUpdate",
             type: "u128",
           },
+          {
+            name: "twapObservationClampMode",
+            docs: [
+              "Whether `twap_max_observation_change_per_update` is an absolute change or",
+              "is in basis points of the last observation. In bps mode, 200 lets the",
+              "observation move 2% per update without having to be re-tuned as the",
+              "token's price moves.",
+            ],
+            type: {
+              defined: "ObservationClampMode",
+            },
+          },
           {
             name: "minQuoteFutarchicLiquidity",
             docs: [
@@ -905,6 +2024,56 @@ export const IDL: Autocrat = {
             name: "minBaseFutarchicLiquidity",
             type: "u64",
           },
+          {
+            name: "swapFeeBps",
+            docs: [
+              "The swap fee, in basis points, that both of a proposal's AMMs must charge.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "protocolFeeBps",
+            docs: [
+              "The share of the swap fee, in basis points, that both of a proposal's AMMs",
+              "must send to the treasury.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "minSettleGraceSlots",
+            docs: [
+              "If a proposal's vaults have a `settle_deadline`, it has to leave at least",
+              "this long after the proposal can be finalized for someone to finalize it.",
+              "Otherwise, anyone could revert the vaults and fail the proposal.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "executionDelaySlots",
+            docs: [
+              "How many slots a proposal has to wait after passing before it can be",
+              "executed, so that holders have time to react to a malicious proposal",
+              "that passed anyway.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "executionWindowSlots",
+            docs: [
+              "How many slots a passed proposal can be executed in once its execution",
+              "delay is over. After that, it can only be expired. `u64::MAX` means",
+              "that passed proposals never expire.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "withdrawalWindowSlots",
+            docs: [
+              "How many slots a proposer has to withdraw their proposal after creating",
+              "it. 0 means that proposals can't be withdrawn.",
+            ],
+            type: "u64",
+          },
         ],
       },
     },
@@ -936,11 +2105,45 @@ export const IDL: Autocrat = {
             },
           },
           {
-            name: "instruction",
+            name: "passedAtSlot",
+            docs: [
+              "The slot that `finalize_proposal` passed the proposal in, or 0 if it",
+              "hasn't passed.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "executableAtSlot",
+            docs: [
+              "The first slot that a passed proposal can be executed in, fixed from",
+              "the DAO's `execution_delay_slots` when it passes.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "expiresAtSlot",
+            docs: [
+              "The first slot that a passed proposal can no longer be executed in,",
+              "fixed from the DAO's `execution_window_slots` when it passes.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "instructions",
+            docs: [
+              "Executed in order by `execute_proposal`, over as many calls as it takes.",
+            ],
             type: {
-              defined: "ProposalInstruction",
+              vec: {
+                defined: "ProposalInstruction",
+              },
             },
           },
+          {
+            name: "executedInstructionIndex",
+            docs: ["How many of `instructions` have been executed so far."],
+            type: "u32",
+          },
           {
             name: "passAmm",
             type: "publicKey",
@@ -969,6 +2172,18 @@ export const IDL: Autocrat = {
             name: "failLpTokensLocked",
             type: "u64",
           },
+          {
+            name: "passAmmSeqNum",
+            docs: [
+              "The AMMs' `seq_num`s when the proposal was created. Instructions can",
+              "only be appended while both are unchanged, i.e. before anyone uses them.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "failAmmSeqNum",
+            type: "u64",
+          },
         ],
       },
     },
@@ -987,6 +2202,14 @@ export const IDL: Autocrat = {
             name: "twapMaxObservationChangePerUpdate",
             type: "u128",
           },
+          {
+            name: "twapObservationClampMode",
+            type: {
+              option: {
+                defined: "ObservationClampMode",
+              },
+            },
+          },
           {
             name: "minQuoteFutarchicLiquidity",
             type: "u64",
@@ -1007,6 +2230,42 @@ export const IDL: Autocrat = {
               option: "u64",
             },
           },
+          {
+            name: "swapFeeBps",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "protocolFeeBps",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "minSettleGraceSlots",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "executionDelaySlots",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "executionWindowSlots",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "withdrawalWindowSlots",
+            type: {
+              option: "u64",
+            },
+          },
         ],
       },
     },
@@ -1020,9 +2279,11 @@ export const IDL: Autocrat = {
             type: "string",
           },
           {
-            name: "instruction",
+            name: "instructions",
             type: {
-              defined: "ProposalInstruction",
+              vec: {
+                defined: "ProposalInstruction",
+              },
             },
           },
           {
@@ -1036,6 +2297,55 @@ export const IDL: Autocrat = {
         ],
       },
     },
+    {
+      name: "MergeAndExitArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "counterpartAmount",
+            docs: [
+              "How many `counterpart_mint` tokens to swap back into the vault's",
+              "conditional tokens before merging, can be 0",
+            ],
+            type: "u64",
+          },
+          {
+            name: "extraMergeAmount",
+            docs: [
+              "How many pairs of conditional tokens the user already holds to merge",
+              "along with whatever the swap produces, can be 0",
+            ],
+            type: "u64",
+          },
+          {
+            name: "minUnderlyingAmount",
+            docs: ["The minimum underlying tokens the user must get back"],
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "SplitAndSwapArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "underlyingAmount",
+            docs: ["How many underlying tokens to deposit into the vault"],
+            type: "u64",
+          },
+          {
+            name: "minOutputAmount",
+            docs: [
+              "The minimum `counterpart_mint` tokens the user must end up with",
+            ],
+            type: "u64",
+          },
+        ],
+      },
+    },
     {
       name: "UpdateDaoParams",
       type: {
@@ -1065,6 +2375,50 @@ export const IDL: Autocrat = {
               option: "u128",
             },
           },
+          {
+            name: "swapFeeBps",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "protocolFeeBps",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "minSettleGraceSlots",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "twapObservationClampMode",
+            type: {
+              option: {
+                defined: "ObservationClampMode",
+              },
+            },
+          },
+          {
+            name: "executionDelaySlots",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "executionWindowSlots",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "withdrawalWindowSlots",
+            type: {
+              option: "u64",
+            },
+          },
         ],
       },
     },
@@ -1112,6 +2466,24 @@ export const IDL: Autocrat = {
         ],
       },
     },
+    {
+      name: "ObservationClampMode",
+      docs: [
+        "Mirrors the amm's `ObservationClampMode`, so that autocrat's IDL defines",
+        "the type that its DAOs store.",
+      ],
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Absolute",
+          },
+          {
+            name: "Bps",
+          },
+        ],
+      },
+    },
     {
       name: "ProposalState",
       type: {
@@ -1129,6 +2501,12 @@ export const IDL: Autocrat = {
           {
             name: "Executed",
           },
+          {
+            name: "Expired",
+          },
+          {
+            name: "Withdrawn",
+          },
         ],
       },
     },
@@ -1189,5 +2567,105 @@ export const IDL: Autocrat = {
       name: "InsufficientLpTokenLock",
       msg: "The LP tokens passed in have less liquidity than the DAO's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`",
     },
+    {
+      code: 6011,
+      name: "SwapFeeTooHigh",
+      msg: "`swap_fee_bps` must be less than `BPS_SCALE`",
+    },
+    {
+      code: 6012,
+      name: "InvalidSwapFee",
+      msg: "An amm has a `swap_fee_bps` that doesn't match the `dao`'s config",
+    },
+    {
+      code: 6013,
+      name: "ProtocolFeeTooHigh",
+      msg: "`protocol_fee_bps` must be at most `BPS_SCALE`",
+    },
+    {
+      code: 6014,
+      name: "InvalidProtocolFee",
+      msg: "An amm has a `protocol_fee_bps` that doesn't match the `dao`'s config",
+    },
+    {
+      code: 6015,
+      name: "InvalidProtocolFeeRecipient",
+      msg: "An amm's `protocol_fee_recipient` isn't the `dao`'s treasury",
+    },
+    {
+      code: 6016,
+      name: "InvalidObservationClampMode",
+      msg: "An amm has an `observation_clamp_mode` that doesn't match the `dao`'s config",
+    },
+    {
+      code: 6017,
+      name: "InvalidObservationClamp",
+      msg: "A bps observation clamp needs a non-zero initial observation and a max change between 1 and `BPS_SCALE`",
+    },
+    {
+      code: 6018,
+      name: "InvalidRouteVault",
+      msg: "A route's vault must be one of the proposal's vaults",
+    },
+    {
+      code: 6019,
+      name: "InvalidRouteAmm",
+      msg: "A route's amm must be one of the proposal's amms",
+    },
+    {
+      code: 6020,
+      name: "ZeroRouteAmount",
+      msg: "Must route a non-zero amount",
+    },
+    {
+      code: 6021,
+      name: "RouteSlippageExceeded",
+      msg: "A route wouldn't have given the user their minimum output, reverting",
+    },
+    {
+      code: 6022,
+      name: "SettleDeadlineTooEarly",
+      msg: "A vault's `settle_deadline` doesn't leave the DAO's `min_settle_grace_slots` to finalize the proposal",
+    },
+    {
+      code: 6023,
+      name: "NoProposalInstructions",
+      msg: "A proposal needs at least one instruction",
+    },
+    {
+      code: 6024,
+      name: "ZeroInstructionsToExecute",
+      msg: "Must execute at least one instruction",
+    },
+    {
+      code: 6025,
+      name: "ProposalMarketsAlreadyActive",
+      msg: "Instructions can only be added to a proposal before anyone uses its markets",
+    },
+    {
+      code: 6026,
+      name: "ExecutionDelayNotElapsed",
+      msg: "A passed proposal can't be executed until the DAO's `execution_delay_slots` have elapsed",
+    },
+    {
+      code: 6027,
+      name: "ProposalExpired",
+      msg: "This proposal wasn't executed within the DAO's `execution_window_slots`",
+    },
+    {
+      code: 6028,
+      name: "ProposalNotExpired",
+      msg: "This proposal can still be executed, so it can't be expired",
+    },
+    {
+      code: 6029,
+      name: "ProposalWithdrawalWindowOver",
+      msg: "A proposal can only be withdrawn by its proposer, within the DAO's `withdrawal_window_slots` of being created",
+    },
+    {
+      code: 6030,
+      name: "WithdrawalWindowTooLong",
+      msg: "A DAO's `withdrawal_window_slots` must be shorter than its `slots_per_proposal`",
+    },
   ],
 };
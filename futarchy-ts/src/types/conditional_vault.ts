@@ -15,6 +15,16 @@ export type ConditionalVault = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: "parentVault";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          docs: [
+            "Only needed if `underlying_token_mint` is another vault's conditional",
+            "token."
+          ];
+        },
         {
           name: "conditionalOnFinalizeTokenMint";
           isMut: true;
@@ -128,6 +138,49 @@ export type ConditionalVault = {
         }
       ];
     },
+    {
+      name: "initializeMultiOutcomeVault";
+      accounts: [
+        {
+          name: "vault";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "underlyingTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: "InitializeMultiOutcomeVaultArgs";
+          };
+        }
+      ];
+    },
     {
       name: "settleConditionalVault";
       accounts: [
@@ -313,219 +366,1542 @@ export type ConditionalVault = {
         }
       ];
       args: [];
-    }
-  ];
-  accounts: [
-    {
-      name: "conditionalVault";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "status";
-            type: {
-              defined: "VaultStatus";
-            };
-          },
-          {
-            name: "settlementAuthority";
-            docs: [
-              "The account that can either finalize the vault to make conditional tokens",
-              "redeemable for underlying tokens or revert the vault to make deposit",
-              "slips redeemable for underlying tokens."
-            ];
-            type: "publicKey";
-          },
-          {
-            name: "underlyingTokenMint";
-            docs: ["The mint of the tokens that are deposited into the vault."];
-            type: "publicKey";
-          },
-          {
-            name: "proposal";
-            docs: [
-              "We need to be able to create multiple vault for a single underlying token",
-              "account, so we use proposal as a PDA seed."
-            ];
-            type: "publicKey";
-          },
-          {
-            name: "underlyingTokenAccount";
-            docs: ["The vault's storage account for deposited funds."];
-            type: "publicKey";
-          },
-          {
-            name: "conditionalOnFinalizeTokenMint";
-            type: "publicKey";
-          },
-          {
-            name: "conditionalOnRevertTokenMint";
-            type: "publicKey";
-          },
-          {
-            name: "pdaBump";
-            type: "u8";
-          },
-          {
-            name: "decimals";
-            type: "u8";
-          }
-        ];
-      };
-    }
-  ];
-  types: [
-    {
-      name: "AddMetadataToConditionalTokensArgs";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "proposalNumber";
-            type: "u64";
-          },
-          {
-            name: "onFinalizeUri";
-            type: "string";
-          },
-          {
-            name: "onRevertUri";
-            type: "string";
-          }
-        ];
-      };
-    },
-    {
-      name: "InitializeConditionalVaultArgs";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "settlementAuthority";
-            type: "publicKey";
-          },
-          {
-            name: "proposal";
-            type: "publicKey";
-          }
-        ];
-      };
-    },
-    {
-      name: "VaultStatus";
-      type: {
-        kind: "enum";
-        variants: [
-          {
-            name: "Active";
-          },
-          {
-            name: "Finalized";
-          },
-          {
-            name: "Reverted";
-          }
-        ];
-      };
-    }
-  ];
-  errors: [
-    {
-      code: 6000;
-      name: "InsufficientUnderlyingTokens";
-      msg: "Insufficient underlying token balance to mint this amount of conditional tokens";
-    },
-    {
-      code: 6001;
-      name: "InvalidVaultUnderlyingTokenAccount";
-      msg: "This `vault_underlying_token_account` is not this vault's `underlying_token_account`";
-    },
-    {
-      code: 6002;
-      name: "InvalidConditionalTokenMint";
-      msg: "This conditional token mint is not this vault's conditional token mint";
     },
     {
-      code: 6003;
-      name: "CantRedeemConditionalTokens";
-      msg: "Vault needs to be settled as finalized before users can redeem conditional tokens for underlying tokens";
-    },
-    {
-      code: 6004;
-      name: "VaultAlreadySettled";
-      msg: "Once a vault has been settled, its status as either finalized or reverted cannot be changed";
-    }
-  ];
-};
-
-export const IDL: ConditionalVault = {
-  version: "1.0.0",
-  name: "conditional_vault",
-  instructions: [
-    {
-      name: "initializeConditionalVault",
+      name: "mintConditionalTokensToRecipient";
       accounts: [
         {
-          name: "vault",
-          isMut: true,
-          isSigner: false,
+          name: "vault";
+          isMut: false;
+          isSigner: false;
         },
         {
-          name: "underlyingTokenMint",
-          isMut: false,
-          isSigner: false,
+          name: "conditionalOnFinalizeTokenMint";
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: "conditionalOnFinalizeTokenMint",
-          isMut: true,
-          isSigner: false,
+          name: "conditionalOnRevertTokenMint";
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: "conditionalOnRevertTokenMint",
-          isMut: true,
-          isSigner: false,
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: "vaultUnderlyingTokenAccount",
-          isMut: false,
-          isSigner: false,
+          name: "authority";
+          isMut: false;
+          isSigner: true;
         },
         {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
+          name: "userUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: "tokenProgram",
-          isMut: false,
-          isSigner: false,
+          name: "recipientConditionalOnFinalizeTokenAccount";
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: "associatedTokenProgram",
-          isMut: false,
-          isSigner: false,
+          name: "recipientConditionalOnRevertTokenAccount";
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
-        },
-      ],
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
       args: [
         {
-          name: "args",
-          type: {
-            defined: "InitializeConditionalVaultArgs",
-          },
-        },
-      ],
+          name: "amount";
+          type: "u64";
+        }
+      ];
+    },
+    {
+      name: "mergeConditionalTokensToRecipient";
+      accounts: [
+        {
+          name: "vault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "conditionalOnRevertTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "recipientUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        }
+      ];
+    },
+    {
+      name: "redeemConditionalTokensToRecipient";
+      accounts: [
+        {
+          name: "vault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "conditionalOnRevertTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "recipientUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "redeemConditionalTokensThroughParent";
+      accounts: [
+        {
+          name: "vault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "conditionalOnRevertTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "parentVault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "parentConditionalTokenMint";
+          isMut: true;
+          isSigner: false;
+          docs: [
+            "The parent vault's conditional mint that is this vault's underlying."
+          ];
+        },
+        {
+          name: "parentVaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userParentConditionalTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "redeemPartialConditionalTokensForUnderlyingTokens";
+      accounts: [
+        {
+          name: "vault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "userUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "outcome";
+          type: "u8";
+        },
+        {
+          name: "amount";
+          type: "u64";
+        }
+      ];
+    },
+    {
+      name: "settleConditionalVaultWithPayouts";
+      accounts: [
+        {
+          name: "settlementAuthority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "vault";
+          isMut: true;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "payoutNumerators";
+          type: {
+            vec: "u64";
+          };
+        }
+      ];
+    },
+    {
+      name: "settleConditionalVaultFromOracle";
+      accounts: [
+        {
+          name: "vault";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "settlementOracle";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "revertExpiredVault";
+      accounts: [
+        {
+          name: "vault";
+          isMut: true;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "closeConditionalVault";
+      accounts: [
+        {
+          name: "vault";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "rentPayer";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "rentPayerUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "settleMultiOutcomeVault";
+      accounts: [
+        {
+          name: "settlementAuthority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "vault";
+          isMut: true;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "winningOutcome";
+          type: "u8";
+        }
+      ];
+    },
+    {
+      name: "mintMultiOutcomeTokens";
+      accounts: [
+        {
+          name: "vault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "userUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        }
+      ];
+    },
+    {
+      name: "mergeMultiOutcomeTokens";
+      accounts: [
+        {
+          name: "vault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "userUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        }
+      ];
+    },
+    {
+      name: "redeemMultiOutcomeTokens";
+      accounts: [
+        {
+          name: "vault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "userUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    }
+  ];
+  accounts: [
+    {
+      name: "conditionalVault";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "status";
+            type: {
+              defined: "VaultStatus";
+            };
+          },
+          {
+            name: "settlementAuthority";
+            docs: [
+              "The account that can either finalize the vault to make conditional tokens",
+              "redeemable for underlying tokens or revert the vault to make deposit",
+              "slips redeemable for underlying tokens."
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "underlyingTokenMint";
+            docs: ["The mint of the tokens that are deposited into the vault."];
+            type: "publicKey";
+          },
+          {
+            name: "proposal";
+            docs: [
+              "We need to be able to create multiple vault for a single underlying token",
+              "account, so we use proposal as a PDA seed."
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "underlyingTokenAccount";
+            docs: ["The vault's storage account for deposited funds."];
+            type: "publicKey";
+          },
+          {
+            name: "conditionalOnFinalizeTokenMint";
+            docs: [
+              "For multi-outcome vaults, these are both the default pubkey so that the",
+              "two-outcome instructions can't be used with them."
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "conditionalOnRevertTokenMint";
+            type: "publicKey";
+          },
+          {
+            name: "pdaBump";
+            type: "u8";
+          },
+          {
+            name: "decimals";
+            type: "u8";
+          },
+          {
+            name: "outcomeMints";
+            docs: [
+              "One mint per outcome. For two-outcome vaults, these are",
+              "`conditional_on_finalize_token_mint` and `conditional_on_revert_token_mint`."
+            ];
+            type: {
+              vec: "publicKey";
+            };
+          },
+          {
+            name: "payoutNumerators";
+            docs: [
+              "How much underlying each outcome's tokens are worth once the vault is",
+              "`Settled`, as a fraction of `payout_denominator`. Empty until then."
+            ];
+            type: {
+              vec: "u64";
+            };
+          },
+          {
+            name: "payoutDenominator";
+            type: "u64";
+          },
+          {
+            name: "settleDeadline";
+            docs: [
+              "The slot after which anyone can revert the vault if it still hasn't",
+              "been settled, so that funds can't be locked forever."
+            ];
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "rentPayer";
+            docs: [
+              "Who paid for the vault's accounts, and gets the rent back on",
+              "`close_conditional_vault`."
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "parentVault";
+            docs: [
+              "If the underlying token is one of another vault's conditional tokens,",
+              "that vault. This lets conditions be combined, such as \"proposal A",
+              "passes and proposal B fails\". Only two-outcome vaults can be nested,",
+              "but their parent can have any number of outcomes."
+            ];
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "settlementOracle";
+            docs: [
+              "If set, anyone can settle the vault once this oracle resolves, without",
+              "needing a signature from `settlement_authority`."
+            ];
+            type: {
+              option: {
+                defined: "SettlementOracle";
+              };
+            };
+          }
+        ];
+      };
+    }
+  ];
+  types: [
+    {
+      name: "AddMetadataToConditionalTokensArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "proposalNumber";
+            type: "u64";
+          },
+          {
+            name: "onFinalizeUri";
+            type: "string";
+          },
+          {
+            name: "onRevertUri";
+            type: "string";
+          }
+        ];
+      };
+    },
+    {
+      name: "InitializeConditionalVaultArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "settlementAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "proposal";
+            type: "publicKey";
+          },
+          {
+            name: "settleDeadline";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "settlementOracle";
+            type: {
+              option: {
+                defined: "SettlementOracle";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "InitializeMultiOutcomeVaultArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "settlementAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "proposal";
+            type: "publicKey";
+          },
+          {
+            name: "numOutcomes";
+            type: "u8";
+          },
+          {
+            name: "settleDeadline";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "settlementOracle";
+            type: {
+              option: {
+                defined: "SettlementOracle";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "SettlementOracle";
+      docs: [
+        "An account that anyone can settle a vault from, by reading the byte at",
+        "`outcome_offset` in its data.",
+        "",
+        "To settle from an autocrat `Proposal`, `owner_program` is autocrat and",
+        "`outcome_offset` is where `state` ends up after the proposal's description",
+        "URL. To settle the same way as the proposal's own vaults, the resolutions",
+        "should be:",
+        "- `Passed` (1), `Executed` (3) and `Expired` (4) to outcome 0 (finalize),",
+        "since an expired proposal still passed.",
+        "- `Failed` (2) and `Withdrawn` (5) to outcome 1 (revert).",
+        "",
+        "`Pending` (0) must be left out, so that the vault can't be settled before",
+        "the proposal is."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "account";
+            type: "publicKey";
+          },
+          {
+            name: "ownerProgram";
+            docs: [
+              "The program that must own `account`, since otherwise anyone could",
+              "write whatever outcome they wanted into it."
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "outcomeOffset";
+            type: "u32";
+          },
+          {
+            name: "resolutions";
+            docs: [
+              "Any value that isn't listed here means the oracle hasn't resolved yet."
+            ];
+            type: {
+              vec: {
+                defined: "OracleResolution";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "OracleResolution";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "value";
+            type: "u8";
+          },
+          {
+            name: "winningOutcome";
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "VaultStatus";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Active";
+          },
+          {
+            name: "Finalized";
+          },
+          {
+            name: "Reverted";
+          },
+          {
+            name: "Settled";
+          }
+        ];
+      };
+    }
+  ];
+  errors: [
+    {
+      code: 6000;
+      name: "InsufficientUnderlyingTokens";
+      msg: "Insufficient underlying token balance to mint this amount of conditional tokens";
+    },
+    {
+      code: 6001;
+      name: "InvalidVaultUnderlyingTokenAccount";
+      msg: "This `vault_underlying_token_account` is not this vault's `underlying_token_account`";
+    },
+    {
+      code: 6002;
+      name: "InvalidConditionalTokenMint";
+      msg: "This conditional token mint is not this vault's conditional token mint";
+    },
+    {
+      code: 6003;
+      name: "CantRedeemConditionalTokens";
+      msg: "Vault needs to be settled as finalized before users can redeem conditional tokens for underlying tokens";
+    },
+    {
+      code: 6004;
+      name: "VaultAlreadySettled";
+      msg: "Once a vault has been settled, its status as either finalized or reverted cannot be changed";
+    },
+    {
+      code: 6005;
+      name: "InvalidNumOutcomes";
+      msg: "A vault can have between 2 and `MAX_OUTCOMES` outcomes";
+    },
+    {
+      code: 6006;
+      name: "InvalidOutcomeMint";
+      msg: "An outcome mint isn't at the address this vault expects";
+    },
+    {
+      code: 6007;
+      name: "InvalidOutcomeAccounts";
+      msg: "Expected one outcome mint and one user token account per outcome";
+    },
+    {
+      code: 6008;
+      name: "InvalidOutcomeIndex";
+      msg: "This vault doesn't have an outcome with that index";
+    },
+    {
+      code: 6009;
+      name: "InvalidSettlement";
+      msg: "Two-outcome vaults settle as finalized or reverted, multi-outcome vaults settle by naming the winning outcome";
+    },
+    {
+      code: 6010;
+      name: "InvalidPayouts";
+      msg: "Payouts need one numerator per outcome, and can't all be zero";
+    },
+    {
+      code: 6011;
+      name: "InsufficientConditionalTokens";
+      msg: "Insufficient conditional token balance to redeem this amount";
+    },
+    {
+      code: 6012;
+      name: "SettleDeadlineNotReached";
+      msg: "This vault doesn't have a `settle_deadline`, or it hasn't passed yet";
+    },
+    {
+      code: 6013;
+      name: "VaultNotSettled";
+      msg: "A vault needs to be settled before it can be closed";
+    },
+    {
+      code: 6014;
+      name: "ConditionalTokensOutstanding";
+      msg: "A vault can't be closed until all of its conditional tokens have been redeemed";
+    },
+    {
+      code: 6015;
+      name: "InvalidParentVault";
+      msg: "A parent vault must have the nested vault's underlying token as one of its conditional tokens";
+    },
+    {
+      code: 6016;
+      name: "MultiOutcomeVaultCantBeNested";
+      msg: "Only two-outcome vaults can be nested under a parent vault";
+    },
+    {
+      code: 6017;
+      name: "InvalidSettlementOracle";
+      msg: "A settlement oracle needs between 1 and `MAX_ORACLE_RESOLUTIONS` resolutions, each naming one of the vault's outcomes, and must be the account and owner that the vault expects";
+    },
+    {
+      code: 6018;
+      name: "SettlementOracleNotResolved";
+      msg: "The settlement oracle hasn't resolved to an outcome yet";
+    }
+  ];
+};
+
+export const IDL: ConditionalVault = {
+  version: "1.0.0",
+  name: "conditional_vault",
+  instructions: [
+    {
+      name: "initializeConditionalVault",
+      accounts: [
+        {
+          name: "vault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "underlyingTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "parentVault",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            "Only needed if `underlying_token_mint` is another vault's conditional",
+            "token.",
+          ],
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultUnderlyingTokenAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "args",
+          type: {
+            defined: "InitializeConditionalVaultArgs",
+          },
+        },
+      ],
+    },
+    {
+      name: "addMetadataToConditionalTokens",
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "vault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "underlyingTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "underlyingTokenMetadata",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnFinalizeTokenMetadata",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMetadata",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenMetadataProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "rent",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "args",
+          type: {
+            defined: "AddMetadataToConditionalTokensArgs",
+          },
+        },
+      ],
+    },
+    {
+      name: "initializeMultiOutcomeVault",
+      accounts: [
+        {
+          name: "vault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "underlyingTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultUnderlyingTokenAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "args",
+          type: {
+            defined: "InitializeMultiOutcomeVaultArgs",
+          },
+        },
+      ],
+    },
+    {
+      name: "settleConditionalVault",
+      accounts: [
+        {
+          name: "settlementAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "vault",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "newStatus",
+          type: {
+            defined: "VaultStatus",
+          },
+        },
+      ],
+    },
+    {
+      name: "mergeConditionalTokensForUnderlyingTokens",
+      accounts: [
+        {
+          name: "vault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "amount",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "mintConditionalTokens",
+      accounts: [
+        {
+          name: "vault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "amount",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "redeemConditionalTokensForUnderlyingTokens",
+      accounts: [
+        {
+          name: "vault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "mintConditionalTokensToRecipient",
+      accounts: [
+        {
+          name: "vault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "userUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "recipientConditionalOnFinalizeTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "recipientConditionalOnRevertTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "amount",
+          type: "u64",
+        },
+      ],
     },
     {
-      name: "addMetadataToConditionalTokens",
+      name: "mergeConditionalTokensToRecipient",
       accounts: [
         {
-          name: "payer",
+          name: "vault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "recipientUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "amount",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "redeemConditionalTokensToRecipient",
+      accounts: [
+        {
+          name: "vault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "recipientUnderlyingTokenAccount",
           isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "redeemConditionalTokensThroughParent",
+      accounts: [
+        {
+          name: "vault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnFinalizeTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "conditionalOnRevertTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "parentVault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "parentConditionalTokenMint",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "The parent vault's conditional mint that is this vault's underlying.",
+          ],
+        },
+        {
+          name: "parentVaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "userConditionalOnFinalizeTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userConditionalOnRevertTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userParentConditionalTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "redeemPartialConditionalTokensForUnderlyingTokens",
+      accounts: [
+        {
+          name: "vault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "userUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "outcome",
+          type: "u8",
+        },
+        {
+          name: "amount",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "settleConditionalVaultWithPayouts",
+      accounts: [
+        {
+          name: "settlementAuthority",
+          isMut: false,
           isSigner: true,
         },
         {
@@ -533,63 +1909,76 @@ export const IDL: ConditionalVault = {
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
+        {
+          name: "payoutNumerators",
+          type: {
+            vec: "u64",
+          },
+        },
+      ],
+    },
+    {
+      name: "settleConditionalVaultFromOracle",
+      accounts: [
+        {
+          name: "vault",
+          isMut: true,
+          isSigner: false,
+        },
         {
-          name: "underlyingTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "underlyingTokenMetadata",
+          name: "settlementOracle",
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: "revertExpiredVault",
+      accounts: [
         {
-          name: "conditionalOnFinalizeTokenMint",
+          name: "vault",
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: "closeConditionalVault",
+      accounts: [
         {
-          name: "conditionalOnRevertTokenMint",
+          name: "vault",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "conditionalOnFinalizeTokenMetadata",
+          name: "rentPayer",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "conditionalOnRevertTokenMetadata",
+          name: "vaultUnderlyingTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "tokenMetadataProgram",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "systemProgram",
-          isMut: false,
+          name: "rentPayerUnderlyingTokenAccount",
+          isMut: true,
           isSigner: false,
         },
         {
-          name: "rent",
+          name: "tokenProgram",
           isMut: false,
           isSigner: false,
         },
       ],
-      args: [
-        {
-          name: "args",
-          type: {
-            defined: "AddMetadataToConditionalTokensArgs",
-          },
-        },
-      ],
+      args: [],
     },
     {
-      name: "settleConditionalVault",
+      name: "settleMultiOutcomeVault",
       accounts: [
         {
           name: "settlementAuthority",
@@ -604,31 +1993,19 @@ export const IDL: ConditionalVault = {
       ],
       args: [
         {
-          name: "newStatus",
-          type: {
-            defined: "VaultStatus",
-          },
+          name: "winningOutcome",
+          type: "u8",
         },
       ],
     },
     {
-      name: "mergeConditionalTokensForUnderlyingTokens",
+      name: "mintMultiOutcomeTokens",
       accounts: [
         {
           name: "vault",
           isMut: false,
           isSigner: false,
         },
-        {
-          name: "conditionalOnFinalizeTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnRevertTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: "vaultUnderlyingTokenAccount",
           isMut: true,
@@ -639,16 +2016,6 @@ export const IDL: ConditionalVault = {
           isMut: false,
           isSigner: true,
         },
-        {
-          name: "userConditionalOnFinalizeTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userConditionalOnRevertTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: "userUnderlyingTokenAccount",
           isMut: true,
@@ -668,23 +2035,13 @@ export const IDL: ConditionalVault = {
       ],
     },
     {
-      name: "mintConditionalTokens",
+      name: "mergeMultiOutcomeTokens",
       accounts: [
         {
           name: "vault",
           isMut: false,
           isSigner: false,
         },
-        {
-          name: "conditionalOnFinalizeTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnRevertTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: "vaultUnderlyingTokenAccount",
           isMut: true,
@@ -695,16 +2052,6 @@ export const IDL: ConditionalVault = {
           isMut: false,
           isSigner: true,
         },
-        {
-          name: "userConditionalOnFinalizeTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userConditionalOnRevertTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: "userUnderlyingTokenAccount",
           isMut: true,
@@ -724,23 +2071,13 @@ export const IDL: ConditionalVault = {
       ],
     },
     {
-      name: "redeemConditionalTokensForUnderlyingTokens",
+      name: "redeemMultiOutcomeTokens",
       accounts: [
         {
           name: "vault",
           isMut: false,
           isSigner: false,
         },
-        {
-          name: "conditionalOnFinalizeTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnRevertTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: "vaultUnderlyingTokenAccount",
           isMut: true,
@@ -751,16 +2088,6 @@ export const IDL: ConditionalVault = {
           isMut: false,
           isSigner: true,
         },
-        {
-          name: "userConditionalOnFinalizeTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userConditionalOnRevertTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: "userUnderlyingTokenAccount",
           isMut: true,
@@ -816,6 +2143,10 @@ export const IDL: ConditionalVault = {
           },
           {
             name: "conditionalOnFinalizeTokenMint",
+            docs: [
+              "For multi-outcome vaults, these are both the default pubkey so that the",
+              "two-outcome instructions can't be used with them.",
+            ],
             type: "publicKey",
           },
           {
@@ -830,6 +2161,72 @@ export const IDL: ConditionalVault = {
             name: "decimals",
             type: "u8",
           },
+          {
+            name: "outcomeMints",
+            docs: [
+              "One mint per outcome. For two-outcome vaults, these are",
+              "`conditional_on_finalize_token_mint` and `conditional_on_revert_token_mint`.",
+            ],
+            type: {
+              vec: "publicKey",
+            },
+          },
+          {
+            name: "payoutNumerators",
+            docs: [
+              "How much underlying each outcome's tokens are worth once the vault is",
+              "`Settled`, as a fraction of `payout_denominator`. Empty until then.",
+            ],
+            type: {
+              vec: "u64",
+            },
+          },
+          {
+            name: "payoutDenominator",
+            type: "u64",
+          },
+          {
+            name: "settleDeadline",
+            docs: [
+              "The slot after which anyone can revert the vault if it still hasn't",
+              "been settled, so that funds can't be locked forever.",
+            ],
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "rentPayer",
+            docs: [
+              "Who paid for the vault's accounts, and gets the rent back on",
+              "`close_conditional_vault`.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "parentVault",
+            docs: [
+              "If the underlying token is one of another vault's conditional tokens,",
+              "that vault. This lets conditions be combined, such as \"proposal A",
+              "passes and proposal B fails\". Only two-outcome vaults can be nested,",
+              "but their parent can have any number of outcomes.",
+            ],
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "settlementOracle",
+            docs: [
+              "If set, anyone can settle the vault once this oracle resolves, without",
+              "needing a signature from `settlement_authority`.",
+            ],
+            type: {
+              option: {
+                defined: "SettlementOracle",
+              },
+            },
+          },
         ],
       },
     },
@@ -868,6 +2265,120 @@ export const IDL: ConditionalVault = {
             name: "proposal",
             type: "publicKey",
           },
+          {
+            name: "settleDeadline",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "settlementOracle",
+            type: {
+              option: {
+                defined: "SettlementOracle",
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "InitializeMultiOutcomeVaultArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "settlementAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "proposal",
+            type: "publicKey",
+          },
+          {
+            name: "numOutcomes",
+            type: "u8",
+          },
+          {
+            name: "settleDeadline",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "settlementOracle",
+            type: {
+              option: {
+                defined: "SettlementOracle",
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "SettlementOracle",
+      docs: [
+        "An account that anyone can settle a vault from, by reading the byte at",
+        "`outcome_offset` in its data.",
+        "",
+        "To settle from an autocrat `Proposal`, `owner_program` is autocrat and",
+        "`outcome_offset` is where `state` ends up after the proposal's description",
+        "URL. To settle the same way as the proposal's own vaults, the resolutions",
+        "should be:",
+        "- `Passed` (1), `Executed` (3) and `Expired` (4) to outcome 0 (finalize),",
+        "since an expired proposal still passed.",
+        "- `Failed` (2) and `Withdrawn` (5) to outcome 1 (revert).",
+        "",
+        "`Pending` (0) must be left out, so that the vault can't be settled before",
+        "the proposal is.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "account",
+            type: "publicKey",
+          },
+          {
+            name: "ownerProgram",
+            docs: [
+              "The program that must own `account`, since otherwise anyone could",
+              "write whatever outcome they wanted into it.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "outcomeOffset",
+            type: "u32",
+          },
+          {
+            name: "resolutions",
+            docs: [
+              "Any value that isn't listed here means the oracle hasn't resolved yet.",
+            ],
+            type: {
+              vec: {
+                defined: "OracleResolution",
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "OracleResolution",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "value",
+            type: "u8",
+          },
+          {
+            name: "winningOutcome",
+            type: "u8",
+          },
         ],
       },
     },
@@ -885,6 +2396,9 @@ export const IDL: ConditionalVault = {
           {
             name: "Reverted",
           },
+          {
+            name: "Settled",
+          },
         ],
       },
     },
@@ -915,5 +2429,75 @@ export const IDL: ConditionalVault = {
       name: "VaultAlreadySettled",
       msg: "Once a vault has been settled, its status as either finalized or reverted cannot be changed",
     },
+    {
+      code: 6005,
+      name: "InvalidNumOutcomes",
+      msg: "A vault can have between 2 and `MAX_OUTCOMES` outcomes",
+    },
+    {
+      code: 6006,
+      name: "InvalidOutcomeMint",
+      msg: "An outcome mint isn't at the address this vault expects",
+    },
+    {
+      code: 6007,
+      name: "InvalidOutcomeAccounts",
+      msg: "Expected one outcome mint and one user token account per outcome",
+    },
+    {
+      code: 6008,
+      name: "InvalidOutcomeIndex",
+      msg: "This vault doesn't have an outcome with that index",
+    },
+    {
+      code: 6009,
+      name: "InvalidSettlement",
+      msg: "Two-outcome vaults settle as finalized or reverted, multi-outcome vaults settle by naming the winning outcome",
+    },
+    {
+      code: 6010,
+      name: "InvalidPayouts",
+      msg: "Payouts need one numerator per outcome, and can't all be zero",
+    },
+    {
+      code: 6011,
+      name: "InsufficientConditionalTokens",
+      msg: "Insufficient conditional token balance to redeem this amount",
+    },
+    {
+      code: 6012,
+      name: "SettleDeadlineNotReached",
+      msg: "This vault doesn't have a `settle_deadline`, or it hasn't passed yet",
+    },
+    {
+      code: 6013,
+      name: "VaultNotSettled",
+      msg: "A vault needs to be settled before it can be closed",
+    },
+    {
+      code: 6014,
+      name: "ConditionalTokensOutstanding",
+      msg: "A vault can't be closed until all of its conditional tokens have been redeemed",
+    },
+    {
+      code: 6015,
+      name: "InvalidParentVault",
+      msg: "A parent vault must have the nested vault's underlying token as one of its conditional tokens",
+    },
+    {
+      code: 6016,
+      name: "MultiOutcomeVaultCantBeNested",
+      msg: "Only two-outcome vaults can be nested under a parent vault",
+    },
+    {
+      code: 6017,
+      name: "InvalidSettlementOracle",
+      msg: "A settlement oracle needs between 1 and `MAX_ORACLE_RESOLUTIONS` resolutions, each naming one of the vault's outcomes, and must be the account and owner that the vault expects",
+    },
+    {
+      code: 6018,
+      name: "SettlementOracleNotResolved",
+      msg: "The settlement oracle hasn't resolved to an outcome yet",
+    },
   ],
 };
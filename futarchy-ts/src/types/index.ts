@@ -1,5 +1,6 @@
 import { Autocrat as AutocratIDLType } from "./autocrat";
 import { Amm as AmmIDLType } from "./amm";
+import { ConditionalVault as ConditionalVaultIDLType } from "./conditional_vault";
 
 import type { IdlAccounts, IdlTypes } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
@@ -19,3 +20,10 @@ export type ProposalWrapper = {
 export type Dao = IdlAccounts<AutocratIDLType>["dao"];
 
 export type Amm = IdlAccounts<AmmIDLType>["amm"];
+export type TwapCheckpoints = IdlAccounts<AmmIDLType>["twapCheckpoints"];
+export type SwapQuote = IdlTypes<AmmIDLType>["SwapQuote"];
+export type AddLiquidityQuote = IdlTypes<AmmIDLType>["AddLiquidityQuote"];
+export type RemoveLiquidityQuote = IdlTypes<AmmIDLType>["RemoveLiquidityQuote"];
+
+export type SettlementOracle =
+  IdlTypes<ConditionalVaultIDLType>["SettlementOracle"];
@@ -37,6 +37,21 @@ export const getVaultRevertMintAddr = (
   return getVaultMintAddr(programId, vault, "conditional_on_revert_mint");
 };
 
+export const getVaultOutcomeMintAddr = (
+  programId: PublicKey,
+  vault: PublicKey,
+  outcome: number
+) => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("conditional_outcome_mint"),
+      vault.toBuffer(),
+      Buffer.from([outcome]),
+    ],
+    programId
+  );
+};
+
 const getVaultMintAddr = (
   programId: PublicKey,
   vault: PublicKey,
@@ -102,6 +117,16 @@ export const getAmmLpMintAddr = (
   );
 };
 
+export const getAmmTwapCheckpointsAddr = (
+  programId: PublicKey,
+  amm: PublicKey
+): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [utils.bytes.utf8.encode("twap_checkpoints"), amm.toBuffer()],
+    programId
+  );
+};
+
 export const getAmmAuthAddr = (programId: PublicKey): [PublicKey, number] => {
   return PublicKey.findProgramAddressSync(
     [utils.bytes.utf8.encode("amm_auth")],
@@ -109,9 +134,23 @@ export const getAmmAuthAddr = (programId: PublicKey): [PublicKey, number] => {
   );
 };
 
-export const getATA = (mint: PublicKey, owner: PublicKey) => {
+// the PDA that anchor's `#[event_cpi]` self-CPIs events through
+export const getEventAuthorityAddr = (
+  programId: PublicKey
+): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [utils.bytes.utf8.encode("__event_authority")],
+    programId
+  );
+};
+
+export const getATA = (
+  mint: PublicKey,
+  owner: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+) => {
   return PublicKey.findProgramAddressSync(
-    [owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    [owner.toBuffer(), tokenProgram.toBuffer(), mint.toBuffer()],
     ASSOCIATED_TOKEN_PROGRAM_ID
   );
 };
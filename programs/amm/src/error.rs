@@ -28,6 +28,34 @@ pub enum AmmError {
     AddLiquiditySlippageExceeded,
     #[msg("LP would have spent more than `max_base_amount`")]
     AddLiquidityMaxBaseExceeded,
-    #[msg("`quote_amount` must be greater than 100000000 when initializing a pool")]
+    #[msg("`quote_amount` must be at least `MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT` when initializing a pool")]
     InsufficientQuoteAmount,
+    #[msg("`swap_fee_bps` must be less than `BPS_SCALE`")]
+    InvalidSwapFee,
+    #[msg("`protocol_fee_bps` can't be more than `BPS_SCALE`")]
+    InvalidProtocolFee,
+    #[msg("Can't swap for as many or more tokens than a pool holds")]
+    OutputAmountExceedsReserves,
+    #[msg("Output token amount is too large for a swap, causes overflow")]
+    OutputAmountOverflow,
+    #[msg("A user would have spent more than their `input_amount_max`, reverting")]
+    SwapInputMaxExceeded,
+    #[msg("A TWAP window must end after it starts")]
+    InvalidTwapWindow,
+    #[msg("There's no TWAP checkpoint recorded at that slot")]
+    TwapCheckpointNotFound,
+    #[msg("This AMM has TWAP checkpoints, so cranks must pass them")]
+    MissingTwapCheckpoints,
+    #[msg("A bps observation clamp needs a non-zero initial observation and a max change between 1 and `BPS_SCALE`")]
+    InvalidObservationClamp,
+    #[msg("Couldn't calculate a Token-2022 transfer fee")]
+    TransferFeeCalculationError,
+    #[msg("Can't close an AMM while anyone besides the AMM itself holds its LP tokens")]
+    AmmNotEmpty,
+    #[msg("Protocol fees must be collected before an AMM can be closed")]
+    UncollectedProtocolFees,
+    #[msg("Can't close an AMM that has never had liquidity")]
+    AmmNeverFunded,
+    #[msg("TWAP checkpoints' rent can only go back to whoever paid for them")]
+    InvalidTwapCheckpointsRentPayer,
 }
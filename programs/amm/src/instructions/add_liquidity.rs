@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, *};
+use anchor_spl::token::{self, MintTo};
 
 use crate::error::AmmError;
-use crate::AddOrRemoveLiquidity;
+use crate::events::{AddLiquidityEvent, CommonFields};
 use crate::{generate_amm_seeds, state::*};
+use crate::{get_pre_fee_amount, transfer_checked_and_measure, AddOrRemoveLiquidity};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct AddLiquidityArgs {
@@ -21,16 +22,21 @@ impl AddOrRemoveLiquidity<'_> {
             user,
             amm,
             lp_mint,
-            base_mint: _,
-            quote_mint: _,
+            base_mint,
+            quote_mint,
             user_ata_lp,
             user_ata_base,
             user_ata_quote,
             vault_ata_base,
             vault_ata_quote,
+            vault_ata_lp,
             associated_token_program: _,
             token_program,
+            base_token_program,
+            quote_token_program,
             system_program: _,
+            event_authority: _,
+            program: _,
         } = ctx.accounts;
 
         let AddLiquidityArgs {
@@ -50,7 +56,9 @@ impl AddOrRemoveLiquidity<'_> {
             AmmError::InsufficientBalance
         );
 
-        amm.update_twap(Clock::get()?.slot);
+        let clock = Clock::get()?;
+
+        amm.update_twap(clock.slot);
 
         // airlifted from uniswap v1:
         // https://github.com/Uniswap/v1-contracts/blob/c10c08d81d6114f694baa8bd32f555a40f6264da/contracts/uniswap_exchange.vy#L48
@@ -58,22 +66,30 @@ impl AddOrRemoveLiquidity<'_> {
         require!(max_base_amount > 0, AmmError::ZeroLiquidityToAdd);
         require!(quote_amount > 0, AmmError::ZeroLiquidityToAdd);
 
+        // we account for what the vaults actually receive, which can be less than
+        // what's sent if either mint charges a transfer fee
+        let quote_amount = transfer_checked_and_measure(
+            quote_token_program,
+            quote_mint,
+            user_ata_quote,
+            vault_ata_quote,
+            user.to_account_info(),
+            &[],
+            quote_amount,
+        )?;
+
         let total_lp_supply = lp_mint.supply;
 
         let (lp_tokens_to_mint, base_amount) = if total_lp_supply > 0 {
             require!(min_lp_tokens > 0, AmmError::ZeroMinLpTokens);
 
-            let quote_reserve = amm.quote_amount as u128;
-            let base_reserve = amm.base_amount as u128;
-
-            let base_amount = (((quote_amount as u128 * base_reserve) / quote_reserve) + 1) as u64;
-
-            let lp_tokens_to_mint =
-                ((quote_amount as u128 * total_lp_supply as u128) / quote_reserve) as u64;
+            let (base_amount, lp_tokens_to_mint) =
+                amm.get_liquidity_to_add(quote_amount, total_lp_supply);
+            let base_amount_to_send = get_pre_fee_amount(base_mint, base_amount)?;
 
             require_gte!(
                 max_base_amount,
-                base_amount,
+                base_amount_to_send,
                 AmmError::AddLiquidityMaxBaseExceeded
             );
             require_gte!(
@@ -82,12 +98,35 @@ impl AddOrRemoveLiquidity<'_> {
                 AmmError::AddLiquiditySlippageExceeded
             );
 
-            (lp_tokens_to_mint, base_amount)
+            let base_amount_received = transfer_checked_and_measure(
+                base_token_program,
+                base_mint,
+                user_ata_base,
+                vault_ata_base,
+                user.to_account_info(),
+                &[],
+                base_amount_to_send,
+            )?;
+
+            assert!(base_amount_received >= base_amount);
+
+            (lp_tokens_to_mint, base_amount_received)
         } else {
-            // equivalent to $100 if quote is USDC, here for rounding
-            require_gte!(quote_amount, 100000000, AmmError::InsufficientQuoteAmount);
+            require_gte!(
+                quote_amount,
+                MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT,
+                AmmError::InsufficientQuoteAmount
+            );
 
-            let base_amount = max_base_amount;
+            let base_amount = transfer_checked_and_measure(
+                base_token_program,
+                base_mint,
+                user_ata_base,
+                vault_ata_base,
+                user.to_account_info(),
+                &[],
+                max_base_amount,
+            )?;
 
             let initial_lp_tokens = quote_amount;
 
@@ -100,6 +139,27 @@ impl AddOrRemoveLiquidity<'_> {
         let seeds = generate_amm_seeds!(amm);
         let signer = &[&seeds[..]];
 
+        // on the first deposit, lock `MINIMUM_LIQUIDITY` in the AMM's own LP
+        // account so that the pool can never be drained back to zero
+        let lp_tokens_to_mint = if total_lp_supply == 0 {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    MintTo {
+                        mint: lp_mint.to_account_info(),
+                        to: vault_ata_lp.to_account_info(),
+                        authority: amm.to_account_info(),
+                    },
+                    signer,
+                ),
+                MINIMUM_LIQUIDITY,
+            )?;
+
+            lp_tokens_to_mint - MINIMUM_LIQUIDITY
+        } else {
+            lp_tokens_to_mint
+        };
+
         token::mint_to(
             CpiContext::new_with_signer(
                 token_program.to_account_info(),
@@ -113,22 +173,17 @@ impl AddOrRemoveLiquidity<'_> {
             lp_tokens_to_mint,
         )?;
 
-        for (amount, from, to) in [
-            (base_amount, user_ata_base, vault_ata_base),
-            (quote_amount, user_ata_quote, vault_ata_quote),
-        ] {
-            token::transfer(
-                CpiContext::new(
-                    token_program.to_account_info(),
-                    Transfer {
-                        from: from.to_account_info(),
-                        to: to.to_account_info(),
-                        authority: user.to_account_info(),
-                    },
-                ),
-                amount,
-            )?;
-        }
+        amm.seq_num += 1;
+
+        let event = AddLiquidityEvent {
+            common: CommonFields::new(&clock, amm.key(), amm),
+            user: user.key(),
+            quote_amount,
+            base_amount,
+            lp_tokens_minted: lp_tokens_to_mint,
+        };
+
+        emit_cpi!(event);
 
         Ok(())
     }
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{self, Burn, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+use crate::error::AmmError;
+use crate::events::{CloseAmmEvent, CommonFields};
+use crate::instructions::common::harvest_withheld_fees;
+use crate::state::*;
+use crate::{generate_amm_seeds, MINIMUM_LIQUIDITY};
+
+/// Permissionless, since the rent can only ever go back to `rent_payer`.
+///
+/// An AMM can be closed once every LP token is either burned or locked, which
+/// for a proposal's AMMs can only happen after it's been finalized and the
+/// locked LP has been returned and removed. Whatever dust is left in the vaults
+/// backing `MINIMUM_LIQUIDITY` is burned so that they can be closed. For a
+/// proposal's AMMs that dust is conditional tokens, so the underlying backing
+/// it stays in the conditional vault until `close_conditional_vault` sweeps it.
+/// LP mints are legacy SPL mints, which can't be closed, so they're left behind.
+/// If the AMM has TWAP checkpoints, they're closed too.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseAmm<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = lp_mint,
+        has_one = base_mint,
+        has_one = quote_mint,
+        has_one = rent_payer,
+    )]
+    pub amm: Account<'info, Amm>,
+    /// CHECK: only receives lamports, and is checked against the AMM
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+    #[account(mut, mint::token_program = token_program)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, mint::token_program = base_token_program)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, mint::token_program = quote_token_program)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = base_token_program,
+    )]
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_ata_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// Required if the AMM has TWAP checkpoints.
+    #[account(
+        mut,
+        has_one = amm,
+        seeds = [TWAP_CHECKPOINTS_SEED_PREFIX, amm.key().as_ref()],
+        bump
+    )]
+    pub twap_checkpoints: Option<Box<Account<'info, TwapCheckpoints>>>,
+    /// CHECK: only receives lamports, and is checked against the checkpoints
+    #[account(mut)]
+    pub twap_checkpoints_rent_payer: Option<UncheckedAccount<'info>>,
+    pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+impl CloseAmm<'_> {
+    pub fn validate(&self) -> Result<()> {
+        // otherwise, anyone could close an AMM before its first deposit
+        require_gte!(
+            self.lp_mint.supply,
+            MINIMUM_LIQUIDITY,
+            AmmError::AmmNeverFunded
+        );
+
+        // the only LP left must be what the AMM locked for itself
+        require_eq!(
+            self.lp_mint.supply,
+            self.vault_ata_lp.amount,
+            AmmError::AmmNotEmpty
+        );
+
+        require!(
+            self.amm.base_protocol_fees == 0 && self.amm.quote_protocol_fees == 0,
+            AmmError::UncollectedProtocolFees
+        );
+
+        if self.amm.has_twap_checkpoints {
+            let (Some(twap_checkpoints), Some(twap_checkpoints_rent_payer)) =
+                (&self.twap_checkpoints, &self.twap_checkpoints_rent_payer)
+            else {
+                return err!(AmmError::MissingTwapCheckpoints);
+            };
+
+            require_keys_eq!(
+                twap_checkpoints.rent_payer,
+                twap_checkpoints_rent_payer.key(),
+                AmmError::InvalidTwapCheckpointsRentPayer
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let CloseAmm {
+            amm,
+            rent_payer,
+            lp_mint,
+            base_mint,
+            quote_mint,
+            vault_ata_base,
+            vault_ata_quote,
+            vault_ata_lp,
+            twap_checkpoints,
+            twap_checkpoints_rent_payer,
+            token_program,
+            base_token_program,
+            quote_token_program,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        let base_amount_burned = vault_ata_base.amount;
+        let quote_amount_burned = vault_ata_quote.amount;
+
+        let seeds = generate_amm_seeds!(amm);
+        let signer = &[&seeds[..]];
+
+        for (token_program, mint, vault) in [
+            (token_program.to_account_info(), lp_mint, vault_ata_lp),
+            (
+                base_token_program.to_account_info(),
+                base_mint,
+                vault_ata_base,
+            ),
+            (
+                quote_token_program.to_account_info(),
+                quote_mint,
+                vault_ata_quote,
+            ),
+        ] {
+            if vault.amount > 0 {
+                token_interface::burn(
+                    CpiContext::new_with_signer(
+                        token_program.clone(),
+                        Burn {
+                            mint: mint.to_account_info(),
+                            from: vault.to_account_info(),
+                            authority: amm.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    vault.amount,
+                )?;
+            }
+
+            harvest_withheld_fees(&token_program, mint, vault)?;
+
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program,
+                CloseAccount {
+                    account: vault.to_account_info(),
+                    destination: rent_payer.to_account_info(),
+                    authority: amm.to_account_info(),
+                },
+                signer,
+            ))?;
+        }
+
+        if let (Some(twap_checkpoints), Some(twap_checkpoints_rent_payer)) =
+            (twap_checkpoints, twap_checkpoints_rent_payer)
+        {
+            twap_checkpoints.close(twap_checkpoints_rent_payer.to_account_info())?;
+        }
+
+        amm.base_amount = 0;
+        amm.quote_amount = 0;
+        amm.seq_num += 1;
+
+        let event = CloseAmmEvent {
+            common: CommonFields::new(&Clock::get()?, amm.key(), amm),
+            rent_payer: rent_payer.key(),
+            base_amount_burned,
+            quote_amount_burned,
+        };
+
+        emit_cpi!(event);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::events::{CollectProtocolFeesEvent, CommonFields};
+use crate::generate_amm_seeds;
+use crate::state::*;
+use crate::transfer_checked_and_measure;
+
+/// Permissionless, since the fees can only ever go to `protocol_fee_recipient`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectProtocolFees<'info> {
+    #[account(
+        mut,
+        has_one = base_mint,
+        has_one = quote_mint,
+    )]
+    pub amm: Account<'info, Amm>,
+    #[account(mint::token_program = base_token_program)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = quote_token_program)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = amm.protocol_fee_recipient,
+        associated_token::token_program = base_token_program,
+    )]
+    pub recipient_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = amm.protocol_fee_recipient,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub recipient_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = base_token_program,
+    )]
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+impl CollectProtocolFees<'_> {
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let CollectProtocolFees {
+            amm,
+            base_mint,
+            quote_mint,
+            recipient_ata_base,
+            recipient_ata_quote,
+            vault_ata_base,
+            vault_ata_quote,
+            base_token_program,
+            quote_token_program,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        let base_fees = amm.base_protocol_fees;
+        let quote_fees = amm.quote_protocol_fees;
+
+        amm.base_protocol_fees = 0;
+        amm.quote_protocol_fees = 0;
+
+        let seeds = generate_amm_seeds!(amm);
+
+        for (amount, token_program, mint, from, to) in [
+            (
+                base_fees,
+                base_token_program,
+                base_mint,
+                vault_ata_base,
+                recipient_ata_base,
+            ),
+            (
+                quote_fees,
+                quote_token_program,
+                quote_mint,
+                vault_ata_quote,
+                recipient_ata_quote,
+            ),
+        ] {
+            transfer_checked_and_measure(
+                token_program,
+                mint,
+                from,
+                to,
+                amm.to_account_info(),
+                &[seeds],
+                amount,
+            )?;
+        }
+
+        amm.seq_num += 1;
+
+        let event = CollectProtocolFeesEvent {
+            common: CommonFields::new(&Clock::get()?, amm.key(), amm),
+            recipient: amm.protocol_fee_recipient,
+            base_amount: base_fees,
+            quote_amount: quote_fees,
+        };
+
+        emit_cpi!(event);
+
+        Ok(())
+    }
+}
@@ -1,9 +1,19 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::{self, TransferFeeConfig},
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
+use crate::error::AmmError;
 use crate::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct AddOrRemoveLiquidity<'info> {
     #[account(mut)]
@@ -16,41 +26,156 @@ pub struct AddOrRemoveLiquidity<'info> {
     )]
     pub amm: Account<'info, Amm>,
     #[account(mut)]
-    pub lp_mint: Box<Account<'info, Mint>>,
-    pub base_mint: Account<'info, Mint>,
-    pub quote_mint: Account<'info, Mint>,
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = base_token_program)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = quote_token_program)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = lp_mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub user_ata_lp: Box<Account<'info, TokenAccount>>,
+    pub user_ata_lp: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = base_mint,
         associated_token::authority = user,
+        associated_token::token_program = base_token_program,
     )]
-    pub user_ata_base: Box<Account<'info, TokenAccount>>,
+    pub user_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = quote_mint,
         associated_token::authority = user,
+        associated_token::token_program = quote_token_program,
     )]
-    pub user_ata_quote: Box<Account<'info, TokenAccount>>,
+    pub user_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = base_mint,
         associated_token::authority = amm,
+        associated_token::token_program = base_token_program,
     )]
-    pub vault_ata_base: Account<'info, TokenAccount>,
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = quote_mint,
         associated_token::authority = amm,
+        associated_token::token_program = quote_token_program,
     )]
-    pub vault_ata_quote: Account<'info, TokenAccount>,
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// Holds the `MINIMUM_LIQUIDITY` locked on the first deposit.
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_ata_lp: Box<InterfaceAccount<'info, TokenAccount>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    /// The program that owns the LP mint, which is always a legacy SPL mint.
     pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
+
+/// Returns how much of `mint` needs to be sent for `post_fee_amount` to arrive.
+/// This is `post_fee_amount` unless `mint` is a Token-2022 mint with a transfer fee.
+pub fn get_pre_fee_amount(mint: &InterfaceAccount<Mint>, post_fee_amount: u64) -> Result<u64> {
+    let mint_info = mint.to_account_info();
+
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(post_fee_amount);
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    let Ok(transfer_fee_config) = mint_state.get_extension::<TransferFeeConfig>() else {
+        return Ok(post_fee_amount);
+    };
+
+    transfer_fee_config
+        .get_epoch_fee(Clock::get()?.epoch)
+        .calculate_pre_fee_amount(post_fee_amount)
+        .ok_or(error!(AmmError::TransferFeeCalculationError))
+}
+
+/// Moves any transfer fees withheld in `account` to `mint`, since a Token-2022
+/// account can't be closed while it's withholding fees. Does nothing unless
+/// `mint` has a transfer fee.
+pub fn harvest_withheld_fees<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    account: &InterfaceAccount<'info, TokenAccount>,
+) -> Result<()> {
+    let mint_info = mint.to_account_info();
+
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    {
+        let mint_data = mint_info.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+        if mint_state.get_extension::<TransferFeeConfig>().is_err() {
+            return Ok(());
+        }
+    }
+
+    let ix = transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+        token_program.key,
+        mint_info.key,
+        &[&account.key()],
+    )?;
+
+    anchor_lang::solana_program::program::invoke(
+        &ix,
+        &[mint_info, account.to_account_info(), token_program.clone()],
+    )?;
+
+    Ok(())
+}
+
+/// Does a `transfer_checked` and returns how much `to` actually received, which
+/// is less than `amount` if the mint charges a transfer fee.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_and_measure<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &mut InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<u64> {
+    let pre_balance = to.amount;
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+        mint.decimals,
+    )?;
+
+    to.reload()?;
+
+    let received = to.amount - pre_balance;
+
+    assert!(received <= amount);
+
+    Ok(received)
+}
@@ -1,18 +1,66 @@
 use anchor_lang::prelude::*;
 
+use crate::error::AmmError;
+use crate::events::{CommonFields, CrankThatTwapEvent};
 use crate::state::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CrankThatTwap<'info> {
     #[account(mut)]
     pub amm: Account<'info, Amm>,
+    /// The oracle's new state gets checkpointed here. Required once the AMM
+    /// has checkpoints.
+    #[account(
+        mut,
+        has_one = amm,
+        seeds = [TWAP_CHECKPOINTS_SEED_PREFIX, amm.key().as_ref()],
+        bump
+    )]
+    pub twap_checkpoints: Option<Box<Account<'info, TwapCheckpoints>>>,
 }
 
 impl CrankThatTwap<'_> {
+    pub fn validate(&self) -> Result<()> {
+        if self.amm.has_twap_checkpoints {
+            require!(
+                self.twap_checkpoints.is_some(),
+                AmmError::MissingTwapCheckpoints
+            );
+        }
+
+        Ok(())
+    }
+
     pub fn handle(ctx: Context<Self>) -> Result<()> {
-        let CrankThatTwap { amm } = ctx.accounts;
+        let CrankThatTwap {
+            amm,
+            twap_checkpoints,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        let clock = Clock::get()?;
+
+        let oracle_updated = amm.update_twap(clock.slot).is_some();
+
+        if let Some(twap_checkpoints) = twap_checkpoints {
+            twap_checkpoints.record(&amm.oracle);
+        }
+
+        // a crank within a minute of the last update leaves the amm as it
+        // was, so there's no state change to sequence
+        if !oracle_updated {
+            return Ok(());
+        }
+
+        amm.seq_num += 1;
+
+        let event = CrankThatTwapEvent {
+            common: CommonFields::new(&clock, amm.key(), amm),
+        };
 
-        amm.update_twap(Clock::get()?.slot);
+        emit_cpi!(event);
 
         Ok(())
     }
@@ -1,17 +1,27 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::error::AmmError;
+use crate::events::{CommonFields, CreateAmmEvent};
 use crate::state::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateAmmArgs {
+    /// Must be strictly less than `BPS_SCALE`, so 100 is a 1% fee.
+    pub swap_fee_bps: u64,
+    /// The share of the swap fee that goes to `protocol_fee_recipient`, at
+    /// most `BPS_SCALE`.
+    pub protocol_fee_bps: u64,
+    pub protocol_fee_recipient: Pubkey,
     pub twap_initial_observation: u128,
     pub twap_max_observation_change_per_update: u128,
+    pub twap_observation_clamp_mode: ObservationClampMode,
     pub proposal: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(args: CreateAmmArgs)]
 pub struct CreateAmm<'info> {
@@ -38,59 +48,107 @@ pub struct CreateAmm<'info> {
         mint::authority = amm,
         mint::freeze_authority = amm,
         mint::decimals = 9,
+        mint::token_program = token_program,
     )]
-    pub lp_mint: Box<Account<'info, Mint>>,
-    pub base_mint: Box<Account<'info, Mint>>,
-    pub quote_mint: Box<Account<'info, Mint>>,
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = base_token_program)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = quote_token_program)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         init_if_needed,
         payer = user,
         associated_token::authority = amm,
-        associated_token::mint = base_mint
+        associated_token::mint = base_mint,
+        associated_token::token_program = base_token_program,
     )]
-    pub vault_ata_base: Box<Account<'info, TokenAccount>>,
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         init_if_needed,
         payer = user,
         associated_token::authority = amm,
-        associated_token::mint = quote_mint
+        associated_token::mint = quote_mint,
+        associated_token::token_program = quote_token_program,
     )]
-    pub vault_ata_quote: Box<Account<'info, TokenAccount>>,
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// Holds the LP tokens that get permanently locked on the first deposit.
+    #[account(
+        init,
+        payer = user,
+        associated_token::authority = amm,
+        associated_token::mint = lp_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_ata_lp: Box<InterfaceAccount<'info, TokenAccount>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    /// The program that owns the LP mint. LP mints are always legacy SPL mints,
+    /// but the base and quote mints can be owned by either token program.
     pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 impl CreateAmm<'_> {
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, args: &CreateAmmArgs) -> Result<()> {
         require_neq!(
             self.base_mint.key(),
             self.quote_mint.key(),
             AmmError::SameTokenMints
         );
 
+        require_gt!(BPS_SCALE, args.swap_fee_bps, AmmError::InvalidSwapFee);
+        require_gte!(
+            BPS_SCALE,
+            args.protocol_fee_bps,
+            AmmError::InvalidProtocolFee
+        );
+
+        if args.twap_observation_clamp_mode == ObservationClampMode::Bps {
+            // a 0 observation would never be able to move
+            require!(
+                args.twap_initial_observation > 0,
+                AmmError::InvalidObservationClamp
+            );
+            require!(
+                args.twap_max_observation_change_per_update > 0
+                    && args.twap_max_observation_change_per_update <= BPS_SCALE as u128,
+                AmmError::InvalidObservationClamp
+            );
+        }
+
         Ok(())
     }
 
     pub fn handle(ctx: Context<Self>, args: CreateAmmArgs) -> Result<()> {
         let CreateAmm {
-            user: _,
+            user,
             amm,
             lp_mint,
             base_mint,
             quote_mint,
             vault_ata_base: _,
             vault_ata_quote: _,
+            vault_ata_lp: _,
             associated_token_program: _,
             token_program: _,
+            base_token_program: _,
+            quote_token_program: _,
             system_program: _,
+            event_authority: _,
+            program: _,
         } = ctx.accounts;
 
-        let current_slot = Clock::get()?.slot;
+        let clock = Clock::get()?;
+        let current_slot = clock.slot;
 
         let CreateAmmArgs {
+            swap_fee_bps,
+            protocol_fee_bps,
+            protocol_fee_recipient,
             twap_initial_observation,
             twap_max_observation_change_per_update,
+            twap_observation_clamp_mode,
             proposal,
         } = args;
 
@@ -110,13 +168,41 @@ impl CreateAmm<'_> {
             base_amount: 0,
             quote_amount: 0,
 
+            swap_fee_bps,
+            protocol_fee_bps,
+            protocol_fee_recipient,
+            base_protocol_fees: 0,
+            quote_protocol_fees: 0,
+
             oracle: TwapOracle::new(
                 current_slot,
                 twap_initial_observation,
                 twap_max_observation_change_per_update,
+                twap_observation_clamp_mode,
             ),
+
+            has_twap_checkpoints: false,
+            seq_num: 0,
+
+            rent_payer: user.key(),
         });
 
+        let event = CreateAmmEvent {
+            common: CommonFields::new(&clock, amm.key(), amm),
+            user: user.key(),
+            proposal,
+            lp_mint: lp_mint.key(),
+            base_mint: base_mint.key(),
+            quote_mint: quote_mint.key(),
+            swap_fee_bps,
+            protocol_fee_bps,
+            twap_initial_observation,
+            twap_max_observation_change_per_update,
+            twap_observation_clamp_mode,
+        };
+
+        emit_cpi!(event);
+
         Ok(())
     }
 }
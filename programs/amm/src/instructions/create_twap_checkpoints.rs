@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CreateTwapCheckpoints<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub amm: Account<'info, Amm>,
+    #[account(
+        init,
+        payer = payer,
+        space = TwapCheckpoints::SPACE,
+        seeds = [TWAP_CHECKPOINTS_SEED_PREFIX, amm.key().as_ref()],
+        bump
+    )]
+    pub twap_checkpoints: Box<Account<'info, TwapCheckpoints>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl CreateTwapCheckpoints<'_> {
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let CreateTwapCheckpoints {
+            payer,
+            amm,
+            twap_checkpoints,
+            system_program: _,
+        } = ctx.accounts;
+
+        twap_checkpoints.set_inner(TwapCheckpoints {
+            amm: amm.key(),
+            rent_payer: payer.key(),
+            next_index: 0,
+            checkpoints: Vec::with_capacity(MAX_TWAP_CHECKPOINTS),
+        });
+
+        // seed the buffer with wherever the oracle is at right now
+        twap_checkpoints.record(&amm.oracle);
+
+        amm.has_twap_checkpoints = true;
+
+        Ok(())
+    }
+}
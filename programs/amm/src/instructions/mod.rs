@@ -1,13 +1,21 @@
 pub use add_liquidity::*;
+pub use close_amm::*;
+pub use collect_protocol_fees::*;
 pub use common::*;
 pub use crank_that_twap::*;
 pub use create_amm::*;
+pub use create_twap_checkpoints::*;
+pub use quote::*;
 pub use remove_liquidity::*;
 pub use swap::*;
 
 pub mod add_liquidity;
+pub mod close_amm;
+pub mod collect_protocol_fees;
 pub mod common;
 pub mod crank_that_twap;
 pub mod create_amm;
+pub mod create_twap_checkpoints;
+pub mod quote;
 pub mod remove_liquidity;
 pub mod swap;
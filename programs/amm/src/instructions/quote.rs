@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QuoteSwapArgs {
+    pub swap_type: SwapType,
+    pub input_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QuoteAddLiquidityArgs {
+    pub quote_amount: u64,
+    pub max_base_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QuoteRemoveLiquidityArgs {
+    pub lp_tokens_to_burn: u64,
+}
+
+/// Read-only instructions that run the same math as `swap`, `add_liquidity`
+/// and `remove_liquidity` and hand back the result as return data, so that
+/// clients can simulate against the program instead of reimplementing it.
+///
+/// Amounts are what the vaults would receive and send, so they don't include
+/// any Token-2022 transfer fees.
+#[derive(Accounts)]
+pub struct Quote<'info> {
+    pub amm: Account<'info, Amm>,
+    #[account(address = amm.lp_mint)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+impl Quote<'_> {
+    pub fn handle_swap(ctx: Context<Self>, args: QuoteSwapArgs) -> Result<SwapQuote> {
+        let QuoteSwapArgs {
+            swap_type,
+            input_amount,
+        } = args;
+
+        ctx.accounts.amm.quote_swap(input_amount, swap_type)
+    }
+
+    pub fn handle_add_liquidity(
+        ctx: Context<Self>,
+        args: QuoteAddLiquidityArgs,
+    ) -> Result<AddLiquidityQuote> {
+        let QuoteAddLiquidityArgs {
+            quote_amount,
+            max_base_amount,
+        } = args;
+
+        ctx.accounts.amm.quote_add_liquidity(
+            quote_amount,
+            max_base_amount,
+            ctx.accounts.lp_mint.supply,
+        )
+    }
+
+    pub fn handle_remove_liquidity(
+        ctx: Context<Self>,
+        args: QuoteRemoveLiquidityArgs,
+    ) -> Result<RemoveLiquidityQuote> {
+        let QuoteRemoveLiquidityArgs { lp_tokens_to_burn } = args;
+
+        ctx.accounts
+            .amm
+            .quote_remove_liquidity(lp_tokens_to_burn, ctx.accounts.lp_mint.supply)
+    }
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Transfer};
+use anchor_spl::token::{self, Burn};
 
+use crate::events::{CommonFields, RemoveLiquidityEvent};
 use crate::{error::AmmError, *};
 
 #[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
@@ -20,16 +21,21 @@ impl AddOrRemoveLiquidity<'_> {
             amm,
             lp_mint,
 
-            base_mint: _,
-            quote_mint: _,
+            base_mint,
+            quote_mint,
             user_ata_lp,
             user_ata_base,
             user_ata_quote,
             vault_ata_base,
             vault_ata_quote,
+            vault_ata_lp: _,
             associated_token_program: _,
             token_program,
+            base_token_program,
+            quote_token_program,
             system_program: _,
+            event_authority: _,
+            program: _,
         } = ctx.accounts;
 
         let RemoveLiquidityArgs {
@@ -46,7 +52,9 @@ impl AddOrRemoveLiquidity<'_> {
 
         require!(lp_tokens_to_burn > 0, AmmError::ZeroLiquidityRemove);
 
-        amm.update_twap(Clock::get()?.slot);
+        let clock = Clock::get()?;
+
+        amm.update_twap(clock.slot);
 
         // airlifted from uniswap v1:
         // https://github.com/Uniswap/v1-contracts/blob/c10c08d81d6114f694baa8bd32f555a40f6264da/contracts/uniswap_exchange.vy#L83
@@ -57,17 +65,6 @@ impl AddOrRemoveLiquidity<'_> {
         let (base_to_withdraw, quote_to_withdraw) =
             amm.get_base_and_quote_withdrawable(lp_tokens_to_burn, total_liquidity);
 
-        require_gte!(
-            base_to_withdraw,
-            min_base_amount,
-            AmmError::SwapSlippageExceeded
-        );
-        require_gte!(
-            quote_to_withdraw,
-            min_quote_amount,
-            AmmError::SwapSlippageExceeded
-        );
-
         token::burn(
             CpiContext::new(
                 token_program.to_account_info(),
@@ -85,24 +82,51 @@ impl AddOrRemoveLiquidity<'_> {
 
         let seeds = generate_amm_seeds!(amm);
 
-        for (amount_to_withdraw, from, to) in [
-            (base_to_withdraw, vault_ata_base, user_ata_base),
-            (quote_to_withdraw, vault_ata_quote, user_ata_quote),
+        // the slippage checks are against what the user actually receives, which
+        // is less than what's withdrawn if a mint charges a transfer fee
+        for (amount_to_withdraw, min_amount, token_program, mint, from, to) in [
+            (
+                base_to_withdraw,
+                min_base_amount,
+                base_token_program,
+                base_mint,
+                vault_ata_base,
+                user_ata_base,
+            ),
+            (
+                quote_to_withdraw,
+                min_quote_amount,
+                quote_token_program,
+                quote_mint,
+                vault_ata_quote,
+                user_ata_quote,
+            ),
         ] {
-            token::transfer(
-                CpiContext::new_with_signer(
-                    token_program.to_account_info(),
-                    Transfer {
-                        from: from.to_account_info(),
-                        to: to.to_account_info(),
-                        authority: amm.to_account_info(),
-                    },
-                    &[seeds],
-                ),
+            let amount_received = transfer_checked_and_measure(
+                token_program,
+                mint,
+                from,
+                to,
+                amm.to_account_info(),
+                &[seeds],
                 amount_to_withdraw,
             )?;
+
+            require_gte!(amount_received, min_amount, AmmError::SwapSlippageExceeded);
         }
 
+        amm.seq_num += 1;
+
+        let event = RemoveLiquidityEvent {
+            common: CommonFields::new(&clock, amm.key(), amm),
+            user: user.key(),
+            lp_tokens_burned: lp_tokens_to_burn,
+            base_amount: base_to_withdraw,
+            quote_amount: quote_to_withdraw,
+        };
+
+        emit_cpi!(event);
+
         Ok(())
     }
 }
@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, *};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::error::AmmError;
+use crate::events::{CommonFields, SwapEvent};
 use crate::generate_amm_seeds;
 use crate::state::*;
+use crate::{get_pre_fee_amount, transfer_checked_and_measure};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct SwapArgs {
@@ -13,6 +15,14 @@ pub struct SwapArgs {
     pub output_amount_min: u64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SwapExactOutputArgs {
+    pub swap_type: SwapType,
+    pub output_amount: u64,
+    pub input_amount_max: u64,
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Swap<'info> {
     #[account(mut)]
@@ -23,126 +33,256 @@ pub struct Swap<'info> {
         has_one = quote_mint,
     )]
     pub amm: Account<'info, Amm>,
-    pub base_mint: Account<'info, Mint>,
-    pub quote_mint: Account<'info, Mint>,
+    #[account(mint::token_program = base_token_program)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = quote_token_program)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         mut,
         associated_token::mint = base_mint,
         associated_token::authority = user,
+        associated_token::token_program = base_token_program,
     )]
-    pub user_ata_base: Account<'info, TokenAccount>,
+    pub user_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = quote_mint,
         associated_token::authority = user,
+        associated_token::token_program = quote_token_program,
     )]
-    pub user_ata_quote: Account<'info, TokenAccount>,
+    pub user_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = base_mint,
         associated_token::authority = amm,
+        associated_token::token_program = base_token_program,
     )]
-    pub vault_ata_base: Account<'info, TokenAccount>,
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = quote_mint,
         associated_token::authority = amm,
+        associated_token::token_program = quote_token_program,
     )]
-    pub vault_ata_quote: Account<'info, TokenAccount>,
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 impl Swap<'_> {
     pub fn handle(ctx: Context<Swap>, args: SwapArgs) -> Result<()> {
-        let Swap {
-            user,
-            amm,
-            base_mint: _,
-            quote_mint: _,
-            user_ata_base,
-            user_ata_quote,
-            vault_ata_base,
-            vault_ata_quote,
-            associated_token_program: _,
-            token_program,
-            system_program: _,
-        } = ctx.accounts;
-
         let SwapArgs {
             swap_type,
             input_amount,
             output_amount_min,
         } = args;
 
+        ctx.accounts
+            .require_input_balance(input_amount, swap_type)?;
+
+        assert!(input_amount > 0);
+
+        let clock = Clock::get()?;
+
+        ctx.accounts.amm.update_twap(clock.slot);
+
+        // swap whatever the vault actually received, which is less than
+        // `input_amount` if the input mint charges a transfer fee
+        let input_amount = ctx.accounts.transfer_in(input_amount, swap_type)?;
+
+        let output_amount = ctx.accounts.amm.swap(input_amount, swap_type)?;
+
+        let output_amount_received = ctx.accounts.transfer_out(output_amount, swap_type)?;
+
+        require_gte!(
+            output_amount_received,
+            output_amount_min,
+            AmmError::SwapSlippageExceeded
+        );
+
+        let event = ctx
+            .accounts
+            .swap_event(&clock, swap_type, input_amount, output_amount);
+
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn handle_exact_output(ctx: Context<Swap>, args: SwapExactOutputArgs) -> Result<()> {
+        let SwapExactOutputArgs {
+            swap_type,
+            output_amount,
+            input_amount_max,
+        } = args;
+
+        assert!(output_amount > 0);
+
+        let clock = Clock::get()?;
+
+        ctx.accounts.amm.update_twap(clock.slot);
+
+        let (input_mint, output_mint) = match swap_type {
+            SwapType::Buy => (&ctx.accounts.quote_mint, &ctx.accounts.base_mint),
+            SwapType::Sell => (&ctx.accounts.base_mint, &ctx.accounts.quote_mint),
+        };
+
+        // gross both sides up for transfer fees so that the user gets exactly
+        // `output_amount` and the vault gets at least what the swap needs
+        let output_amount_to_send = get_pre_fee_amount(output_mint, output_amount)?;
+        let input_amount_needed = ctx
+            .accounts
+            .amm
+            .get_swap_input(output_amount_to_send, swap_type)?;
+        let input_amount_to_send = get_pre_fee_amount(input_mint, input_amount_needed)?;
+
+        require_gte!(
+            input_amount_max,
+            input_amount_to_send,
+            AmmError::SwapInputMaxExceeded
+        );
+
+        ctx.accounts
+            .require_input_balance(input_amount_to_send, swap_type)?;
+
+        let input_amount = ctx.accounts.transfer_in(input_amount_to_send, swap_type)?;
+
+        assert!(input_amount >= input_amount_needed);
+
+        ctx.accounts
+            .amm
+            .apply_swap(input_amount, output_amount_to_send, swap_type);
+
+        let output_amount_received = ctx
+            .accounts
+            .transfer_out(output_amount_to_send, swap_type)?;
+
+        require_gte!(
+            output_amount_received,
+            output_amount,
+            AmmError::SwapSlippageExceeded
+        );
+
+        let event = ctx
+            .accounts
+            .swap_event(&clock, swap_type, input_amount, output_amount_to_send);
+
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    fn require_input_balance(&self, input_amount: u64, swap_type: SwapType) -> Result<()> {
         match swap_type {
             SwapType::Buy => require_gte!(
-                user_ata_quote.amount,
+                self.user_ata_quote.amount,
                 input_amount,
                 AmmError::InsufficientBalance
             ),
             SwapType::Sell => require_gte!(
-                user_ata_base.amount,
+                self.user_ata_base.amount,
                 input_amount,
                 AmmError::InsufficientBalance
             ),
         };
 
-        assert!(input_amount > 0);
-
-        amm.update_twap(Clock::get()?.slot);
-
-        let output_amount = amm.swap(input_amount, swap_type)?;
+        Ok(())
+    }
 
-        let seeds = generate_amm_seeds!(amm);
+    /// Moves `amount` from the user into the input vault and returns how much
+    /// the vault received.
+    fn transfer_in(&mut self, amount: u64, swap_type: SwapType) -> Result<u64> {
+        let Swap {
+            user,
+            base_mint,
+            quote_mint,
+            user_ata_base,
+            user_ata_quote,
+            vault_ata_base,
+            vault_ata_quote,
+            base_token_program,
+            quote_token_program,
+            ..
+        } = self;
 
-        let (user_from, vault_to, vault_from, user_to) = match swap_type {
+        let (token_program, mint, user_from, vault_to) = match swap_type {
             SwapType::Buy => (
+                quote_token_program,
+                quote_mint,
                 user_ata_quote,
                 vault_ata_quote,
-                vault_ata_base,
-                user_ata_base,
             ),
+            SwapType::Sell => (base_token_program, base_mint, user_ata_base, vault_ata_base),
+        };
+
+        transfer_checked_and_measure(
+            token_program,
+            mint,
+            user_from,
+            vault_to,
+            user.to_account_info(),
+            &[],
+            amount,
+        )
+    }
+
+    /// Moves `amount` from the output vault to the user and returns how much
+    /// the user received. The AMM's accounting must already be done.
+    fn transfer_out(&mut self, amount: u64, swap_type: SwapType) -> Result<u64> {
+        let Swap {
+            amm,
+            base_mint,
+            quote_mint,
+            user_ata_base,
+            user_ata_quote,
+            vault_ata_base,
+            vault_ata_quote,
+            base_token_program,
+            quote_token_program,
+            ..
+        } = self;
+
+        let seeds = generate_amm_seeds!(amm);
+
+        let (token_program, mint, vault_from, user_to) = match swap_type {
+            SwapType::Buy => (base_token_program, base_mint, vault_ata_base, user_ata_base),
             SwapType::Sell => (
-                user_ata_base,
-                vault_ata_base,
+                quote_token_program,
+                quote_mint,
                 vault_ata_quote,
                 user_ata_quote,
             ),
         };
 
-        token::transfer(
-            CpiContext::new(
-                token_program.to_account_info(),
-                Transfer {
-                    from: user_from.to_account_info(),
-                    to: vault_to.to_account_info(),
-                    authority: user.to_account_info(),
-                },
-            ),
-            input_amount,
-        )?;
-
-        token::transfer(
-            CpiContext::new_with_signer(
-                token_program.to_account_info(),
-                Transfer {
-                    from: vault_from.to_account_info(),
-                    to: user_to.to_account_info(),
-                    authority: amm.to_account_info(),
-                },
-                &[seeds],
-            ),
-            output_amount,
-        )?;
+        transfer_checked_and_measure(
+            token_program,
+            mint,
+            vault_from,
+            user_to,
+            amm.to_account_info(),
+            &[seeds],
+            amount,
+        )
+    }
 
-        require_gte!(
-            output_amount,
-            output_amount_min,
-            AmmError::SwapSlippageExceeded
-        );
+    /// Bumps the AMM's `seq_num` and builds the event for a finished swap.
+    fn swap_event(
+        &mut self,
+        clock: &Clock,
+        swap_type: SwapType,
+        input_amount: u64,
+        output_amount: u64,
+    ) -> SwapEvent {
+        self.amm.seq_num += 1;
 
-        Ok(())
+        SwapEvent {
+            common: CommonFields::new(clock, self.amm.key(), &self.amm),
+            user: self.user.key(),
+            swap_type,
+            input_amount,
+            output_amount,
+        }
     }
 }
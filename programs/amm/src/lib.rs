@@ -16,6 +16,7 @@ security_txt! {
 }
 
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -30,7 +31,7 @@ pub mod amm {
 
     use super::*;
 
-    #[access_control(ctx.accounts.validate())]
+    #[access_control(ctx.accounts.validate(&args))]
     pub fn create_amm(ctx: Context<CreateAmm>, args: CreateAmmArgs) -> Result<()> {
         CreateAmm::handle(ctx, args)
     }
@@ -50,7 +51,43 @@ pub mod amm {
         Swap::handle(ctx, args)
     }
 
+    pub fn swap_exact_output(ctx: Context<Swap>, args: SwapExactOutputArgs) -> Result<()> {
+        Swap::handle_exact_output(ctx, args)
+    }
+
+    #[access_control(ctx.accounts.validate())]
     pub fn crank_that_twap(ctx: Context<CrankThatTwap>) -> Result<()> {
         CrankThatTwap::handle(ctx)
     }
+
+    pub fn create_twap_checkpoints(ctx: Context<CreateTwapCheckpoints>) -> Result<()> {
+        CreateTwapCheckpoints::handle(ctx)
+    }
+
+    pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
+        CollectProtocolFees::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn close_amm(ctx: Context<CloseAmm>) -> Result<()> {
+        CloseAmm::handle(ctx)
+    }
+
+    pub fn quote_swap(ctx: Context<Quote>, args: QuoteSwapArgs) -> Result<SwapQuote> {
+        Quote::handle_swap(ctx, args)
+    }
+
+    pub fn quote_add_liquidity(
+        ctx: Context<Quote>,
+        args: QuoteAddLiquidityArgs,
+    ) -> Result<AddLiquidityQuote> {
+        Quote::handle_add_liquidity(ctx, args)
+    }
+
+    pub fn quote_remove_liquidity(
+        ctx: Context<Quote>,
+        args: QuoteRemoveLiquidityArgs,
+    ) -> Result<RemoveLiquidityQuote> {
+        Quote::handle_remove_liquidity(ctx, args)
+    }
 }
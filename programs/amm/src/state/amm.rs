@@ -2,7 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::clock::Slot;
 
 use crate::error::AmmError;
-use crate::{MAX_PRICE, ONE_MINUTE_IN_SLOTS, PRICE_SCALE};
+use crate::{
+    BPS_SCALE, MAX_PRICE, MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT, MINIMUM_LIQUIDITY,
+    ONE_MINUTE_IN_SLOTS, PRICE_SCALE,
+};
 use std::cmp::{max, min, Ordering};
 
 #[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize)]
@@ -13,6 +16,48 @@ pub enum SwapType {
     Sell,
 }
 
+/// What a swap would do to a pool, as returned by `quote_swap`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct SwapQuote {
+    pub input_amount: u64,
+    pub output_amount: u64,
+    /// The whole swap fee taken from `input_amount`, including the protocol's cut.
+    pub fee_amount: u64,
+    pub protocol_fee_amount: u64,
+    /// How far the swap moves the pool price, in basis points of the pre-trade
+    /// price.
+    pub price_impact_bps: u64,
+    /// The pool price after the swap, scaled the same way as `TwapOracle` prices.
+    pub post_trade_price: u128,
+}
+
+/// What adding liquidity would do, as returned by `quote_add_liquidity`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct AddLiquidityQuote {
+    pub quote_amount: u64,
+    pub base_amount: u64,
+    /// The LP tokens the depositor gets, net of any locked `MINIMUM_LIQUIDITY`.
+    pub lp_tokens_minted: u64,
+}
+
+/// What removing liquidity would do, as returned by `quote_remove_liquidity`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveLiquidityQuote {
+    pub lp_tokens_burned: u64,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum ObservationClampMode {
+    /// `max_observation_change_per_update` is an absolute change in price units.
+    #[default]
+    Absolute,
+    /// `max_observation_change_per_update` is in basis points of the last
+    /// observation, so it doesn't need re-tuning as the price moves.
+    Bps,
+}
+
 #[derive(Default, Clone, Copy, Debug, AnchorDeserialize, AnchorSerialize)]
 pub struct TwapOracle {
     pub last_updated_slot: u64,
@@ -40,10 +85,12 @@ pub struct TwapOracle {
     /// client's responsibility to sanity check the assets or to handle an
     /// aggregator at t2 being smaller than an aggregator at t1.
     pub aggregator: u128,
-    /// The most that an observation can change per update.
+    /// The most that an observation can change per update, interpreted
+    /// according to `observation_clamp_mode`.
     pub max_observation_change_per_update: u128,
     /// What the initial `latest_observation` is set to.
     pub initial_observation: u128,
+    pub observation_clamp_mode: ObservationClampMode,
 }
 
 impl TwapOracle {
@@ -51,6 +98,7 @@ impl TwapOracle {
         current_slot: Slot,
         initial_observation: u128,
         max_observation_change_per_update: u128,
+        observation_clamp_mode: ObservationClampMode,
     ) -> Self {
         Self {
             last_updated_slot: current_slot,
@@ -59,6 +107,21 @@ impl TwapOracle {
             aggregator: 0,
             max_observation_change_per_update,
             initial_observation,
+            observation_clamp_mode,
+        }
+    }
+
+    /// The most that the next observation can move away from `last_observation`.
+    pub fn max_observation_change(&self) -> u128 {
+        match self.observation_clamp_mode {
+            ObservationClampMode::Absolute => self.max_observation_change_per_update,
+            // always allow at least 1 so that tiny observations don't get stuck
+            ObservationClampMode::Bps => max(
+                self.last_observation
+                    .saturating_mul(self.max_observation_change_per_update)
+                    / BPS_SCALE as u128,
+                1,
+            ),
         }
     }
 }
@@ -84,7 +147,30 @@ pub struct Amm {
     pub base_amount: u64,
     pub quote_amount: u64,
 
+    /// The fee charged on the input side of every swap, in basis points. It
+    /// stays in the pool and accrues to LPs.
+    pub swap_fee_bps: u64,
+    /// The share of every swap fee, in basis points, that goes to the protocol
+    /// instead of to LPs.
+    pub protocol_fee_bps: u64,
+    /// Who `collect_protocol_fees` pays out to.
+    pub protocol_fee_recipient: Pubkey,
+    /// Protocol fees sit in the vaults alongside the reserves, but they aren't
+    /// part of `base_amount` / `quote_amount` so they don't affect pricing.
+    pub base_protocol_fees: u64,
+    pub quote_protocol_fees: u64,
+
     pub oracle: TwapOracle,
+
+    /// Set by `create_twap_checkpoints`. From then on, every `crank_that_twap`
+    /// has to pass the checkpoints so that none of the cranks get skipped.
+    pub has_twap_checkpoints: bool,
+
+    /// Incremented on every state change and included in every event.
+    pub seq_num: u64,
+
+    /// Who paid for this AMM's accounts, and gets the rent back on `close_amm`.
+    pub rent_payer: Pubkey,
 }
 
 impl Amm {
@@ -92,17 +178,15 @@ impl Amm {
         self.base_amount as u128 * self.quote_amount as u128
     }
 
-    /// Does the internal accounting to swap `input_amount` into the returned
-    /// output amount so that output amount can be transferred to the user.
-    pub fn swap(&mut self, input_amount: u64, swap_type: SwapType) -> Result<u64> {
-        let base_amount_start = self.base_amount as u128;
-        let quote_amount_start = self.quote_amount as u128;
-
-        let k = self.k();
+    /// Returns how many output tokens a swap of `input_amount` would yield at
+    /// the current reserves, net of `swap_fee_bps`. Doesn't mutate the AMM.
+    pub fn get_swap_output(&self, input_amount: u64, swap_type: SwapType) -> Result<u64> {
+        let base_amount = self.base_amount as u128;
+        let quote_amount = self.quote_amount as u128;
 
         let (input_reserve, output_reserve) = match swap_type {
-            SwapType::Buy => (quote_amount_start, base_amount_start),
-            SwapType::Sell => (base_amount_start, quote_amount_start),
+            SwapType::Buy => (quote_amount, base_amount),
+            SwapType::Sell => (base_amount, quote_amount),
         };
 
         // airlifted from uniswap v1:
@@ -111,23 +195,100 @@ impl Amm {
         require!(input_reserve != 0, AmmError::NoReserves);
         require!(output_reserve != 0, AmmError::NoReserves);
 
-        let input_amount_with_fee = input_amount as u128 * 990;
+        let input_amount_with_fee = input_amount as u128 * (BPS_SCALE - self.swap_fee_bps) as u128;
 
         let numerator = input_amount_with_fee
             .checked_mul(output_reserve)
             .ok_or(error!(AmmError::InputAmountOverflow))?;
 
-        let denominator = (input_reserve * 1000) + input_amount_with_fee;
+        let denominator = (input_reserve * BPS_SCALE as u128) + input_amount_with_fee;
+
+        Ok((numerator / denominator) as u64)
+    }
+
+    /// Returns how many input tokens are needed to get exactly `output_amount`
+    /// out of a swap at the current reserves, fees included. Rounds up, so the
+    /// pool never gives out more than it's paid for. Doesn't mutate the AMM.
+    pub fn get_swap_input(&self, output_amount: u64, swap_type: SwapType) -> Result<u64> {
+        let base_amount = self.base_amount as u128;
+        let quote_amount = self.quote_amount as u128;
+
+        let (input_reserve, output_reserve) = match swap_type {
+            SwapType::Buy => (quote_amount, base_amount),
+            SwapType::Sell => (base_amount, quote_amount),
+        };
+
+        // the inverse of `get_swap_output`, see uniswap v2's `getAmountIn`:
+        // https://github.com/Uniswap/v2-periphery/blob/0335e8f7e1bd1e8d8329fd300aea2ef2f36dd19f/contracts/libraries/UniswapV2Library.sol#L53-L60
+
+        require!(input_reserve != 0, AmmError::NoReserves);
+        require!(output_reserve != 0, AmmError::NoReserves);
+        require_gt!(
+            output_reserve,
+            output_amount as u128,
+            AmmError::OutputAmountExceedsReserves
+        );
+
+        let numerator = input_reserve
+            .checked_mul(output_amount as u128)
+            .and_then(|n| n.checked_mul(BPS_SCALE as u128))
+            .ok_or(error!(AmmError::OutputAmountOverflow))?;
+
+        let denominator =
+            (output_reserve - output_amount as u128) * (BPS_SCALE - self.swap_fee_bps) as u128;
+
+        let input_amount = numerator.div_ceil(denominator);
+
+        u64::try_from(input_amount).map_err(|_| error!(AmmError::OutputAmountOverflow))
+    }
+
+    /// Returns the part of the fee on `input_amount` that goes to the protocol.
+    /// Rounds down so that LPs get any dust.
+    pub fn get_protocol_fee(&self, input_amount: u64) -> u64 {
+        let swap_fee = (input_amount as u128 * self.swap_fee_bps as u128) / BPS_SCALE as u128;
+
+        // must fit into u64 since it's smaller than `input_amount`
+        ((swap_fee * self.protocol_fee_bps as u128) / BPS_SCALE as u128) as u64
+    }
+
+    /// Does the internal accounting to swap `input_amount` into the returned
+    /// output amount so that output amount can be transferred to the user.
+    pub fn swap(&mut self, input_amount: u64, swap_type: SwapType) -> Result<u64> {
+        let output_amount = self.get_swap_output(input_amount, swap_type)?;
+
+        self.apply_swap(input_amount, output_amount, swap_type);
 
-        let output_amount = (numerator / denominator) as u64;
+        Ok(output_amount)
+    }
+
+    /// Does the internal accounting to swap the returned input amount into
+    /// exactly `output_amount` so that input amount can be taken from the user.
+    pub fn swap_exact_output(&mut self, output_amount: u64, swap_type: SwapType) -> Result<u64> {
+        let input_amount = self.get_swap_input(output_amount, swap_type)?;
+
+        self.apply_swap(input_amount, output_amount, swap_type);
+
+        Ok(input_amount)
+    }
+
+    /// Moves `input_amount` into and `output_amount` out of the reserves, net of
+    /// protocol fees. Panics if that would decrease k.
+    pub fn apply_swap(&mut self, input_amount: u64, output_amount: u64, swap_type: SwapType) {
+        let k = self.k();
+
+        // the protocol's cut is always smaller than the fee, so k still can't go down
+        let protocol_fee = self.get_protocol_fee(input_amount);
+        let input_to_reserves = input_amount - protocol_fee;
 
         match swap_type {
             SwapType::Buy => {
-                self.quote_amount += input_amount;
+                self.quote_amount += input_to_reserves;
+                self.quote_protocol_fees += protocol_fee;
                 self.base_amount -= output_amount;
             }
             SwapType::Sell => {
-                self.base_amount += input_amount;
+                self.base_amount += input_to_reserves;
+                self.base_protocol_fees += protocol_fee;
                 self.quote_amount -= output_amount;
             }
         }
@@ -135,8 +296,112 @@ impl Amm {
         let new_k = self.k();
 
         assert!(new_k >= k);
+    }
 
-        Ok(output_amount)
+    /// Returns the pool's current price, scaled the same way as `TwapOracle`
+    /// prices. Must only be called when the pool has reserves.
+    pub fn get_price(&self) -> u128 {
+        (self.quote_amount as u128 * PRICE_SCALE) / self.base_amount as u128
+    }
+
+    /// Runs a swap of `input_amount` against a copy of the pool and reports
+    /// what it would do, without touching this AMM's state.
+    pub fn quote_swap(&self, input_amount: u64, swap_type: SwapType) -> Result<SwapQuote> {
+        let mut amm = self.clone();
+
+        let output_amount = amm.swap(input_amount, swap_type)?;
+
+        let pre_trade_price = self.get_price();
+        let post_trade_price = amm.get_price();
+
+        let price_impact_bps = (pre_trade_price.abs_diff(post_trade_price) * BPS_SCALE as u128)
+            .checked_div(pre_trade_price)
+            .map_or(u64::MAX, |bps| bps.try_into().unwrap_or(u64::MAX));
+
+        Ok(SwapQuote {
+            input_amount,
+            output_amount,
+            fee_amount: ((input_amount as u128 * self.swap_fee_bps as u128) / BPS_SCALE as u128)
+                as u64,
+            protocol_fee_amount: self.get_protocol_fee(input_amount),
+            price_impact_bps,
+            post_trade_price,
+        })
+    }
+
+    /// Reports what depositing `quote_amount` would mint, mirroring
+    /// `add_liquidity`. On a pool's first deposit all of `max_base_amount` is
+    /// deposited and `MINIMUM_LIQUIDITY` is locked.
+    pub fn quote_add_liquidity(
+        &self,
+        quote_amount: u64,
+        max_base_amount: u64,
+        lp_total_supply: u64,
+    ) -> Result<AddLiquidityQuote> {
+        require!(max_base_amount > 0, AmmError::ZeroLiquidityToAdd);
+        require!(quote_amount > 0, AmmError::ZeroLiquidityToAdd);
+
+        let (base_amount, lp_tokens_minted) = if lp_total_supply > 0 {
+            let (base_amount, lp_tokens_to_mint) =
+                self.get_liquidity_to_add(quote_amount, lp_total_supply);
+
+            require_gte!(
+                max_base_amount,
+                base_amount,
+                AmmError::AddLiquidityMaxBaseExceeded
+            );
+
+            (base_amount, lp_tokens_to_mint)
+        } else {
+            require_gte!(
+                quote_amount,
+                MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT,
+                AmmError::InsufficientQuoteAmount
+            );
+
+            (max_base_amount, quote_amount - MINIMUM_LIQUIDITY)
+        };
+
+        Ok(AddLiquidityQuote {
+            quote_amount,
+            base_amount,
+            lp_tokens_minted,
+        })
+    }
+
+    /// Reports what burning `lp_tokens` would withdraw, mirroring
+    /// `remove_liquidity`.
+    pub fn quote_remove_liquidity(
+        &self,
+        lp_tokens: u64,
+        lp_total_supply: u64,
+    ) -> Result<RemoveLiquidityQuote> {
+        require!(lp_tokens > 0, AmmError::ZeroLiquidityRemove);
+        require_gte!(lp_total_supply, lp_tokens, AmmError::InsufficientBalance);
+
+        let (base_amount, quote_amount) =
+            self.get_base_and_quote_withdrawable(lp_tokens, lp_total_supply);
+
+        Ok(RemoveLiquidityQuote {
+            lp_tokens_burned: lp_tokens,
+            base_amount,
+            quote_amount,
+        })
+    }
+
+    /// Returns the base tokens that need to be deposited alongside `quote_amount`
+    /// to keep the pool's ratio, and the LP tokens that should be minted for
+    /// them. Rounds in the pool's favor on both.
+    pub fn get_liquidity_to_add(&self, quote_amount: u64, lp_total_supply: u64) -> (u64, u64) {
+        let quote_reserve = self.quote_amount as u128;
+        let base_reserve = self.base_amount as u128;
+
+        let base_amount = (((quote_amount as u128 * base_reserve) / quote_reserve) + 1) as u64;
+
+        let lp_tokens_to_mint =
+            ((quote_amount as u128 * lp_total_supply as u128) / quote_reserve) as u64;
+
+        (base_amount, lp_tokens_to_mint)
     }
 
     /// Get the number of base and quote tokens withdrawable from a position
@@ -214,15 +479,14 @@ impl Amm {
         let price = (self.quote_amount as u128 * PRICE_SCALE) / self.base_amount as u128;
 
         let last_observation = oracle.last_observation;
+        let max_observation_change = oracle.max_observation_change();
 
         let new_observation = if price > last_observation {
-            let max_observation =
-                last_observation.saturating_add(oracle.max_observation_change_per_update);
+            let max_observation = last_observation.saturating_add(max_observation_change);
 
             min(price, max_observation)
         } else {
-            let min_observation =
-                last_observation.saturating_sub(oracle.max_observation_change_per_update);
+            let min_observation = last_observation.saturating_sub(max_observation_change);
 
             max(price, min_observation)
         };
@@ -240,9 +504,10 @@ impl Amm {
             last_price: price,
             last_observation: new_observation,
             aggregator: new_aggregator,
-            // these two shouldn't change
+            // these three shouldn't change
             max_observation_change_per_update: oracle.max_observation_change_per_update,
             initial_observation: oracle.initial_observation,
+            observation_clamp_mode: oracle.observation_clamp_mode,
         };
 
         assert!(new_oracle.last_updated_slot > oracle.last_updated_slot);
@@ -311,6 +576,7 @@ mod simple_amm_tests {
         let mut amm = Amm {
             base_amount: 3,
             quote_amount: 8,
+            swap_fee_bps: 100,
             ..Amm::default()
         };
 
@@ -331,12 +597,255 @@ mod simple_amm_tests {
         assert_eq!(amm_clone.k(), 30); // 2 x 15
     }
 
+    #[test]
+    pub fn swap_fee_tiers() {
+        let amm = |swap_fee_bps| Amm {
+            base_amount: 1_000_000,
+            quote_amount: 1_000_000,
+            swap_fee_bps,
+            ..Amm::default()
+        };
+
+        // no fee is just x * y = k, rounded down
+        assert_eq!(amm(0).get_swap_output(10_000, Sell).unwrap(), 9_900);
+        assert_eq!(amm(30).get_swap_output(10_000, Sell).unwrap(), 9_871);
+        assert_eq!(amm(100).get_swap_output(10_000, Sell).unwrap(), 9_802);
+        assert_eq!(amm(300).get_swap_output(10_000, Buy).unwrap(), 9_606);
+
+        // the quote should match what a swap actually does
+        let mut thin_amm = amm(30);
+        let quoted = thin_amm.get_swap_output(10_000, Buy).unwrap();
+        assert_eq!(thin_amm.swap(10_000, Buy).unwrap(), quoted);
+        assert_eq!(thin_amm.base_amount, 1_000_000 - quoted);
+        assert_eq!(thin_amm.quote_amount, 1_010_000);
+    }
+
+    #[test]
+    pub fn protocol_fees_excluded_from_reserves() {
+        let mut amm = Amm {
+            base_amount: 1_000_000,
+            quote_amount: 1_000_000,
+            swap_fee_bps: 100,
+            protocol_fee_bps: 2_500,
+            ..Amm::default()
+        };
+
+        let k = amm.k();
+
+        // a 1% fee on 100_000 is 1_000, a quarter of which goes to the protocol
+        assert_eq!(amm.get_protocol_fee(100_000), 250);
+
+        let output = amm.swap(100_000, Buy).unwrap();
+        assert_eq!(output, 90_081);
+        assert_eq!(amm.quote_amount, 1_000_000 + 100_000 - 250);
+        assert_eq!(amm.quote_protocol_fees, 250);
+        assert_eq!(amm.base_protocol_fees, 0);
+        assert!(amm.k() > k);
+
+        amm.swap(100_000, Sell).unwrap();
+        assert_eq!(amm.base_amount, 1_000_000 - output + 100_000 - 250);
+        assert_eq!(amm.base_protocol_fees, 250);
+
+        // dust rounds in favor of LPs
+        assert_eq!(amm.get_protocol_fee(399), 0);
+    }
+
+    #[test]
+    pub fn exact_output_swaps() {
+        let amm = Amm {
+            base_amount: 1_000_000,
+            quote_amount: 2_000_000,
+            swap_fee_bps: 30,
+            ..Amm::default()
+        };
+
+        for swap_type in [Buy, Sell] {
+            for output_amount in [1, 7, 1_000, 123_456, 999_999] {
+                let input_amount = amm.get_swap_input(output_amount, swap_type).unwrap();
+
+                // enough to get the output, but not a unit more than needed
+                assert!(amm.get_swap_output(input_amount, swap_type).unwrap() >= output_amount);
+                assert!(amm.get_swap_output(input_amount - 1, swap_type).unwrap() < output_amount);
+
+                let mut amm_clone = amm.clone();
+                let k = amm_clone.k();
+                assert_eq!(
+                    amm_clone
+                        .swap_exact_output(output_amount, swap_type)
+                        .unwrap(),
+                    input_amount
+                );
+                assert!(amm_clone.k() >= k);
+            }
+        }
+
+        assert_eq!(
+            amm.get_swap_input(1_000_000, Buy).unwrap_err(),
+            AmmError::OutputAmountExceedsReserves.into()
+        );
+        assert_eq!(
+            Amm::default().get_swap_input(1, Sell).unwrap_err(),
+            AmmError::NoReserves.into()
+        );
+    }
+
+    #[test]
+    pub fn quotes_match_execution() {
+        let amm = Amm {
+            base_amount: 1_000_000,
+            quote_amount: 2_000_000,
+            swap_fee_bps: 100,
+            protocol_fee_bps: 2_000,
+            ..Amm::default()
+        };
+
+        for swap_type in [Buy, Sell] {
+            let quote = amm.quote_swap(100_000, swap_type).unwrap();
+
+            let mut amm_clone = amm.clone();
+            let output_amount = amm_clone.swap(100_000, swap_type).unwrap();
+
+            assert_eq!(quote.output_amount, output_amount);
+            assert_eq!(quote.fee_amount, 1_000);
+            assert_eq!(quote.protocol_fee_amount, 200);
+            assert_eq!(quote.post_trade_price, amm_clone.get_price());
+        }
+
+        // quoting doesn't touch the pool
+        assert_eq!(amm.base_amount, 1_000_000);
+        assert_eq!(amm.quote_amount, 2_000_000);
+
+        // buying pushes the price up by about 10%, selling pushes it down by about 17%
+        assert_eq!(
+            amm.quote_swap(100_000, Buy).unwrap().price_impact_bps,
+            1_018
+        );
+        assert_eq!(
+            amm.quote_swap(100_000, Sell).unwrap().price_impact_bps,
+            1_726
+        );
+
+        let add = amm
+            .quote_add_liquidity(200_000, 200_000, 1_000_000)
+            .unwrap();
+        assert_eq!(
+            add.base_amount,
+            amm.get_liquidity_to_add(200_000, 1_000_000).0
+        );
+        assert_eq!(add.lp_tokens_minted, 100_000);
+        assert_eq!(
+            amm.quote_add_liquidity(200_000, 100_000, 1_000_000)
+                .unwrap_err(),
+            AmmError::AddLiquidityMaxBaseExceeded.into()
+        );
+        assert_eq!(
+            Amm::default()
+                .quote_add_liquidity(MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT, 5, 0)
+                .unwrap()
+                .lp_tokens_minted,
+            MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT - MINIMUM_LIQUIDITY
+        );
+        assert_eq!(
+            Amm::default()
+                .quote_add_liquidity(MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT - 1, 5, 0)
+                .unwrap_err(),
+            AmmError::InsufficientQuoteAmount.into()
+        );
+
+        let remove = amm.quote_remove_liquidity(250_000, 1_000_000).unwrap();
+        assert_eq!(
+            (remove.base_amount, remove.quote_amount),
+            (250_000, 500_000)
+        );
+    }
+
+    /// Does the accounting for a pool's first deposit, returning the LP tokens
+    /// that the depositor gets and the LP supply.
+    fn first_deposit(amm: &mut Amm, base_amount: u64, quote_amount: u64) -> (u64, u64) {
+        amm.base_amount += base_amount;
+        amm.quote_amount += quote_amount;
+
+        (quote_amount - MINIMUM_LIQUIDITY, quote_amount)
+    }
+
+    #[test]
+    pub fn minimum_liquidity_is_never_withdrawable() {
+        let mut amm = Amm {
+            swap_fee_bps: 100,
+            oracle: TwapOracle::new(0, PRICE_SCALE, MAX_PRICE, ObservationClampMode::Absolute),
+            ..Amm::default()
+        };
+
+        let (user_lp, lp_supply) = first_deposit(&mut amm, 100_000_000, 100_000_000);
+
+        // the lone LP pulls everything they can
+        let (base, quote) = amm.get_base_and_quote_withdrawable(user_lp, lp_supply);
+        amm.base_amount -= base;
+        amm.quote_amount -= quote;
+
+        assert_eq!(amm.base_amount, MINIMUM_LIQUIDITY);
+        assert_eq!(amm.quote_amount, MINIMUM_LIQUIDITY);
+        assert!(amm.k() > 0);
+
+        // so the pool can still be traded against and the TWAP keeps updating
+        assert!(amm.swap(10, SwapType::Buy).is_ok());
+        assert!(amm.update_twap(ONE_MINUTE_IN_SLOTS).is_some());
+    }
+
+    #[test]
+    pub fn donations_dont_move_the_pool() {
+        let mut amm = Amm::default();
+
+        let (_, lp_supply) = first_deposit(&mut amm, 100_000_000, 100_000_000);
+
+        // reserves are tracked internally, so tokens sent straight to the vaults
+        // don't change the price or what an LP position is worth
+        let victim_quote = 50_000_000;
+        let (victim_base, victim_lp) = amm.get_liquidity_to_add(victim_quote, lp_supply);
+
+        assert_eq!(victim_base, 50_000_001);
+        assert_eq!(victim_lp, 50_000_000);
+    }
+
+    #[test]
+    pub fn share_inflation_is_bounded() {
+        let mut amm = Amm::default();
+
+        // an attacker makes the smallest possible first deposit and withdraws
+        // everything but the locked liquidity
+        let (attacker_lp, lp_supply) = first_deposit(
+            &mut amm,
+            MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT,
+            MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT,
+        );
+        let (base, quote) = amm.get_base_and_quote_withdrawable(attacker_lp, lp_supply);
+        amm.base_amount -= base;
+        amm.quote_amount -= quote;
+        let lp_supply = lp_supply - attacker_lp;
+
+        assert_eq!(lp_supply, MINIMUM_LIQUIDITY);
+
+        // the locked liquidity means a victim's deposit still gets them a
+        // proportional share, losing at most one LP token's worth to rounding
+        let victim_quote = 1_000_000_000;
+        let (victim_base, victim_lp) = amm.get_liquidity_to_add(victim_quote, lp_supply);
+        assert!(victim_lp > 0);
+
+        amm.base_amount += victim_base;
+        amm.quote_amount += victim_quote;
+        let lp_supply = lp_supply + victim_lp;
+
+        let (_, victim_quote_back) = amm.get_base_and_quote_withdrawable(victim_lp, lp_supply);
+        let one_lp_token_of_quote = amm.quote_amount / lp_supply + 1;
+        assert!(victim_quote - victim_quote_back <= one_lp_token_of_quote);
+    }
+
     #[test]
     pub fn simple_twap_math_amm() {
         let mut amm = Amm {
             base_amount: 5,
             quote_amount: 50,
-            oracle: TwapOracle::new(0, 1_000_000, MAX_PRICE),
+            oracle: TwapOracle::new(0, 1_000_000, MAX_PRICE, ObservationClampMode::Absolute),
             ..Amm::default()
         };
 
@@ -347,12 +856,46 @@ mod simple_amm_tests {
         assert_eq!(amm.update_twap(ONE_MINUTE_IN_SLOTS), Some(10 * PRICE_SCALE));
     }
 
+    #[test]
+    pub fn bps_clamped_twap() {
+        // price is 10, observations can only move 2% per update
+        let mut amm = Amm {
+            base_amount: 5,
+            quote_amount: 50,
+            oracle: TwapOracle::new(0, 5 * PRICE_SCALE, 200, ObservationClampMode::Bps),
+            ..Amm::default()
+        };
+
+        let first = amm.update_twap(ONE_MINUTE_IN_SLOTS).unwrap();
+        assert_eq!(first, 5 * PRICE_SCALE * 102 / 100);
+
+        // the next clamp is relative to the new observation, not the initial one
+        let second = amm.update_twap(2 * ONE_MINUTE_IN_SLOTS).unwrap();
+        assert_eq!(second, first * 102 / 100);
+
+        // and it clamps downwards too
+        let mut amm = Amm {
+            base_amount: 50,
+            quote_amount: 5,
+            oracle: TwapOracle::new(0, 5 * PRICE_SCALE, 200, ObservationClampMode::Bps),
+            ..Amm::default()
+        };
+        assert_eq!(
+            amm.update_twap(ONE_MINUTE_IN_SLOTS).unwrap(),
+            5 * PRICE_SCALE * 98 / 100
+        );
+
+        // tiny observations can still move
+        let oracle = TwapOracle::new(0, 1, 200, ObservationClampMode::Bps);
+        assert_eq!(oracle.max_observation_change(), 1);
+    }
+
     #[test]
     pub fn overflow_twap() {
         let mut amm = Amm {
             base_amount: 1,
             quote_amount: u64::MAX,
-            oracle: TwapOracle::new(0, MAX_PRICE, MAX_PRICE),
+            oracle: TwapOracle::new(0, MAX_PRICE, MAX_PRICE, ObservationClampMode::Absolute),
             ..Amm::default()
         };
 
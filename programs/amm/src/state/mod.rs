@@ -1,13 +1,24 @@
 pub use amm::*;
+pub use twap_checkpoints::*;
 
 pub mod amm;
+pub mod twap_checkpoints;
 
 pub const BPS_SCALE: u64 = 100 * 100;
 pub const TEN_SECONDS_IN_SLOTS: u64 = 25;
 pub const ONE_MINUTE_IN_SLOTS: u64 = TEN_SECONDS_IN_SLOTS * 6;
 pub const PRICE_SCALE: u128 = 1_000_000_000_000;
 pub const MAX_PRICE: u128 = u64::MAX as u128 * PRICE_SCALE;
+pub const TWAP_CHECKPOINT_INTERVAL_SLOTS: u64 = ONE_MINUTE_IN_SLOTS * 15;
+pub const MAX_TWAP_CHECKPOINTS: usize = 384;
+/// LP tokens minted to the AMM's own LP account on the first deposit, which it
+/// can never withdraw. This keeps a pool from ever being drained back to zero.
+pub const MINIMUM_LIQUIDITY: u64 = 1_000;
+/// The smallest `quote_amount` that a pool's first deposit can be, which is
+/// $100 if quote is USDC. Here for rounding.
+pub const MINIMUM_FIRST_DEPOSIT_QUOTE_AMOUNT: u64 = 100_000_000;
 
 pub const AMM_SEED_PREFIX: &[u8] = b"amm__";
 pub const AMM_LP_MINT_SEED_PREFIX: &[u8] = b"amm_lp_mint";
 pub const AMM_AUTH_SEED_PREFIX: &[u8] = b"amm_auth";
+pub const TWAP_CHECKPOINTS_SEED_PREFIX: &[u8] = b"twap_checkpoints";
@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Slot;
+
+use crate::error::AmmError;
+use crate::state::*;
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct TwapCheckpoint {
+    pub slot: u64,
+    /// The oracle's `aggregator` as of `slot`.
+    pub aggregator: u128,
+}
+
+impl TwapCheckpoint {
+    pub const SIZE: usize = 8 + 16;
+}
+
+/// A ring buffer of `TwapOracle` checkpoints that lives alongside an AMM, so
+/// that the TWAP between any two recorded slots can be computed on-chain.
+///
+/// A checkpoint is recorded at most once per `TWAP_CHECKPOINT_INTERVAL_SLOTS`,
+/// so the buffer covers `MAX_TWAP_CHECKPOINTS * TWAP_CHECKPOINT_INTERVAL_SLOTS`
+/// slots (~4 days) before the oldest checkpoints start getting overwritten.
+#[account]
+#[derive(Default)]
+pub struct TwapCheckpoints {
+    pub amm: Pubkey,
+    /// Who paid for this account, and gets the rent back when the AMM is closed.
+    pub rent_payer: Pubkey,
+    /// Where the next checkpoint will be written once `checkpoints` is full.
+    pub next_index: u16,
+    pub checkpoints: Vec<TwapCheckpoint>,
+}
+
+impl TwapCheckpoints {
+    pub const SPACE: usize = 8 + 32 + 32 + 2 + 4 + MAX_TWAP_CHECKPOINTS * TwapCheckpoint::SIZE;
+
+    pub fn latest(&self) -> Option<&TwapCheckpoint> {
+        if self.checkpoints.len() < MAX_TWAP_CHECKPOINTS {
+            self.checkpoints.last()
+        } else {
+            let latest_index =
+                (self.next_index as usize + MAX_TWAP_CHECKPOINTS - 1) % MAX_TWAP_CHECKPOINTS;
+            self.checkpoints.get(latest_index)
+        }
+    }
+
+    /// Records the oracle's current state if at least `TWAP_CHECKPOINT_INTERVAL_SLOTS`
+    /// have passed since the latest checkpoint, overwriting the oldest checkpoint
+    /// if the buffer is full.
+    ///
+    /// Returns the checkpoint if one was recorded.
+    pub fn record(&mut self, oracle: &TwapOracle) -> Option<TwapCheckpoint> {
+        if let Some(latest) = self.latest() {
+            if oracle.last_updated_slot < latest.slot + TWAP_CHECKPOINT_INTERVAL_SLOTS {
+                return None;
+            }
+        }
+
+        let checkpoint = TwapCheckpoint {
+            slot: oracle.last_updated_slot,
+            aggregator: oracle.aggregator,
+        };
+
+        if self.checkpoints.len() < MAX_TWAP_CHECKPOINTS {
+            self.checkpoints.push(checkpoint);
+        } else {
+            self.checkpoints[self.next_index as usize] = checkpoint;
+            self.next_index = ((self.next_index as usize + 1) % MAX_TWAP_CHECKPOINTS) as u16;
+        }
+
+        assert!(self.checkpoints.len() <= MAX_TWAP_CHECKPOINTS);
+
+        Some(checkpoint)
+    }
+
+    pub fn get_checkpoint(&self, slot: Slot) -> Option<&TwapCheckpoint> {
+        self.checkpoints.iter().find(|c| c.slot == slot)
+    }
+
+    /// Returns the time-weighted average observation between two recorded
+    /// checkpoints, in the same units as `Amm::get_twap`.
+    pub fn get_twap(&self, start_slot: Slot, end_slot: Slot) -> Result<u128> {
+        require_gt!(end_slot, start_slot, AmmError::InvalidTwapWindow);
+
+        let start = self
+            .get_checkpoint(start_slot)
+            .ok_or(error!(AmmError::TwapCheckpointNotFound))?;
+        let end = self
+            .get_checkpoint(end_slot)
+            .ok_or(error!(AmmError::TwapCheckpointNotFound))?;
+
+        // the aggregator is allowed to wrap, so wrap back when subtracting
+        let aggregated = end.aggregator.wrapping_sub(start.aggregator);
+
+        Ok(aggregated / (end.slot - start.slot) as u128)
+    }
+}
+
+#[cfg(test)]
+mod twap_checkpoint_tests {
+    use crate::{error::AmmError, state::*};
+
+    fn oracle_at(slot: u64, aggregator: u128) -> TwapOracle {
+        TwapOracle {
+            last_updated_slot: slot,
+            aggregator,
+            ..TwapOracle::default()
+        }
+    }
+
+    #[test]
+    pub fn records_once_per_interval() {
+        let mut checkpoints = TwapCheckpoints::default();
+
+        assert!(checkpoints.record(&oracle_at(10, 0)).is_some());
+        assert!(checkpoints
+            .record(&oracle_at(10 + TWAP_CHECKPOINT_INTERVAL_SLOTS - 1, 5))
+            .is_none());
+        assert!(checkpoints
+            .record(&oracle_at(10 + TWAP_CHECKPOINT_INTERVAL_SLOTS, 5))
+            .is_some());
+
+        assert_eq!(checkpoints.checkpoints.len(), 2);
+        assert_eq!(
+            checkpoints.latest().unwrap().slot,
+            10 + TWAP_CHECKPOINT_INTERVAL_SLOTS
+        );
+    }
+
+    #[test]
+    pub fn wraps_around() {
+        let mut checkpoints = TwapCheckpoints::default();
+
+        let total = MAX_TWAP_CHECKPOINTS as u64 + 3;
+        for i in 0..total {
+            let slot = i * TWAP_CHECKPOINT_INTERVAL_SLOTS;
+            checkpoints.record(&oracle_at(slot, slot as u128 * 7));
+        }
+
+        assert_eq!(checkpoints.checkpoints.len(), MAX_TWAP_CHECKPOINTS);
+        assert_eq!(checkpoints.next_index, 3);
+        assert_eq!(
+            checkpoints.latest().unwrap().slot,
+            (total - 1) * TWAP_CHECKPOINT_INTERVAL_SLOTS
+        );
+
+        // the three oldest checkpoints got overwritten
+        assert!(checkpoints.get_checkpoint(0).is_none());
+        assert!(checkpoints
+            .get_checkpoint(2 * TWAP_CHECKPOINT_INTERVAL_SLOTS)
+            .is_none());
+        assert!(checkpoints
+            .get_checkpoint(3 * TWAP_CHECKPOINT_INTERVAL_SLOTS)
+            .is_some());
+    }
+
+    #[test]
+    pub fn windowed_twap() {
+        let mut checkpoints = TwapCheckpoints::default();
+
+        let interval = TWAP_CHECKPOINT_INTERVAL_SLOTS;
+
+        // observation of 100 for the first interval, then 300 for the second
+        checkpoints.record(&oracle_at(0, 0));
+        checkpoints.record(&oracle_at(interval, 100 * interval as u128));
+        checkpoints.record(&oracle_at(2 * interval, 400 * interval as u128));
+
+        assert_eq!(checkpoints.get_twap(0, interval).unwrap(), 100);
+        assert_eq!(checkpoints.get_twap(interval, 2 * interval).unwrap(), 300);
+        assert_eq!(checkpoints.get_twap(0, 2 * interval).unwrap(), 200);
+
+        assert_eq!(
+            checkpoints.get_twap(interval, interval).unwrap_err(),
+            AmmError::InvalidTwapWindow.into()
+        );
+        assert_eq!(
+            checkpoints.get_twap(0, 1).unwrap_err(),
+            AmmError::TwapCheckpointNotFound.into()
+        );
+    }
+
+    #[test]
+    pub fn windowed_twap_across_aggregator_wrap() {
+        let mut checkpoints = TwapCheckpoints::default();
+
+        let interval = TWAP_CHECKPOINT_INTERVAL_SLOTS;
+
+        checkpoints.record(&oracle_at(0, u128::MAX - 99));
+        checkpoints.record(&oracle_at(interval, 50 * interval as u128 - 100));
+
+        assert_eq!(checkpoints.get_twap(0, interval).unwrap(), 50);
+    }
+}
@@ -26,4 +26,46 @@ pub enum AutocratError {
     InsufficientLpTokenBalance,
     #[msg("The LP tokens passed in have less liquidity than the DAO's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`")]
     InsufficientLpTokenLock,
+    #[msg("`swap_fee_bps` must be less than `BPS_SCALE`")]
+    SwapFeeTooHigh,
+    #[msg("An amm has a `swap_fee_bps` that doesn't match the `dao`'s config")]
+    InvalidSwapFee,
+    #[msg("`protocol_fee_bps` must be at most `BPS_SCALE`")]
+    ProtocolFeeTooHigh,
+    #[msg("An amm has a `protocol_fee_bps` that doesn't match the `dao`'s config")]
+    InvalidProtocolFee,
+    #[msg("An amm's `protocol_fee_recipient` isn't the `dao`'s treasury")]
+    InvalidProtocolFeeRecipient,
+    #[msg("An amm has an `observation_clamp_mode` that doesn't match the `dao`'s config")]
+    InvalidObservationClampMode,
+    #[msg("A bps observation clamp needs a non-zero initial observation and a max change between 1 and `BPS_SCALE`")]
+    InvalidObservationClamp,
+    #[msg("A route's vault must be one of the proposal's vaults")]
+    InvalidRouteVault,
+    #[msg("A route's amm must be one of the proposal's amms")]
+    InvalidRouteAmm,
+    #[msg("Must route a non-zero amount")]
+    ZeroRouteAmount,
+    #[msg("A route wouldn't have given the user their minimum output, reverting")]
+    RouteSlippageExceeded,
+    #[msg("A vault's `settle_deadline` doesn't leave the DAO's `min_settle_grace_slots` to finalize the proposal")]
+    SettleDeadlineTooEarly,
+    #[msg("A proposal needs at least one instruction")]
+    NoProposalInstructions,
+    #[msg("Must execute at least one instruction")]
+    ZeroInstructionsToExecute,
+    #[msg("Instructions can only be added to a proposal before anyone uses its markets")]
+    ProposalMarketsAlreadyActive,
+    #[msg(
+        "A passed proposal can't be executed until the DAO's `execution_delay_slots` have elapsed"
+    )]
+    ExecutionDelayNotElapsed,
+    #[msg("This proposal wasn't executed within the DAO's `execution_window_slots`")]
+    ProposalExpired,
+    #[msg("This proposal can still be executed, so it can't be expired")]
+    ProposalNotExpired,
+    #[msg("A proposal can only be withdrawn by its proposer, within the DAO's `withdrawal_window_slots` of being created")]
+    ProposalWithdrawalWindowOver,
+    #[msg("A DAO's `withdrawal_window_slots` must be shorter than its `slots_per_proposal`")]
+    WithdrawalWindowTooLong,
 }
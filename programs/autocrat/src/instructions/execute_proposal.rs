@@ -8,32 +8,72 @@ pub struct ExecuteProposal<'info> {
 }
 
 impl ExecuteProposal<'_> {
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, max_instructions: u32) -> Result<()> {
         require!(
             self.proposal.state == ProposalState::Passed,
             AutocratError::ProposalNotPassed
         );
 
+        let current_slot = Clock::get()?.slot;
+
+        require!(
+            current_slot >= self.proposal.executable_at_slot,
+            AutocratError::ExecutionDelayNotElapsed
+        );
+
+        // a proposal that's partway through being executed can expire too
+        require!(
+            current_slot < self.proposal.expires_at_slot,
+            AutocratError::ProposalExpired
+        );
+
+        require_gt!(
+            max_instructions,
+            0,
+            AutocratError::ZeroInstructionsToExecute
+        );
+
         Ok(())
     }
 
-    pub fn handle(ctx: Context<Self>) -> Result<()> {
+    /// Executes up to `max_instructions` of the proposal's instructions,
+    /// picking up where the last call left off.
+    pub fn handle(ctx: Context<Self>, max_instructions: u32) -> Result<()> {
         let ExecuteProposal { proposal, dao } = ctx.accounts;
 
-        proposal.state = ProposalState::Executed;
+        let start = proposal.executed_instruction_index as usize;
+        let end = proposal
+            .instructions
+            .len()
+            .min(start + max_instructions as usize);
 
         let dao_key = dao.key();
         let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
         let signer = &[&treasury_seeds[..]];
 
-        let mut svm_instruction: Instruction = proposal.instruction.borrow().into();
-        for acc in svm_instruction.accounts.iter_mut() {
-            if acc.pubkey == dao.treasury.key() {
-                acc.is_signer = true;
+        // the accounts of this call's instructions are passed together as
+        // remaining accounts, and if any of them fails the whole call gets
+        // rolled back
+        for instruction in proposal.instructions[start..end].iter() {
+            let mut svm_instruction: Instruction = instruction.into();
+            for acc in svm_instruction.accounts.iter_mut() {
+                if acc.pubkey == dao.treasury.key() {
+                    acc.is_signer = true;
+                }
             }
+
+            solana_program::program::invoke_signed(
+                &svm_instruction,
+                ctx.remaining_accounts,
+                signer,
+            )?;
         }
 
-        solana_program::program::invoke_signed(&svm_instruction, ctx.remaining_accounts, signer)?;
+        proposal.executed_instruction_index = end as u32;
+
+        if end == proposal.instructions.len() {
+            proposal.state = ProposalState::Executed;
+        }
 
         Ok(())
     }
@@ -0,0 +1,31 @@
+use super::*;
+
+/// Permissionless, so that a stale proposal can't be executed whenever it
+/// suits someone.
+#[derive(Accounts)]
+pub struct ExpireProposal<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl ExpireProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.proposal.state == ProposalState::Passed,
+            AutocratError::ProposalNotPassed
+        );
+
+        require!(
+            Clock::get()?.slot >= self.proposal.expires_at_slot,
+            AutocratError::ProposalNotExpired
+        );
+
+        Ok(())
+    }
+
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        ctx.accounts.proposal.state = ProposalState::Expired;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+use anchor_lang::system_program::{self, Transfer};
+
+#[derive(Accounts)]
+pub struct ExtendProposalInstructions<'info> {
+    #[account(mut, has_one = proposer, has_one = pass_amm, has_one = fail_amm)]
+    pub proposal: Account<'info, Proposal>,
+    pub pass_amm: Box<Account<'info, Amm>>,
+    pub fail_amm: Box<Account<'info, Amm>>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ExtendProposalInstructions<'_> {
+    pub fn validate(&self, instructions: &[ProposalInstruction]) -> Result<()> {
+        require!(
+            self.proposal.state == ProposalState::Pending,
+            AutocratError::ProposalAlreadyFinalized
+        );
+
+        require!(
+            !instructions.is_empty(),
+            AutocratError::NoProposalInstructions
+        );
+
+        // once the markets have moved, traders have priced in the proposal's
+        // instructions and those can't change anymore
+        require_eq!(
+            self.pass_amm.seq_num,
+            self.proposal.pass_amm_seq_num,
+            AutocratError::ProposalMarketsAlreadyActive
+        );
+        require_eq!(
+            self.fail_amm.seq_num,
+            self.proposal.fail_amm_seq_num,
+            AutocratError::ProposalMarketsAlreadyActive
+        );
+
+        Ok(())
+    }
+
+    /// Appends `instructions` to the proposal's, for proposals whose
+    /// instructions don't all fit in the `initialize_proposal` transaction.
+    /// The proposer pays for any space this needs.
+    pub fn handle(ctx: Context<Self>, instructions: Vec<ProposalInstruction>) -> Result<()> {
+        let ExtendProposalInstructions {
+            proposal,
+            pass_amm: _,
+            fail_amm: _,
+            proposer,
+            system_program,
+        } = ctx.accounts;
+
+        proposal.instructions.extend(instructions);
+
+        let space = 8 + proposal.try_to_vec()?.len();
+        let proposal_info = proposal.to_account_info();
+
+        if space > proposal_info.data_len() {
+            let top_up = Rent::get()?
+                .minimum_balance(space)
+                .saturating_sub(proposal_info.lamports());
+
+            if top_up > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        system_program.to_account_info(),
+                        Transfer {
+                            from: proposer.to_account_info(),
+                            to: proposal_info.clone(),
+                        },
+                    ),
+                    top_up,
+                )?;
+            }
+
+            proposal_info.realloc(space, false)?;
+        }
+
+        Ok(())
+    }
+}
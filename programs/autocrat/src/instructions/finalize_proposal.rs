@@ -127,25 +127,48 @@ impl FinalizeProposal<'_> {
             amm.get_twap()
         };
 
-        let pass_market_twap = calculate_twap(pass_amm)?;
-        let fail_market_twap = calculate_twap(fail_amm)?;
-
-        // this can't overflow because each twap can only be MAX_PRICE (~1e31),
-        // MAX_BPS + pass_threshold_bps is at most 1e5, and a u128 can hold
-        // 1e38. still, saturate
-        let threshold = fail_market_twap
-            .saturating_mul(MAX_BPS.saturating_add(dao.pass_threshold_bps).into())
-            / MAX_BPS as u128;
-
-        let (new_proposal_state, new_vault_state) = if pass_market_twap > threshold {
-            (ProposalState::Passed, VaultStatus::Finalized)
-        } else {
+        // if anyone reverted a vault after its `settle_deadline` passed, the
+        // proposal can't pass anymore, so it fails no matter the markets
+        let vault_expired = base_vault.status == VaultStatus::Reverted
+            || quote_vault.status == VaultStatus::Reverted;
+
+        let (new_proposal_state, new_vault_state) = if vault_expired {
             (ProposalState::Failed, VaultStatus::Reverted)
+        } else {
+            let pass_market_twap = calculate_twap(pass_amm)?;
+            let fail_market_twap = calculate_twap(fail_amm)?;
+
+            // this can't overflow because each twap can only be MAX_PRICE (~1e31),
+            // MAX_BPS + pass_threshold_bps is at most 1e5, and a u128 can hold
+            // 1e38. still, saturate
+            let threshold = fail_market_twap
+                .saturating_mul(MAX_BPS.saturating_add(dao.pass_threshold_bps).into())
+                / MAX_BPS as u128;
+
+            if pass_market_twap > threshold {
+                (ProposalState::Passed, VaultStatus::Finalized)
+            } else {
+                (ProposalState::Failed, VaultStatus::Reverted)
+            }
         };
 
         proposal.state = new_proposal_state;
 
-        for vault in [base_vault.to_account_info(), quote_vault.to_account_info()] {
+        if new_proposal_state == ProposalState::Passed {
+            let current_slot = Clock::get()?.slot;
+            proposal.passed_at_slot = current_slot;
+            proposal.executable_at_slot = current_slot.saturating_add(dao.execution_delay_slots);
+            proposal.expires_at_slot = proposal
+                .executable_at_slot
+                .saturating_add(dao.execution_window_slots);
+        }
+
+        for vault in [&base_vault, &quote_vault] {
+            if vault.status != VaultStatus::Active {
+                continue;
+            }
+
+            let vault = vault.to_account_info();
             let vault_program = vault_program.to_account_info();
             let cpi_accounts = SettleConditionalVault {
                 settlement_authority: treasury.to_account_info(),
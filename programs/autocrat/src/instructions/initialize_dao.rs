@@ -4,10 +4,17 @@ pub use super::*;
 pub struct InitializeDaoParams {
     pub twap_initial_observation: u128,
     pub twap_max_observation_change_per_update: u128,
+    pub twap_observation_clamp_mode: Option<ObservationClampMode>,
     pub min_quote_futarchic_liquidity: u64,
     pub min_base_futarchic_liquidity: u64,
     pub pass_threshold_bps: Option<u16>,
     pub slots_per_proposal: Option<u64>,
+    pub swap_fee_bps: Option<u64>,
+    pub protocol_fee_bps: Option<u64>,
+    pub min_settle_grace_slots: Option<u64>,
+    pub execution_delay_slots: Option<u64>,
+    pub execution_window_slots: Option<u64>,
+    pub withdrawal_window_slots: Option<u64>,
 }
 
 #[derive(Accounts)]
@@ -32,12 +39,29 @@ impl InitializeDAO<'_> {
         let InitializeDaoParams {
             twap_initial_observation,
             twap_max_observation_change_per_update,
+            twap_observation_clamp_mode,
             min_base_futarchic_liquidity,
             min_quote_futarchic_liquidity,
             pass_threshold_bps,
             slots_per_proposal,
+            swap_fee_bps,
+            protocol_fee_bps,
+            min_settle_grace_slots,
+            execution_delay_slots,
+            execution_window_slots,
+            withdrawal_window_slots,
         } = params;
 
+        let swap_fee_bps = swap_fee_bps.unwrap_or(DEFAULT_SWAP_FEE_BPS);
+        require_gt!(BPS_SCALE, swap_fee_bps, AutocratError::SwapFeeTooHigh);
+
+        let protocol_fee_bps = protocol_fee_bps.unwrap_or(0);
+        require_gte!(
+            BPS_SCALE,
+            protocol_fee_bps,
+            AutocratError::ProtocolFeeTooHigh
+        );
+
         let dao = &mut ctx.accounts.dao;
 
         let (treasury, treasury_pda_bump) =
@@ -53,10 +77,28 @@ impl InitializeDAO<'_> {
             slots_per_proposal: slots_per_proposal.unwrap_or(THREE_DAYS_IN_SLOTS),
             twap_initial_observation,
             twap_max_observation_change_per_update,
+            twap_observation_clamp_mode: twap_observation_clamp_mode.unwrap_or_default(),
             min_base_futarchic_liquidity,
             min_quote_futarchic_liquidity,
+            swap_fee_bps,
+            protocol_fee_bps,
+            min_settle_grace_slots: min_settle_grace_slots
+                .unwrap_or(DEFAULT_MIN_SETTLE_GRACE_SLOTS),
+            execution_delay_slots: execution_delay_slots.unwrap_or_default(),
+            execution_window_slots: execution_window_slots
+                .unwrap_or(DEFAULT_EXECUTION_WINDOW_SLOTS),
+            withdrawal_window_slots: withdrawal_window_slots
+                .unwrap_or(DEFAULT_WITHDRAWAL_WINDOW_SLOTS),
         });
 
-        Ok(())
+        // otherwise a proposer could wait to see how their proposal's markets
+        // ended up before deciding whether to withdraw it
+        require_gt!(
+            dao.slots_per_proposal,
+            dao.withdrawal_window_slots,
+            AutocratError::WithdrawalWindowTooLong
+        );
+
+        dao.validate_twap_config()
     }
 }
@@ -6,7 +6,7 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeProposalParams {
     pub description_url: String,
-    pub instruction: ProposalInstruction,
+    pub instructions: Vec<ProposalInstruction>,
     pub pass_lp_tokens_to_lock: u64,
     pub fail_lp_tokens_to_lock: u64,
 }
@@ -21,12 +21,16 @@ pub struct InitializeProposal<'info> {
         has_one = proposal,
         constraint = quote_vault.underlying_token_mint == dao.usdc_mint,
         constraint = quote_vault.settlement_authority == dao.treasury @ AutocratError::InvalidSettlementAuthority,
+        // anyone could settle the vault from an oracle, not just the proposal
+        constraint = quote_vault.settlement_oracle.is_none() @ AutocratError::InvalidSettlementAuthority,
     )]
     pub quote_vault: Account<'info, ConditionalVaultAccount>,
     #[account(
         has_one = proposal,
         constraint = base_vault.underlying_token_mint == dao.token_mint,
         constraint = base_vault.settlement_authority == dao.treasury @ AutocratError::InvalidSettlementAuthority,
+        // anyone could settle the vault from an oracle, not just the proposal
+        constraint = base_vault.settlement_oracle.is_none() @ AutocratError::InvalidSettlementAuthority,
     )]
     pub base_vault: Account<'info, ConditionalVaultAccount>,
     #[account(
@@ -96,6 +100,46 @@ impl InitializeProposal<'_> {
                 self.dao.twap_max_observation_change_per_update,
                 AutocratError::InvalidMaxObservationChange
             );
+
+            require_eq!(
+                amm.swap_fee_bps,
+                self.dao.swap_fee_bps,
+                AutocratError::InvalidSwapFee
+            );
+
+            require_eq!(
+                amm.protocol_fee_bps,
+                self.dao.protocol_fee_bps,
+                AutocratError::InvalidProtocolFee
+            );
+
+            require_keys_eq!(
+                amm.protocol_fee_recipient,
+                self.dao.treasury,
+                AutocratError::InvalidProtocolFeeRecipient
+            );
+
+            require!(
+                amm.oracle.observation_clamp_mode == self.dao.twap_observation_clamp_mode.into(),
+                AutocratError::InvalidObservationClampMode
+            );
+        }
+
+        // otherwise, anyone could revert the vaults out from under the proposal
+        // before it can be finalized
+        let earliest_settle_deadline = clock
+            .slot
+            .saturating_add(self.dao.slots_per_proposal)
+            .saturating_add(self.dao.min_settle_grace_slots);
+
+        for vault in [&self.base_vault, &self.quote_vault] {
+            if let Some(settle_deadline) = vault.settle_deadline {
+                require_gt!(
+                    settle_deadline,
+                    earliest_settle_deadline,
+                    AutocratError::SettleDeadlineTooEarly
+                );
+            }
         }
 
         Ok(())
@@ -121,11 +165,16 @@ impl InitializeProposal<'_> {
 
         let InitializeProposalParams {
             description_url,
-            instruction,
+            instructions,
             pass_lp_tokens_to_lock,
             fail_lp_tokens_to_lock,
         } = params;
 
+        require!(
+            !instructions.is_empty(),
+            AutocratError::NoProposalInstructions
+        );
+
         require_gte!(
             pass_lp_user_account.amount,
             pass_lp_tokens_to_lock,
@@ -193,7 +242,11 @@ impl InitializeProposal<'_> {
             description_url,
             slot_enqueued: clock.slot,
             state: ProposalState::Pending,
-            instruction,
+            passed_at_slot: 0,
+            executable_at_slot: 0,
+            expires_at_slot: 0,
+            instructions,
+            executed_instruction_index: 0,
             pass_amm: pass_amm.key(),
             fail_amm: fail_amm.key(),
             base_vault: base_vault.key(),
@@ -201,6 +254,8 @@ impl InitializeProposal<'_> {
             dao: dao.key(),
             pass_lp_tokens_locked: pass_lp_tokens_to_lock,
             fail_lp_tokens_locked: fail_lp_tokens_to_lock,
+            pass_amm_seq_num: pass_amm.seq_num,
+            fail_amm_seq_num: fail_amm.seq_num,
         });
 
         Ok(())
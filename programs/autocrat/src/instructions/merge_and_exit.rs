@@ -0,0 +1,69 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize)]
+pub struct MergeAndExitArgs {
+    /// How many `counterpart_mint` tokens to swap back into the vault's
+    /// conditional tokens before merging, can be 0
+    pub counterpart_amount: u64,
+    /// How many pairs of conditional tokens the user already holds to merge
+    /// along with whatever the swap produces, can be 0
+    pub extra_merge_amount: u64,
+    /// The minimum underlying tokens the user must get back
+    pub min_underlying_amount: u64,
+}
+
+impl RouteThroughVault<'_> {
+    pub fn validate_merge_and_exit(&self) -> Result<()> {
+        require!(
+            self.proposal.state == ProposalState::Pending,
+            AutocratError::ProposalAlreadyFinalized
+        );
+
+        Ok(())
+    }
+
+    /// The reverse of `split_and_swap`. Swaps `counterpart_amount` back into
+    /// the vault's conditional tokens, then merges what that swap produced,
+    /// plus `extra_merge_amount`, into underlying tokens. Any other conditional
+    /// tokens the user holds are left alone.
+    pub fn handle_merge_and_exit(ctx: Context<Self>, args: MergeAndExitArgs) -> Result<()> {
+        let MergeAndExitArgs {
+            counterpart_amount,
+            extra_merge_amount,
+            min_underlying_amount,
+        } = args;
+
+        let accs = ctx.accounts;
+
+        let pre_market_balance = accs.user_market_token_account().amount;
+
+        if counterpart_amount > 0 {
+            accs.swap(accs.swap_type_into_vault(), counterpart_amount)?;
+
+            accs.user_conditional_on_finalize_token_account.reload()?;
+            accs.user_conditional_on_revert_token_account.reload()?;
+        }
+
+        let swapped_amount = accs.user_market_token_account().amount - pre_market_balance;
+
+        let merge_amount = swapped_amount + extra_merge_amount;
+
+        require_gt!(merge_amount, 0, AutocratError::ZeroRouteAmount);
+
+        let pre_underlying_balance = accs.user_underlying_token_account.amount;
+
+        accs.merge_conditional_tokens(merge_amount)?;
+
+        accs.user_underlying_token_account.reload()?;
+
+        let underlying_amount = accs.user_underlying_token_account.amount - pre_underlying_balance;
+
+        require_gte!(
+            underlying_amount,
+            min_underlying_amount,
+            AutocratError::RouteSlippageExceeded
+        );
+
+        Ok(())
+    }
+}
@@ -1,13 +1,25 @@
 use super::*;
 
 pub mod execute_proposal;
+pub mod expire_proposal;
+pub mod extend_proposal_instructions;
 pub mod finalize_proposal;
 pub mod initialize_dao;
 pub mod initialize_proposal;
+pub mod merge_and_exit;
+pub mod route_through_vault;
+pub mod split_and_swap;
 pub mod update_dao;
+pub mod withdraw_proposal;
 
 pub use execute_proposal::*;
+pub use expire_proposal::*;
+pub use extend_proposal_instructions::*;
 pub use finalize_proposal::*;
 pub use initialize_dao::*;
 pub use initialize_proposal::*;
+pub use merge_and_exit::*;
+pub use route_through_vault::*;
+pub use split_and_swap::*;
 pub use update_dao::*;
+pub use withdraw_proposal::*;
@@ -0,0 +1,229 @@
+use super::*;
+
+use amm::cpi::accounts::Swap as AmmSwap;
+use amm::instructions::SwapArgs;
+use amm::program::Amm as AmmProgram;
+use amm::state::SwapType;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, TokenInterface};
+use conditional_vault::cpi::accounts::InteractWithVault;
+
+/// The accounts needed to move between a proposal's underlying tokens and a
+/// position in one of its markets in a single instruction.
+///
+/// `vault` is whichever of the proposal's vaults holds the tokens being
+/// deposited or withdrawn, and `amm` is the market the position is in. The
+/// `counterpart_mint` is the other side of that market, owned by
+/// `counterpart_token_program`, while the vault's conditional mints are always
+/// owned by `token_program`.
+#[derive(Accounts)]
+pub struct RouteThroughVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+        constraint = vault.key() == proposal.base_vault || vault.key() == proposal.quote_vault @ AutocratError::InvalidRouteVault,
+        has_one = conditional_on_finalize_token_mint,
+        has_one = conditional_on_revert_token_mint,
+    )]
+    pub vault: Box<Account<'info, ConditionalVaultAccount>>,
+    #[account(
+        mut,
+        constraint = amm.key() == proposal.pass_amm || amm.key() == proposal.fail_amm @ AutocratError::InvalidRouteAmm,
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+    #[account(mut)]
+    pub conditional_on_finalize_token_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    pub conditional_on_revert_token_mint: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = counterpart_token_program)]
+    pub counterpart_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(mut)]
+    pub vault_underlying_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub user_underlying_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = conditional_on_finalize_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_conditional_on_finalize_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = conditional_on_revert_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_conditional_on_revert_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = counterpart_mint,
+        associated_token::authority = user,
+        associated_token::token_program = counterpart_token_program,
+    )]
+    pub user_counterpart_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    /// CHECK: checked by the amm program
+    #[account(mut)]
+    pub amm_vault_ata_base: UncheckedAccount<'info>,
+    /// CHECK: checked by the amm program
+    #[account(mut)]
+    pub amm_vault_ata_quote: UncheckedAccount<'info>,
+    /// CHECK: checked by the amm program
+    pub amm_event_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub counterpart_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub vault_program: Program<'info, ConditionalVaultProgram>,
+    pub amm_program: Program<'info, AmmProgram>,
+}
+
+impl<'info> RouteThroughVault<'info> {
+    fn is_pass_market(&self) -> bool {
+        self.amm.key() == self.proposal.pass_amm
+    }
+
+    /// The vault's conditional mint that trades in `amm`.
+    fn market_mint(&self) -> &Account<'info, Mint> {
+        if self.is_pass_market() {
+            &self.conditional_on_finalize_token_mint
+        } else {
+            &self.conditional_on_revert_token_mint
+        }
+    }
+
+    /// The user's account for `market_mint`.
+    pub fn user_market_token_account(&self) -> &Account<'info, TokenAccount> {
+        if self.is_pass_market() {
+            &self.user_conditional_on_finalize_token_account
+        } else {
+            &self.user_conditional_on_revert_token_account
+        }
+    }
+
+    /// The swap type that turns the vault's conditional tokens into
+    /// `counterpart_mint` tokens.
+    pub fn swap_type_out_of_vault(&self) -> SwapType {
+        if self.vault.key() == self.proposal.quote_vault {
+            SwapType::Buy
+        } else {
+            SwapType::Sell
+        }
+    }
+
+    /// The swap type that turns `counterpart_mint` tokens back into the
+    /// vault's conditional tokens.
+    pub fn swap_type_into_vault(&self) -> SwapType {
+        match self.swap_type_out_of_vault() {
+            SwapType::Buy => SwapType::Sell,
+            SwapType::Sell => SwapType::Buy,
+        }
+    }
+
+    pub fn mint_conditional_tokens(&self, amount: u64) -> Result<()> {
+        conditional_vault::cpi::mint_conditional_tokens(
+            CpiContext::new(
+                self.vault_program.to_account_info(),
+                self.interact_with_vault(),
+            ),
+            amount,
+        )
+    }
+
+    pub fn merge_conditional_tokens(&self, amount: u64) -> Result<()> {
+        conditional_vault::cpi::merge_conditional_tokens_for_underlying_tokens(
+            CpiContext::new(
+                self.vault_program.to_account_info(),
+                self.interact_with_vault(),
+            ),
+            amount,
+        )
+    }
+
+    /// Swaps through `amm` without a slippage check of its own, callers are
+    /// expected to check what the whole route produced.
+    pub fn swap(&self, swap_type: SwapType, input_amount: u64) -> Result<()> {
+        let market_mint = self.market_mint().to_account_info();
+        let market_token_account = self.user_market_token_account().to_account_info();
+        let counterpart_mint = self.counterpart_mint.to_account_info();
+        let counterpart_token_account = self.user_counterpart_token_account.to_account_info();
+        let market_token_program = self.token_program.to_account_info();
+        let counterpart_token_program = self.counterpart_token_program.to_account_info();
+
+        // the vault's conditional tokens are the quote side of the market iff
+        // the vault is the quote vault
+        let (
+            base_mint,
+            quote_mint,
+            user_ata_base,
+            user_ata_quote,
+            base_token_program,
+            quote_token_program,
+        ) = if self.vault.key() == self.proposal.quote_vault {
+            (
+                counterpart_mint,
+                market_mint,
+                counterpart_token_account,
+                market_token_account,
+                counterpart_token_program,
+                market_token_program,
+            )
+        } else {
+            (
+                market_mint,
+                counterpart_mint,
+                market_token_account,
+                counterpart_token_account,
+                market_token_program,
+                counterpart_token_program,
+            )
+        };
+
+        let cpi_accounts = AmmSwap {
+            user: self.user.to_account_info(),
+            amm: self.amm.to_account_info(),
+            base_mint,
+            quote_mint,
+            user_ata_base,
+            user_ata_quote,
+            vault_ata_base: self.amm_vault_ata_base.to_account_info(),
+            vault_ata_quote: self.amm_vault_ata_quote.to_account_info(),
+            associated_token_program: self.associated_token_program.to_account_info(),
+            base_token_program,
+            quote_token_program,
+            system_program: self.system_program.to_account_info(),
+            event_authority: self.amm_event_authority.to_account_info(),
+            program: self.amm_program.to_account_info(),
+        };
+
+        amm::cpi::swap(
+            CpiContext::new(self.amm_program.to_account_info(), cpi_accounts),
+            SwapArgs {
+                swap_type,
+                input_amount,
+                output_amount_min: 0,
+            },
+        )
+    }
+
+    fn interact_with_vault(&self) -> InteractWithVault<'info> {
+        InteractWithVault {
+            vault: self.vault.to_account_info(),
+            conditional_on_finalize_token_mint: self
+                .conditional_on_finalize_token_mint
+                .to_account_info(),
+            conditional_on_revert_token_mint: self
+                .conditional_on_revert_token_mint
+                .to_account_info(),
+            vault_underlying_token_account: self.vault_underlying_token_account.to_account_info(),
+            authority: self.user.to_account_info(),
+            user_conditional_on_finalize_token_account: self
+                .user_conditional_on_finalize_token_account
+                .to_account_info(),
+            user_conditional_on_revert_token_account: self
+                .user_conditional_on_revert_token_account
+                .to_account_info(),
+            user_underlying_token_account: self.user_underlying_token_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize)]
+pub struct SplitAndSwapArgs {
+    /// How many underlying tokens to deposit into the vault
+    pub underlying_amount: u64,
+    /// The minimum `counterpart_mint` tokens the user must end up with
+    pub min_output_amount: u64,
+}
+
+impl RouteThroughVault<'_> {
+    pub fn validate_split_and_swap(&self) -> Result<()> {
+        require!(
+            self.proposal.state == ProposalState::Pending,
+            AutocratError::ProposalAlreadyFinalized
+        );
+
+        Ok(())
+    }
+
+    /// Deposits underlying tokens into the vault, minting both conditional
+    /// sides, and swaps the side that trades in `amm` into `counterpart_mint`.
+    ///
+    /// Only that one side is swapped. The other side's only market is the
+    /// other AMM, where selling it would buy the other outcome's tokens rather
+    /// than more of `counterpart_mint`. So it's left with the user, and "buy
+    /// pMETA with USDC" leaves them with pMETA and the fUSDC they'd get back if
+    /// the proposal fails.
+    pub fn handle_split_and_swap(ctx: Context<Self>, args: SplitAndSwapArgs) -> Result<()> {
+        let SplitAndSwapArgs {
+            underlying_amount,
+            min_output_amount,
+        } = args;
+
+        require_gt!(underlying_amount, 0, AutocratError::ZeroRouteAmount);
+
+        let accs = ctx.accounts;
+
+        let pre_counterpart_balance = accs.user_counterpart_token_account.amount;
+
+        accs.mint_conditional_tokens(underlying_amount)?;
+
+        accs.swap(accs.swap_type_out_of_vault(), underlying_amount)?;
+
+        accs.user_counterpart_token_account.reload()?;
+
+        let output_amount = accs.user_counterpart_token_account.amount - pre_counterpart_balance;
+
+        require_gte!(
+            output_amount,
+            min_output_amount,
+            AutocratError::RouteSlippageExceeded
+        );
+
+        Ok(())
+    }
+}
@@ -6,6 +6,13 @@ pub struct UpdateDaoParams {
     pub slots_per_proposal: Option<u64>,
     pub twap_initial_observation: Option<u128>,
     pub twap_max_observation_change_per_update: Option<u128>,
+    pub swap_fee_bps: Option<u64>,
+    pub protocol_fee_bps: Option<u64>,
+    pub min_settle_grace_slots: Option<u64>,
+    pub twap_observation_clamp_mode: Option<ObservationClampMode>,
+    pub execution_delay_slots: Option<u64>,
+    pub execution_window_slots: Option<u64>,
+    pub withdrawal_window_slots: Option<u64>,
 }
 
 #[derive(Accounts)]
@@ -31,7 +38,27 @@ impl UpdateDao<'_> {
         update_dao_if_passed!(slots_per_proposal);
         update_dao_if_passed!(twap_initial_observation);
         update_dao_if_passed!(twap_max_observation_change_per_update);
+        update_dao_if_passed!(swap_fee_bps);
+        update_dao_if_passed!(protocol_fee_bps);
+        update_dao_if_passed!(twap_observation_clamp_mode);
+        update_dao_if_passed!(min_settle_grace_slots);
+        update_dao_if_passed!(execution_delay_slots);
+        update_dao_if_passed!(execution_window_slots);
+        update_dao_if_passed!(withdrawal_window_slots);
 
-        Ok(())
+        require_gt!(BPS_SCALE, dao.swap_fee_bps, AutocratError::SwapFeeTooHigh);
+        require_gte!(
+            BPS_SCALE,
+            dao.protocol_fee_bps,
+            AutocratError::ProtocolFeeTooHigh
+        );
+        // checked even if only `slots_per_proposal` changed
+        require_gt!(
+            dao.slots_per_proposal,
+            dao.withdrawal_window_slots,
+            AutocratError::WithdrawalWindowTooLong
+        );
+
+        dao.validate_twap_config()
     }
 }
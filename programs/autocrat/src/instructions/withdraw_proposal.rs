@@ -0,0 +1,147 @@
+use super::*;
+
+/// Lets a proposer take back a proposal that they made a mistake in, without
+/// having to wait for it to fail.
+#[derive(Accounts)]
+pub struct WithdrawProposal<'info> {
+    #[account(mut,
+        has_one = base_vault,
+        has_one = quote_vault,
+        has_one = pass_amm,
+        has_one = fail_amm,
+        has_one = dao,
+        has_one = proposer,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub proposer: Signer<'info>,
+    pub pass_amm: Box<Account<'info, Amm>>,
+    pub fail_amm: Box<Account<'info, Amm>>,
+    #[account(has_one = treasury)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(mut)]
+    pub base_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    #[account(mut)]
+    pub quote_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    /// CHECK: never read
+    pub treasury: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = pass_amm.lp_mint,
+        associated_token::authority = proposer,
+    )]
+    pub pass_lp_user_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = fail_amm.lp_mint,
+        associated_token::authority = proposer,
+    )]
+    pub fail_lp_user_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = pass_amm.lp_mint,
+        associated_token::authority = dao.treasury,
+    )]
+    pub pass_lp_vault_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = fail_amm.lp_mint,
+        associated_token::authority = dao.treasury,
+    )]
+    pub fail_lp_vault_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub vault_program: Program<'info, ConditionalVaultProgram>,
+}
+
+impl WithdrawProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.proposal.state == ProposalState::Pending,
+            AutocratError::ProposalAlreadyFinalized
+        );
+
+        let withdrawal_window_end = self
+            .proposal
+            .slot_enqueued
+            .saturating_add(self.dao.withdrawal_window_slots);
+
+        require!(
+            Clock::get()?.slot < withdrawal_window_end,
+            AutocratError::ProposalWithdrawalWindowOver
+        );
+
+        Ok(())
+    }
+
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let WithdrawProposal {
+            proposal,
+            proposer: _,
+            pass_amm: _,
+            fail_amm: _,
+            dao,
+            base_vault,
+            quote_vault,
+            treasury,
+            pass_lp_user_account,
+            fail_lp_user_account,
+            pass_lp_vault_account,
+            fail_lp_vault_account,
+            token_program,
+            vault_program,
+        } = ctx.accounts;
+
+        let dao_key = dao.key();
+        let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
+        let signer = &[&treasury_seeds[..]];
+
+        for (lp_tokens_to_unlock, from, to) in [
+            (
+                proposal.pass_lp_tokens_locked,
+                pass_lp_vault_account,
+                pass_lp_user_account,
+            ),
+            (
+                proposal.fail_lp_tokens_locked,
+                fail_lp_vault_account,
+                fail_lp_user_account,
+            ),
+        ] {
+            // same as in `finalize_proposal`, so that another proposal moving
+            // the treasury's LP tokens can't brick this one
+            let lp_tokens_to_unlock = std::cmp::min(lp_tokens_to_unlock, from.amount);
+
+            token::transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: from.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: treasury.to_account_info(),
+                    },
+                )
+                .with_signer(signer),
+                lp_tokens_to_unlock,
+            )?;
+        }
+
+        proposal.state = ProposalState::Withdrawn;
+
+        for vault in [base_vault.to_account_info(), quote_vault.to_account_info()] {
+            let vault_program = vault_program.to_account_info();
+            let cpi_accounts = SettleConditionalVault {
+                settlement_authority: treasury.to_account_info(),
+                vault,
+            };
+            let cpi_ctx = CpiContext::new(vault_program, cpi_accounts).with_signer(signer);
+            conditional_vault::cpi::settle_conditional_vault(cpi_ctx, VaultStatus::Reverted)?;
+        }
+
+        base_vault.reload()?;
+        quote_vault.reload()?;
+
+        assert!(base_vault.status == VaultStatus::Reverted);
+        assert!(quote_vault.status == VaultStatus::Reverted);
+
+        Ok(())
+    }
+}
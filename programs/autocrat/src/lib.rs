@@ -3,8 +3,8 @@
 //!
 //! Autocrat has two types of accounts: DAOs and proposals. Every DAO has its
 //! own token, its own treasury account, and list of configs. Proposals are
-//! created for a specific DAO, and contain an SVM instruction and a URL that
-//! should point to a description and justification of that instruction.
+//! created for a specific DAO, and contain a list of SVM instructions and a URL
+//! that should point to a description and justification of those instructions.
 //!
 //! Proposals pass through various states in their lifecycle. Here's a description
 //! of these states:
@@ -13,16 +13,26 @@
 //!   create their LP during this time.
 //! - Trading: to create a proposal, the proposer must call
 //!   `initialize_proposal`, which requires them to lock up some LP tokens in each
-//!   of the markets. Once a proposal is created, anyone can trade its markets.
+//!   of the markets. Instructions that don't fit in that transaction can be
+//!   appended with `extend_proposal_instructions` until anyone uses the markets.
+//!   Once a proposal is created, anyone can trade its markets.
 //!   Prices of these markets are aggregated into a time-weighted average price
 //!   oracle.
+//! - Withdrawn: in the first `withdrawal_window_slots` of the DAO, the proposer can
+//!   withdraw their proposal, which reverts both vaults and gives them back
+//!   their LP tokens.
 //! - Pass or fail: if the TWAP of the pass market is sufficiently higher than the
 //!   TWAP of the fail market, the proposal will pass. If it's not, the proposal will
 //!   fail. If it passes, both vaults will be finalized, allowing pTOKEN holders to
 //!   redeem. If it fails, both vaults will be reverted, allowing fTOKEN holders to
 //!   redeem.
-//! - Executed: if a proposal passes, anyone can make autocrat execute its SVM
-//!   instruction by calling `execute_proposal`.
+//! - Executed: once a passed proposal has waited out the DAO's
+//!   `execution_delay_slots`, anyone can make autocrat execute its SVM
+//!   instructions, in order, by calling `execute_proposal`. Proposals with too
+//!   many instructions to fit in one transaction can be executed over several
+//!   calls, and only become executed once the last instruction has run.
+//! - Expired: if a passed proposal isn't executed within the DAO's
+//!   `execution_window_slots`, anyone can expire it so that it never can be.
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
@@ -39,12 +49,11 @@ pub use crate::error::AutocratError;
 pub use crate::instructions::*;
 pub use crate::state::*;
 
-use amm::state::Amm;
+use amm::state::{Amm, BPS_SCALE};
 
 use solana_program::instruction::Instruction;
 #[cfg(not(feature = "no-entrypoint"))]
 use solana_security_txt::security_txt;
-use std::borrow::Borrow;
 
 #[cfg(not(feature = "no-entrypoint"))]
 security_txt! {
@@ -62,12 +71,26 @@ declare_id!("5scUH1qY87Awh5D8bQkbP2uv9BaXJXiMgrqRBw1K2vVv");
 
 pub const SLOTS_PER_10_SECS: u64 = 25;
 pub const THREE_DAYS_IN_SLOTS: u64 = 3 * 24 * 60 * 6 * SLOTS_PER_10_SECS;
+pub const ONE_DAY_IN_SLOTS: u64 = 24 * 60 * 6 * SLOTS_PER_10_SECS;
 
 pub const TEN_DAYS_IN_SECONDS: i64 = 10 * 24 * 60 * 60;
 
+// by default, proposers can withdraw a proposal in the first hour after creating it
+pub const DEFAULT_WITHDRAWAL_WINDOW_SLOTS: u64 = 60 * 6 * SLOTS_PER_10_SECS;
+
+// by default, a passed proposal has 3 days to be executed
+pub const DEFAULT_EXECUTION_WINDOW_SLOTS: u64 = THREE_DAYS_IN_SLOTS;
+
 // by default, the pass price needs to be 3% higher than the fail price
 pub const DEFAULT_PASS_THRESHOLD_BPS: u16 = 300;
 
+// by default, a proposal's vaults can't be reverted for a day after it could
+// have been finalized
+pub const DEFAULT_MIN_SETTLE_GRACE_SLOTS: u64 = ONE_DAY_IN_SLOTS;
+
+// by default, a proposal's markets charge a 1% swap fee
+pub const DEFAULT_SWAP_FEE_BPS: u64 = 100;
+
 pub const MAX_BPS: u16 = 10_000;
 
 // TWAP can only move by $5 per slot
@@ -94,9 +117,37 @@ pub mod autocrat {
         FinalizeProposal::handle(ctx)
     }
 
+    #[access_control(ctx.accounts.validate(max_instructions))]
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>, max_instructions: u32) -> Result<()> {
+        ExecuteProposal::handle(ctx, max_instructions)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn expire_proposal(ctx: Context<ExpireProposal>) -> Result<()> {
+        ExpireProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate(&instructions))]
+    pub fn extend_proposal_instructions(
+        ctx: Context<ExtendProposalInstructions>,
+        instructions: Vec<ProposalInstruction>,
+    ) -> Result<()> {
+        ExtendProposalInstructions::handle(ctx, instructions)
+    }
+
     #[access_control(ctx.accounts.validate())]
-    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
-        ExecuteProposal::handle(ctx)
+    pub fn withdraw_proposal(ctx: Context<WithdrawProposal>) -> Result<()> {
+        WithdrawProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate_split_and_swap())]
+    pub fn split_and_swap(ctx: Context<RouteThroughVault>, args: SplitAndSwapArgs) -> Result<()> {
+        RouteThroughVault::handle_split_and_swap(ctx, args)
+    }
+
+    #[access_control(ctx.accounts.validate_merge_and_exit())]
+    pub fn merge_and_exit(ctx: Context<RouteThroughVault>, args: MergeAndExitArgs) -> Result<()> {
+        RouteThroughVault::handle_merge_and_exit(ctx, args)
     }
 
     pub fn update_dao(ctx: Context<UpdateDao>, dao_params: UpdateDaoParams) -> Result<()> {
@@ -1,5 +1,23 @@
 pub use super::*;
 
+/// Mirrors the amm's `ObservationClampMode`, so that autocrat's IDL defines
+/// the type that its DAOs store.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum ObservationClampMode {
+    #[default]
+    Absolute,
+    Bps,
+}
+
+impl From<ObservationClampMode> for amm::state::ObservationClampMode {
+    fn from(mode: ObservationClampMode) -> Self {
+        match mode {
+            ObservationClampMode::Absolute => Self::Absolute,
+            ObservationClampMode::Bps => Self::Bps,
+        }
+    }
+}
+
 #[account]
 pub struct Dao {
     pub treasury_pda_bump: u8,
@@ -27,6 +45,11 @@ pub struct Dao {
     /// in 50 minutes.
     pub twap_initial_observation: u128,
     pub twap_max_observation_change_per_update: u128,
+    /// Whether `twap_max_observation_change_per_update` is an absolute change or
+    /// is in basis points of the last observation. In bps mode, 200 lets the
+    /// observation move 2% per update without having to be re-tuned as the
+    /// token's price moves.
+    pub twap_observation_clamp_mode: ObservationClampMode,
     /// As an anti-spam measure and to help liquidity, you need to lock up some liquidity
     /// in both futarchic markets in order to create a proposal.
     ///
@@ -35,4 +58,44 @@ pub struct Dao {
     /// 10 * 1_000_000_000 (10 META).
     pub min_quote_futarchic_liquidity: u64,
     pub min_base_futarchic_liquidity: u64,
+    /// The swap fee, in basis points, that both of a proposal's AMMs must charge.
+    pub swap_fee_bps: u64,
+    /// The share of the swap fee, in basis points, that both of a proposal's AMMs
+    /// must send to the treasury.
+    pub protocol_fee_bps: u64,
+    /// If a proposal's vaults have a `settle_deadline`, it has to leave at least
+    /// this long after the proposal can be finalized for someone to finalize it.
+    /// Otherwise, anyone could revert the vaults and fail the proposal.
+    pub min_settle_grace_slots: u64,
+    /// How many slots a proposal has to wait after passing before it can be
+    /// executed, so that holders have time to react to a malicious proposal
+    /// that passed anyway.
+    pub execution_delay_slots: u64,
+    /// How many slots a passed proposal can be executed in once its execution
+    /// delay is over. After that, it can only be expired. `u64::MAX` means
+    /// that passed proposals never expire.
+    pub execution_window_slots: u64,
+    /// How many slots a proposer has to withdraw their proposal after creating
+    /// it. 0 means that proposals can't be withdrawn.
+    pub withdrawal_window_slots: u64,
+}
+
+impl Dao {
+    /// The same checks that the amm's `create_amm` makes, so that a DAO can't
+    /// be configured into one whose proposals' AMMs can't be created.
+    pub fn validate_twap_config(&self) -> Result<()> {
+        if self.twap_observation_clamp_mode == ObservationClampMode::Bps {
+            require!(
+                self.twap_initial_observation > 0,
+                AutocratError::InvalidObservationClamp
+            );
+            require!(
+                self.twap_max_observation_change_per_update > 0
+                    && self.twap_max_observation_change_per_update <= BPS_SCALE as u128,
+                AutocratError::InvalidObservationClamp
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -6,6 +6,11 @@ pub enum ProposalState {
     Passed,
     Failed,
     Executed,
+    /// The proposal passed but wasn't executed within the DAO's
+    /// `execution_window_slots`.
+    Expired,
+    /// The proposer withdrew the proposal before trading got going.
+    Withdrawn,
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
@@ -29,7 +34,19 @@ pub struct Proposal {
     pub description_url: String,
     pub slot_enqueued: u64,
     pub state: ProposalState,
-    pub instruction: ProposalInstruction,
+    /// The slot that `finalize_proposal` passed the proposal in, or 0 if it
+    /// hasn't passed.
+    pub passed_at_slot: u64,
+    /// The first slot that a passed proposal can be executed in, fixed from
+    /// the DAO's `execution_delay_slots` when it passes.
+    pub executable_at_slot: u64,
+    /// The first slot that a passed proposal can no longer be executed in,
+    /// fixed from the DAO's `execution_window_slots` when it passes.
+    pub expires_at_slot: u64,
+    /// Executed in order by `execute_proposal`, over as many calls as it takes.
+    pub instructions: Vec<ProposalInstruction>,
+    /// How many of `instructions` have been executed so far.
+    pub executed_instruction_index: u32,
     pub pass_amm: Pubkey,
     pub fail_amm: Pubkey,
     pub base_vault: Pubkey,
@@ -37,6 +54,10 @@ pub struct Proposal {
     pub dao: Pubkey,
     pub pass_lp_tokens_locked: u64,
     pub fail_lp_tokens_locked: u64,
+    /// The AMMs' `seq_num`s when the proposal was created. Instructions can
+    /// only be appended while both are unchanged, i.e. before anyone uses them.
+    pub pass_amm_seq_num: u64,
+    pub fail_amm_seq_num: u64,
 }
 
 impl From<&ProposalInstruction> for Instruction {
@@ -12,4 +12,32 @@ pub enum VaultError {
     CantRedeemConditionalTokens,
     #[msg("Once a vault has been settled, its status as either finalized or reverted cannot be changed")]
     VaultAlreadySettled,
+    #[msg("A vault can have between 2 and `MAX_OUTCOMES` outcomes")]
+    InvalidNumOutcomes,
+    #[msg("An outcome mint isn't at the address this vault expects")]
+    InvalidOutcomeMint,
+    #[msg("Expected one outcome mint and one user token account per outcome")]
+    InvalidOutcomeAccounts,
+    #[msg("This vault doesn't have an outcome with that index")]
+    InvalidOutcomeIndex,
+    #[msg("Two-outcome vaults settle as finalized or reverted, multi-outcome vaults settle by naming the winning outcome")]
+    InvalidSettlement,
+    #[msg("Payouts need one numerator per outcome, and can't all be zero")]
+    InvalidPayouts,
+    #[msg("Insufficient conditional token balance to redeem this amount")]
+    InsufficientConditionalTokens,
+    #[msg("This vault doesn't have a `settle_deadline`, or it hasn't passed yet")]
+    SettleDeadlineNotReached,
+    #[msg("A vault needs to be settled before it can be closed")]
+    VaultNotSettled,
+    #[msg("A vault can't be closed until all of its conditional tokens have been redeemed")]
+    ConditionalTokensOutstanding,
+    #[msg("A parent vault must have the nested vault's underlying token as one of its conditional tokens")]
+    InvalidParentVault,
+    #[msg("Only two-outcome vaults can be nested under a parent vault")]
+    MultiOutcomeVaultCantBeNested,
+    #[msg("A settlement oracle needs between 1 and `MAX_ORACLE_RESOLUTIONS` resolutions, each naming one of the vault's outcomes, and must be the account and owner that the vault expects")]
+    InvalidSettlementOracle,
+    #[msg("The settlement oracle hasn't resolved to an outcome yet")]
+    SettlementOracleNotResolved,
 }
@@ -0,0 +1,101 @@
+use super::*;
+
+use anchor_spl::token::CloseAccount;
+
+/// Permissionless, since the rent can only ever go back to `rent_payer`.
+///
+/// Every outcome mint must be passed in order as a remaining account so that
+/// we can check that all conditional tokens have been redeemed. Any underlying
+/// tokens that are still left in the vault after that, like rounding dust from
+/// scalar payouts, go to the `rent_payer` too. Conditional mints are legacy SPL
+/// mints, which can't be closed, so they're left behind.
+#[derive(Accounts)]
+pub struct CloseConditionalVault<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+    )]
+    pub vault: Account<'info, ConditionalVault>,
+    /// CHECK: only receives lamports, and is checked against the vault
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = vault_underlying_token_account.key() == vault.underlying_token_account @ VaultError::InvalidVaultUnderlyingTokenAccount
+    )]
+    pub vault_underlying_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::authority = rent_payer,
+        token::mint = vault.underlying_token_mint
+    )]
+    pub rent_payer_underlying_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CloseConditionalVault<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.vault.status != VaultStatus::Active,
+            VaultError::VaultNotSettled
+        );
+
+        Ok(())
+    }
+
+    pub fn handle(ctx: Context<'_, '_, 'info, 'info, Self>) -> Result<()> {
+        let accs = &ctx.accounts;
+        let vault = &accs.vault;
+
+        require_eq!(
+            ctx.remaining_accounts.len(),
+            vault.outcome_mints.len(),
+            VaultError::InvalidOutcomeAccounts
+        );
+
+        for (mint_info, expected_mint) in ctx.remaining_accounts.iter().zip(&vault.outcome_mints) {
+            require_keys_eq!(
+                mint_info.key(),
+                *expected_mint,
+                VaultError::InvalidConditionalTokenMint
+            );
+
+            let mint = Account::<Mint>::try_from(mint_info)?;
+
+            require_eq!(mint.supply, 0, VaultError::ConditionalTokensOutstanding);
+        }
+
+        let seeds = generate_vault_seeds!(vault);
+        let signer = &[&seeds[..]];
+
+        let leftover_underlying = accs.vault_underlying_token_account.amount;
+
+        if leftover_underlying > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    accs.token_program.to_account_info(),
+                    Transfer {
+                        from: accs.vault_underlying_token_account.to_account_info(),
+                        to: accs.rent_payer_underlying_token_account.to_account_info(),
+                        authority: accs.vault.to_account_info(),
+                    },
+                    signer,
+                ),
+                leftover_underlying,
+            )?;
+        }
+
+        token::close_account(CpiContext::new_with_signer(
+            accs.token_program.to_account_info(),
+            CloseAccount {
+                account: accs.vault_underlying_token_account.to_account_info(),
+                destination: accs.rent_payer.to_account_info(),
+                authority: accs.vault.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        Ok(())
+    }
+}
@@ -37,3 +37,218 @@ pub struct InteractWithVault<'info> {
     pub user_underlying_token_account: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
 }
+
+/// Like `InteractWithVault`, but for multi-outcome vaults. The outcome mints
+/// and the user's token account for each of them are passed as remaining
+/// accounts: first every outcome mint in order, then every user token account
+/// in the same order.
+#[derive(Accounts)]
+pub struct InteractWithMultiOutcomeVault<'info> {
+    pub vault: Account<'info, ConditionalVault>,
+    #[account(
+        mut,
+        constraint = vault_underlying_token_account.key() == vault.underlying_token_account @ VaultError::InvalidVaultUnderlyingTokenAccount
+    )]
+    pub vault_underlying_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = vault.underlying_token_mint
+    )]
+    pub user_underlying_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub struct OutcomeAccounts<'info> {
+    pub mints: Vec<Account<'info, Mint>>,
+    pub user_token_accounts: Vec<Account<'info, TokenAccount>>,
+}
+
+impl<'info> OutcomeAccounts<'info> {
+    pub fn reload(&mut self) -> Result<()> {
+        for mint in self.mints.iter_mut() {
+            mint.reload()?;
+        }
+        for user_token_account in self.user_token_accounts.iter_mut() {
+            user_token_account.reload()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'info> InteractWithMultiOutcomeVault<'info> {
+    /// Deserializes the outcome mints and user token accounts from
+    /// `remaining_accounts`, checking them against the vault.
+    pub fn load_outcome_accounts(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<OutcomeAccounts<'info>> {
+        let num_outcomes = self.vault.outcome_mints.len();
+
+        require!(
+            self.vault.is_multi_outcome(),
+            VaultError::InvalidOutcomeAccounts
+        );
+        require_eq!(
+            remaining_accounts.len(),
+            2 * num_outcomes,
+            VaultError::InvalidOutcomeAccounts
+        );
+
+        let (mint_infos, user_token_account_infos) = remaining_accounts.split_at(num_outcomes);
+
+        let mut outcome_accounts = OutcomeAccounts {
+            mints: Vec::with_capacity(num_outcomes),
+            user_token_accounts: Vec::with_capacity(num_outcomes),
+        };
+
+        for ((mint_info, user_token_account_info), expected_mint) in mint_infos
+            .iter()
+            .zip(user_token_account_infos)
+            .zip(&self.vault.outcome_mints)
+        {
+            require_keys_eq!(
+                mint_info.key(),
+                *expected_mint,
+                VaultError::InvalidConditionalTokenMint
+            );
+
+            let mint = Account::<Mint>::try_from(mint_info)?;
+            let user_token_account = Account::<TokenAccount>::try_from(user_token_account_info)?;
+
+            require_keys_eq!(
+                user_token_account.mint,
+                mint.key(),
+                VaultError::InvalidConditionalTokenMint
+            );
+            require_keys_eq!(
+                user_token_account.owner,
+                self.authority.key(),
+                VaultError::InvalidOutcomeAccounts
+            );
+
+            outcome_accounts.mints.push(mint);
+            outcome_accounts
+                .user_token_accounts
+                .push(user_token_account);
+        }
+
+        Ok(outcome_accounts)
+    }
+
+    /// Deserializes a single outcome's mint and the user's token account for
+    /// it from `remaining_accounts`, in that order. Unlike
+    /// `load_outcome_accounts`, this works for two-outcome vaults too, where
+    /// outcome 0 is conditional-on-finalize and outcome 1 is
+    /// conditional-on-revert.
+    pub fn load_outcome_account(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        outcome: usize,
+    ) -> Result<(Account<'info, Mint>, Account<'info, TokenAccount>)> {
+        let [mint_info, user_token_account_info] = remaining_accounts else {
+            return err!(VaultError::InvalidOutcomeAccounts);
+        };
+
+        require_keys_eq!(
+            mint_info.key(),
+            self.vault.outcome_mints[outcome],
+            VaultError::InvalidConditionalTokenMint
+        );
+
+        let mint = Account::<Mint>::try_from(mint_info)?;
+        let user_token_account = Account::<TokenAccount>::try_from(user_token_account_info)?;
+
+        require_keys_eq!(
+            user_token_account.mint,
+            mint.key(),
+            VaultError::InvalidConditionalTokenMint
+        );
+        require_keys_eq!(
+            user_token_account.owner,
+            self.authority.key(),
+            VaultError::InvalidOutcomeAccounts
+        );
+
+        Ok((mint, user_token_account))
+    }
+}
+
+/// Like `InteractWithVault` for minting, but the conditional tokens are minted
+/// into a `recipient`'s accounts instead of the `authority`'s.
+#[derive(Accounts)]
+pub struct MintToRecipient<'info> {
+    #[account(
+        has_one = conditional_on_finalize_token_mint @ VaultError::InvalidConditionalTokenMint,
+        has_one = conditional_on_revert_token_mint @ VaultError::InvalidConditionalTokenMint,
+    )]
+    pub vault: Account<'info, ConditionalVault>,
+    #[account(mut)]
+    pub conditional_on_finalize_token_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub conditional_on_revert_token_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = vault_underlying_token_account.key() == vault.underlying_token_account @ VaultError::InvalidVaultUnderlyingTokenAccount
+    )]
+    pub vault_underlying_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = vault.underlying_token_mint
+    )]
+    pub user_underlying_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = conditional_on_finalize_token_mint
+    )]
+    pub recipient_conditional_on_finalize_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = conditional_on_revert_token_mint
+    )]
+    pub recipient_conditional_on_revert_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Like `InteractWithVault` for merging and redeeming, but the underlying
+/// tokens are sent to a `recipient`'s account instead of the `authority`'s.
+#[derive(Accounts)]
+pub struct WithdrawToRecipient<'info> {
+    #[account(
+        has_one = conditional_on_finalize_token_mint @ VaultError::InvalidConditionalTokenMint,
+        has_one = conditional_on_revert_token_mint @ VaultError::InvalidConditionalTokenMint,
+    )]
+    pub vault: Account<'info, ConditionalVault>,
+    #[account(mut)]
+    pub conditional_on_finalize_token_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub conditional_on_revert_token_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = vault_underlying_token_account.key() == vault.underlying_token_account @ VaultError::InvalidVaultUnderlyingTokenAccount
+    )]
+    pub vault_underlying_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = conditional_on_finalize_token_mint
+    )]
+    pub user_conditional_on_finalize_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = conditional_on_revert_token_mint
+    )]
+    pub user_conditional_on_revert_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = vault.underlying_token_mint
+    )]
+    pub recipient_underlying_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
@@ -4,6 +4,8 @@ use super::*;
 pub struct InitializeConditionalVaultArgs {
     pub settlement_authority: Pubkey,
     pub proposal: Pubkey,
+    pub settle_deadline: Option<u64>,
+    pub settlement_oracle: Option<SettlementOracle>,
 }
 
 #[derive(Accounts)]
@@ -12,7 +14,7 @@ pub struct InitializeConditionalVault<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + std::mem::size_of::<ConditionalVault>(),
+        space = ConditionalVault::space(2),
         seeds = [
             b"conditional_vault", 
             args.settlement_authority.key().as_ref(),
@@ -23,6 +25,12 @@ pub struct InitializeConditionalVault<'info> {
     )]
     pub vault: Box<Account<'info, ConditionalVault>>,
     pub underlying_token_mint: Account<'info, Mint>,
+    /// Only needed if `underlying_token_mint` is another vault's conditional
+    /// token.
+    #[account(
+        constraint = parent_vault.outcome_index(underlying_token_mint.key()).is_some() @ VaultError::InvalidParentVault
+    )]
+    pub parent_vault: Option<Box<Account<'info, ConditionalVault>>>,
     #[account(
         init,
         payer = payer,
@@ -56,27 +64,48 @@ pub struct InitializeConditionalVault<'info> {
 }
 
 impl InitializeConditionalVault<'_> {
+    pub fn validate(&self, args: &InitializeConditionalVaultArgs) -> Result<()> {
+        if let Some(settlement_oracle) = &args.settlement_oracle {
+            settlement_oracle.validate(2)?;
+        }
+
+        Ok(())
+    }
+
     pub fn handle(ctx: Context<Self>, args: InitializeConditionalVaultArgs) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
 
         let InitializeConditionalVaultArgs {
             proposal,
             settlement_authority,
+            settle_deadline,
+            settlement_oracle,
         } = args;
 
+        let conditional_on_finalize_token_mint =
+            ctx.accounts.conditional_on_finalize_token_mint.key();
+        let conditional_on_revert_token_mint = ctx.accounts.conditional_on_revert_token_mint.key();
+
         vault.set_inner(ConditionalVault {
             status: VaultStatus::Active,
             settlement_authority,
             underlying_token_mint: ctx.accounts.underlying_token_mint.key(),
             proposal,
             underlying_token_account: ctx.accounts.vault_underlying_token_account.key(),
-            conditional_on_finalize_token_mint: ctx
-                .accounts
-                .conditional_on_finalize_token_mint
-                .key(),
-            conditional_on_revert_token_mint: ctx.accounts.conditional_on_revert_token_mint.key(),
+            conditional_on_finalize_token_mint,
+            conditional_on_revert_token_mint,
             pda_bump: ctx.bumps.vault,
             decimals: ctx.accounts.underlying_token_mint.decimals,
+            outcome_mints: vec![
+                conditional_on_finalize_token_mint,
+                conditional_on_revert_token_mint,
+            ],
+            payout_numerators: vec![],
+            payout_denominator: 0,
+            settle_deadline,
+            rent_payer: ctx.accounts.payer.key(),
+            parent_vault: ctx.accounts.parent_vault.as_ref().map(|vault| vault.key()),
+            settlement_oracle,
         });
 
         Ok(())
@@ -0,0 +1,211 @@
+use super::*;
+
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token::InitializeMint2;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeMultiOutcomeVaultArgs {
+    pub settlement_authority: Pubkey,
+    pub proposal: Pubkey,
+    pub num_outcomes: u8,
+    pub settle_deadline: Option<u64>,
+    pub settlement_oracle: Option<SettlementOracle>,
+}
+
+/// The outcome mints are created at the `b"conditional_outcome_mint"` PDAs for
+/// each outcome index, and must be passed in order as remaining accounts.
+///
+/// Multi-outcome vaults can't be nested under a parent vault, so there's no
+/// `parent_vault` here. Use a two-outcome vault to condition on another
+/// vault's outcome.
+#[derive(Accounts)]
+#[instruction(args: InitializeMultiOutcomeVaultArgs)]
+pub struct InitializeMultiOutcomeVault<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = ConditionalVault::space(args.num_outcomes as usize),
+        seeds = [
+            b"conditional_vault",
+            args.settlement_authority.key().as_ref(),
+            underlying_token_mint.key().as_ref(),
+            args.proposal.as_ref()
+        ],
+        bump
+    )]
+    pub vault: Box<Account<'info, ConditionalVault>>,
+    pub underlying_token_mint: Account<'info, Mint>,
+    #[account(
+        associated_token::authority = vault,
+        associated_token::mint = underlying_token_mint
+    )]
+    pub vault_underlying_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeMultiOutcomeVault<'info> {
+    pub fn validate(&self, args: &InitializeMultiOutcomeVaultArgs) -> Result<()> {
+        require!(
+            args.num_outcomes >= 2 && args.num_outcomes as usize <= MAX_OUTCOMES,
+            VaultError::InvalidNumOutcomes
+        );
+
+        if let Some(settlement_oracle) = &args.settlement_oracle {
+            settlement_oracle.validate(args.num_outcomes as usize)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn handle(
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        args: InitializeMultiOutcomeVaultArgs,
+    ) -> Result<()> {
+        let InitializeMultiOutcomeVaultArgs {
+            settlement_authority,
+            proposal,
+            num_outcomes,
+            settle_deadline,
+            settlement_oracle,
+        } = args;
+
+        require_eq!(
+            ctx.remaining_accounts.len(),
+            num_outcomes as usize,
+            VaultError::InvalidOutcomeAccounts
+        );
+
+        let vault_key = ctx.accounts.vault.key();
+        let decimals = ctx.accounts.underlying_token_mint.decimals;
+
+        let mut outcome_mints = Vec::with_capacity(num_outcomes as usize);
+
+        for (outcome, mint) in ctx.remaining_accounts.iter().enumerate() {
+            let outcome = [outcome as u8];
+            let (expected_mint, bump) = Pubkey::find_program_address(
+                &[b"conditional_outcome_mint", vault_key.as_ref(), &outcome],
+                ctx.program_id,
+            );
+
+            require_keys_eq!(mint.key(), expected_mint, VaultError::InvalidOutcomeMint);
+
+            create_pda_account(
+                &ctx.accounts.payer,
+                mint,
+                &ctx.accounts.system_program,
+                Mint::LEN,
+                ctx.accounts.token_program.key,
+                &[&[
+                    b"conditional_outcome_mint",
+                    vault_key.as_ref(),
+                    &outcome,
+                    &[bump],
+                ]],
+            )?;
+
+            token::initialize_mint2(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    InitializeMint2 { mint: mint.clone() },
+                ),
+                decimals,
+                &vault_key,
+                Some(&vault_key),
+            )?;
+
+            outcome_mints.push(expected_mint);
+        }
+
+        ctx.accounts.vault.set_inner(ConditionalVault {
+            status: VaultStatus::Active,
+            settlement_authority,
+            underlying_token_mint: ctx.accounts.underlying_token_mint.key(),
+            proposal,
+            underlying_token_account: ctx.accounts.vault_underlying_token_account.key(),
+            conditional_on_finalize_token_mint: Pubkey::default(),
+            conditional_on_revert_token_mint: Pubkey::default(),
+            pda_bump: ctx.bumps.vault,
+            decimals,
+            outcome_mints,
+            payout_numerators: vec![],
+            payout_denominator: 0,
+            settle_deadline,
+            rent_payer: ctx.accounts.payer.key(),
+            parent_vault: None,
+            settlement_oracle,
+        });
+
+        Ok(())
+    }
+}
+
+/// Creates a PDA the same way Anchor's `init` does. Anyone can send lamports to
+/// a PDA before it's created, and `create_account` fails on an account that
+/// already has lamports, so in that case the account is topped up to rent
+/// exemption, allocated, and assigned instead.
+fn create_pda_account<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            owner,
+        );
+    }
+
+    let top_up = rent.saturating_sub(current_lamports);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            signer_seeds,
+        ),
+        owner,
+    )
+}
@@ -0,0 +1,98 @@
+use super::*;
+
+impl MintToRecipient<'_> {
+    pub fn handle_mint_conditional_tokens_to_recipient(
+        ctx: Context<Self>,
+        amount: u64,
+    ) -> Result<()> {
+        let accs = &ctx.accounts;
+
+        let pre_vault_underlying_balance = accs.vault_underlying_token_account.amount;
+        let pre_user_underlying_balance = accs.user_underlying_token_account.amount;
+        let pre_recipient_conditional_on_finalize_balance =
+            accs.recipient_conditional_on_finalize_token_account.amount;
+        let pre_recipient_conditional_on_revert_balance =
+            accs.recipient_conditional_on_revert_token_account.amount;
+        let pre_finalize_mint_supply = accs.conditional_on_finalize_token_mint.supply;
+        let pre_revert_mint_supply = accs.conditional_on_revert_token_mint.supply;
+
+        require!(
+            pre_user_underlying_balance >= amount,
+            VaultError::InsufficientUnderlyingTokens
+        );
+
+        let vault = &accs.vault;
+
+        let seeds = generate_vault_seeds!(vault);
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new(
+                accs.token_program.to_account_info(),
+                Transfer {
+                    from: accs.user_underlying_token_account.to_account_info(),
+                    to: accs.vault_underlying_token_account.to_account_info(),
+                    authority: accs.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        for (conditional_mint, recipient_conditional_token_account) in [
+            (
+                &accs.conditional_on_finalize_token_mint,
+                &accs.recipient_conditional_on_finalize_token_account,
+            ),
+            (
+                &accs.conditional_on_revert_token_mint,
+                &accs.recipient_conditional_on_revert_token_account,
+            ),
+        ] {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    accs.token_program.to_account_info(),
+                    MintTo {
+                        mint: conditional_mint.to_account_info(),
+                        to: recipient_conditional_token_account.to_account_info(),
+                        authority: accs.vault.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        ctx.accounts
+            .recipient_conditional_on_finalize_token_account
+            .reload()?;
+        ctx.accounts
+            .recipient_conditional_on_revert_token_account
+            .reload()?;
+        ctx.accounts.vault_underlying_token_account.reload()?;
+        ctx.accounts.user_underlying_token_account.reload()?;
+        ctx.accounts.conditional_on_finalize_token_mint.reload()?;
+        ctx.accounts.conditional_on_revert_token_mint.reload()?;
+
+        let accs = &ctx.accounts;
+
+        // Only the paranoid survive ;)
+        assert!(
+            accs.vault_underlying_token_account.amount == pre_vault_underlying_balance + amount
+        );
+        assert!(accs.user_underlying_token_account.amount == pre_user_underlying_balance - amount);
+        assert!(
+            accs.recipient_conditional_on_finalize_token_account.amount
+                == pre_recipient_conditional_on_finalize_balance + amount
+        );
+        assert!(
+            accs.recipient_conditional_on_revert_token_account.amount
+                == pre_recipient_conditional_on_revert_balance + amount
+        );
+        assert!(
+            accs.conditional_on_finalize_token_mint.supply == pre_finalize_mint_supply + amount
+        );
+        assert!(accs.conditional_on_revert_token_mint.supply == pre_revert_mint_supply + amount);
+
+        Ok(())
+    }
+}
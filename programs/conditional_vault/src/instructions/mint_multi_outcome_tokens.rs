@@ -0,0 +1,87 @@
+use super::*;
+
+impl<'info> InteractWithMultiOutcomeVault<'info> {
+    pub fn handle_mint_multi_outcome_tokens(
+        ctx: Context<'_, '_, 'info, 'info, Self>,
+        amount: u64,
+    ) -> Result<()> {
+        let accs = &ctx.accounts;
+
+        let mut outcome_accounts = accs.load_outcome_accounts(ctx.remaining_accounts)?;
+
+        let pre_vault_underlying_balance = accs.vault_underlying_token_account.amount;
+        let pre_user_outcome_balances: Vec<u64> = outcome_accounts
+            .user_token_accounts
+            .iter()
+            .map(|account| account.amount)
+            .collect();
+        let pre_outcome_mint_supplies: Vec<u64> = outcome_accounts
+            .mints
+            .iter()
+            .map(|mint| mint.supply)
+            .collect();
+
+        require!(
+            accs.user_underlying_token_account.amount >= amount,
+            VaultError::InsufficientUnderlyingTokens
+        );
+
+        let vault = &accs.vault;
+
+        let seeds = generate_vault_seeds!(vault);
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new(
+                accs.token_program.to_account_info(),
+                Transfer {
+                    from: accs.user_underlying_token_account.to_account_info(),
+                    to: accs.vault_underlying_token_account.to_account_info(),
+                    authority: accs.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        for (outcome_mint, user_outcome_token_account) in outcome_accounts
+            .mints
+            .iter()
+            .zip(&outcome_accounts.user_token_accounts)
+        {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    accs.token_program.to_account_info(),
+                    MintTo {
+                        mint: outcome_mint.to_account_info(),
+                        to: user_outcome_token_account.to_account_info(),
+                        authority: accs.vault.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        outcome_accounts.reload()?;
+        ctx.accounts.vault_underlying_token_account.reload()?;
+
+        let post_vault_underlying_balance = ctx.accounts.vault_underlying_token_account.amount;
+
+        // Only the paranoid survive ;)
+        assert!(post_vault_underlying_balance == pre_vault_underlying_balance + amount);
+        for (user_outcome_token_account, pre_balance) in outcome_accounts
+            .user_token_accounts
+            .iter()
+            .zip(pre_user_outcome_balances)
+        {
+            assert!(user_outcome_token_account.amount == pre_balance + amount);
+        }
+        for (outcome_mint, pre_supply) in
+            outcome_accounts.mints.iter().zip(pre_outcome_mint_supplies)
+        {
+            assert!(outcome_mint.supply == pre_supply + amount);
+        }
+
+        Ok(())
+    }
+}
@@ -1,15 +1,31 @@
 use super::*;
 
 pub mod add_metadata_to_conditional_tokens;
+pub mod close_conditional_vault;
 pub mod common;
 pub mod initialize_conditional_vault;
+pub mod initialize_multi_outcome_vault;
 pub mod merge_conditional_tokens;
+pub mod merge_multi_outcome_tokens;
 pub mod mint_conditional_tokens;
+pub mod mint_conditional_tokens_to_recipient;
+pub mod mint_multi_outcome_tokens;
 pub mod redeem_conditional_tokens_for_underlying_tokens;
+pub mod redeem_conditional_tokens_through_parent;
+pub mod redeem_multi_outcome_tokens;
+pub mod redeem_partial_conditional_tokens;
+pub mod revert_expired_vault;
 pub mod settle_conditional_vault;
+pub mod settle_conditional_vault_from_oracle;
+pub mod withdraw_to_recipient;
 
 pub use add_metadata_to_conditional_tokens::*;
+pub use close_conditional_vault::*;
 pub use common::*;
 pub use initialize_conditional_vault::*;
+pub use initialize_multi_outcome_vault::*;
+pub use redeem_conditional_tokens_through_parent::*;
 
+pub use revert_expired_vault::*;
 pub use settle_conditional_vault::*;
+pub use settle_conditional_vault_from_oracle::*;
@@ -52,11 +52,9 @@ impl InteractWithVault<'_> {
             )?;
         }
 
-        let redeemable = if vault_status == VaultStatus::Finalized {
-            pre_conditional_on_finalize_balance
-        } else {
-            pre_conditional_on_revert_balance
-        };
+        // pro rata for `Settled` vaults, rounding down on each side
+        let redeemable = vault.redeemable(0, pre_conditional_on_finalize_balance)
+            + vault.redeemable(1, pre_conditional_on_revert_balance);
 
         token::transfer(
             CpiContext::new_with_signer(
@@ -105,12 +103,18 @@ impl InteractWithVault<'_> {
                 post_vault_underlying_balance
                     == pre_vault_underlying_balance - pre_conditional_on_finalize_balance
             );
-        } else {
-            assert!(vault_status == VaultStatus::Reverted);
+        } else if vault_status == VaultStatus::Reverted {
             assert!(
                 post_vault_underlying_balance
                     == pre_vault_underlying_balance - pre_conditional_on_revert_balance
             );
+        } else {
+            assert!(vault_status == VaultStatus::Settled);
+            assert!(
+                redeemable
+                    <= pre_conditional_on_finalize_balance.max(pre_conditional_on_revert_balance)
+            );
+            assert!(post_vault_underlying_balance == pre_vault_underlying_balance - redeemable);
         }
 
         Ok(())
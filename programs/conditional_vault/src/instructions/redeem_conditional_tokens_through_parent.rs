@@ -0,0 +1,238 @@
+use super::*;
+
+/// Redeems a nested vault's conditional tokens and then redeems the parent
+/// vault's conditional tokens that come out of it, in one go. Deeper chains can
+/// be unwound by calling this once per level.
+///
+/// The nested vault has to be a two-outcome vault, but the parent can be a
+/// multi-outcome one.
+#[derive(Accounts)]
+pub struct RedeemThroughParent<'info> {
+    #[account(
+        constraint = !vault.is_multi_outcome() @ VaultError::MultiOutcomeVaultCantBeNested,
+        constraint = vault.conditional_on_finalize_token_mint == conditional_on_finalize_token_mint.key() @ VaultError::InvalidConditionalTokenMint,
+        constraint = vault.conditional_on_revert_token_mint == conditional_on_revert_token_mint.key() @ VaultError::InvalidConditionalTokenMint,
+        constraint = vault.parent_vault == Some(parent_vault.key()) @ VaultError::InvalidParentVault,
+    )]
+    pub vault: Box<Account<'info, ConditionalVault>>,
+    #[account(mut)]
+    pub conditional_on_finalize_token_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    pub conditional_on_revert_token_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = vault_underlying_token_account.key() == vault.underlying_token_account @ VaultError::InvalidVaultUnderlyingTokenAccount
+    )]
+    pub vault_underlying_token_account: Box<Account<'info, TokenAccount>>,
+    pub parent_vault: Box<Account<'info, ConditionalVault>>,
+    /// The parent vault's conditional mint that is this vault's underlying.
+    #[account(
+        mut,
+        address = vault.underlying_token_mint,
+    )]
+    pub parent_conditional_token_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = parent_vault_underlying_token_account.key() == parent_vault.underlying_token_account @ VaultError::InvalidVaultUnderlyingTokenAccount
+    )]
+    pub parent_vault_underlying_token_account: Box<Account<'info, TokenAccount>>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = conditional_on_finalize_token_mint
+    )]
+    pub user_conditional_on_finalize_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = conditional_on_revert_token_mint
+    )]
+    pub user_conditional_on_revert_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = parent_conditional_token_mint
+    )]
+    pub user_parent_conditional_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = parent_vault.underlying_token_mint
+    )]
+    pub user_underlying_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl RedeemThroughParent<'_> {
+    /// Both vaults need to be settled, in either order. Until the parent is
+    /// settled, the nested vault can still be redeemed on its own for the
+    /// parent's conditional tokens.
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.vault.status != VaultStatus::Active,
+            VaultError::CantRedeemConditionalTokens
+        );
+        require!(
+            self.parent_vault.status != VaultStatus::Active,
+            VaultError::CantRedeemConditionalTokens
+        );
+
+        Ok(())
+    }
+
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let accs = &ctx.accounts;
+        let vault = &accs.vault;
+        let parent_vault = &accs.parent_vault;
+
+        // checked by `vault`'s initialization
+        let parent_outcome = parent_vault
+            .outcome_index(vault.underlying_token_mint)
+            .unwrap();
+
+        // storing some numbers for later invariant checks
+        let pre_vault_underlying_balance = accs.vault_underlying_token_account.amount;
+        let pre_parent_vault_underlying_balance = accs.parent_vault_underlying_token_account.amount;
+        let pre_user_parent_conditional_balance = accs.user_parent_conditional_token_account.amount;
+        let pre_user_underlying_balance = accs.user_underlying_token_account.amount;
+        let pre_finalize_mint_supply = accs.conditional_on_finalize_token_mint.supply;
+        let pre_revert_mint_supply = accs.conditional_on_revert_token_mint.supply;
+        let pre_parent_conditional_mint_supply = accs.parent_conditional_token_mint.supply;
+
+        let pre_conditional_on_finalize_balance =
+            accs.user_conditional_on_finalize_token_account.amount;
+        let pre_conditional_on_revert_balance =
+            accs.user_conditional_on_revert_token_account.amount;
+
+        // first, redeem at this vault for the parent's conditional tokens
+        for (conditional_mint, user_conditional_token_account) in [
+            (
+                &accs.conditional_on_finalize_token_mint,
+                &accs.user_conditional_on_finalize_token_account,
+            ),
+            (
+                &accs.conditional_on_revert_token_mint,
+                &accs.user_conditional_on_revert_token_account,
+            ),
+        ] {
+            token::burn(
+                CpiContext::new(
+                    accs.token_program.to_account_info(),
+                    Burn {
+                        mint: conditional_mint.to_account_info(),
+                        from: user_conditional_token_account.to_account_info(),
+                        authority: accs.authority.to_account_info(),
+                    },
+                ),
+                user_conditional_token_account.amount,
+            )?;
+        }
+
+        let parent_conditional_redeemable = vault
+            .redeemable(0, pre_conditional_on_finalize_balance)
+            + vault.redeemable(1, pre_conditional_on_revert_balance);
+
+        let seeds = generate_vault_seeds!(vault);
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                accs.token_program.to_account_info(),
+                Transfer {
+                    from: accs.vault_underlying_token_account.to_account_info(),
+                    to: accs.user_parent_conditional_token_account.to_account_info(),
+                    authority: accs.vault.to_account_info(),
+                },
+                signer,
+            ),
+            parent_conditional_redeemable,
+        )?;
+
+        // then, redeem those at the parent vault
+        token::burn(
+            CpiContext::new(
+                accs.token_program.to_account_info(),
+                Burn {
+                    mint: accs.parent_conditional_token_mint.to_account_info(),
+                    from: accs.user_parent_conditional_token_account.to_account_info(),
+                    authority: accs.authority.to_account_info(),
+                },
+            ),
+            parent_conditional_redeemable,
+        )?;
+
+        let underlying_redeemable =
+            parent_vault.redeemable(parent_outcome, parent_conditional_redeemable);
+
+        let parent_seeds = generate_vault_seeds!(parent_vault);
+        let parent_signer = &[&parent_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                accs.token_program.to_account_info(),
+                Transfer {
+                    from: accs.parent_vault_underlying_token_account.to_account_info(),
+                    to: accs.user_underlying_token_account.to_account_info(),
+                    authority: accs.parent_vault.to_account_info(),
+                },
+                parent_signer,
+            ),
+            underlying_redeemable,
+        )?;
+
+        ctx.accounts
+            .user_conditional_on_finalize_token_account
+            .reload()?;
+        ctx.accounts
+            .user_conditional_on_revert_token_account
+            .reload()?;
+        ctx.accounts
+            .user_parent_conditional_token_account
+            .reload()?;
+        ctx.accounts.user_underlying_token_account.reload()?;
+        ctx.accounts.vault_underlying_token_account.reload()?;
+        ctx.accounts
+            .parent_vault_underlying_token_account
+            .reload()?;
+        ctx.accounts.conditional_on_finalize_token_mint.reload()?;
+        ctx.accounts.conditional_on_revert_token_mint.reload()?;
+        ctx.accounts.parent_conditional_token_mint.reload()?;
+
+        let accs = &ctx.accounts;
+
+        // Only the paranoid survive ;)
+        assert!(accs.user_conditional_on_finalize_token_account.amount == 0);
+        assert!(accs.user_conditional_on_revert_token_account.amount == 0);
+        assert!(
+            accs.conditional_on_finalize_token_mint.supply
+                == pre_finalize_mint_supply - pre_conditional_on_finalize_balance
+        );
+        assert!(
+            accs.conditional_on_revert_token_mint.supply
+                == pre_revert_mint_supply - pre_conditional_on_revert_balance
+        );
+        assert!(
+            accs.vault_underlying_token_account.amount
+                == pre_vault_underlying_balance - parent_conditional_redeemable
+        );
+        assert!(
+            accs.user_parent_conditional_token_account.amount
+                == pre_user_parent_conditional_balance
+        );
+        assert!(
+            accs.parent_conditional_token_mint.supply
+                == pre_parent_conditional_mint_supply - parent_conditional_redeemable
+        );
+        assert!(
+            accs.parent_vault_underlying_token_account.amount
+                == pre_parent_vault_underlying_balance - underlying_redeemable
+        );
+        assert!(
+            accs.user_underlying_token_account.amount
+                == pre_user_underlying_balance + underlying_redeemable
+        );
+
+        Ok(())
+    }
+}
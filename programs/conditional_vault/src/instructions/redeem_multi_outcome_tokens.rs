@@ -0,0 +1,95 @@
+use super::*;
+
+impl<'info> InteractWithMultiOutcomeVault<'info> {
+    pub fn validate_redeem_multi_outcome_tokens(&self) -> Result<()> {
+        require!(
+            self.vault.status == VaultStatus::Settled,
+            VaultError::CantRedeemConditionalTokens
+        );
+
+        Ok(())
+    }
+
+    pub fn handle_redeem_multi_outcome_tokens(
+        ctx: Context<'_, '_, 'info, 'info, Self>,
+    ) -> Result<()> {
+        let accs = &ctx.accounts;
+        let vault = &accs.vault;
+
+        let mut outcome_accounts = accs.load_outcome_accounts(ctx.remaining_accounts)?;
+
+        // storing some numbers for later invariant checks
+        let pre_vault_underlying_balance = accs.vault_underlying_token_account.amount;
+        let pre_user_outcome_balances: Vec<u64> = outcome_accounts
+            .user_token_accounts
+            .iter()
+            .map(|account| account.amount)
+            .collect();
+        let pre_outcome_mint_supplies: Vec<u64> = outcome_accounts
+            .mints
+            .iter()
+            .map(|mint| mint.supply)
+            .collect();
+
+        let seeds = generate_vault_seeds!(vault);
+        let signer = &[&seeds[..]];
+
+        // burn every outcome, even the ones that pay nothing
+        for (outcome_mint, user_outcome_token_account) in outcome_accounts
+            .mints
+            .iter()
+            .zip(&outcome_accounts.user_token_accounts)
+        {
+            token::burn(
+                CpiContext::new(
+                    accs.token_program.to_account_info(),
+                    Burn {
+                        mint: outcome_mint.to_account_info(),
+                        from: user_outcome_token_account.to_account_info(),
+                        authority: accs.authority.to_account_info(),
+                    },
+                ),
+                user_outcome_token_account.amount,
+            )?;
+        }
+
+        let redeemable = pre_user_outcome_balances
+            .iter()
+            .enumerate()
+            .map(|(outcome, balance)| vault.payout(outcome, *balance))
+            .sum();
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                accs.token_program.to_account_info(),
+                Transfer {
+                    from: accs.vault_underlying_token_account.to_account_info(),
+                    to: accs.user_underlying_token_account.to_account_info(),
+                    authority: accs.vault.to_account_info(),
+                },
+                signer,
+            ),
+            redeemable,
+        )?;
+
+        outcome_accounts.reload()?;
+        ctx.accounts.vault_underlying_token_account.reload()?;
+
+        let post_vault_underlying_balance = ctx.accounts.vault_underlying_token_account.amount;
+
+        for user_outcome_token_account in outcome_accounts.user_token_accounts.iter() {
+            assert!(user_outcome_token_account.amount == 0);
+        }
+        for ((outcome_mint, pre_supply), pre_balance) in outcome_accounts
+            .mints
+            .iter()
+            .zip(pre_outcome_mint_supplies)
+            .zip(&pre_user_outcome_balances)
+        {
+            assert!(outcome_mint.supply == pre_supply - pre_balance);
+        }
+        assert!(post_vault_underlying_balance == pre_vault_underlying_balance - redeemable);
+
+        Ok(())
+    }
+}
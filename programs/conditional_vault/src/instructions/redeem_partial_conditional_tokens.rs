@@ -0,0 +1,91 @@
+use super::*;
+
+impl<'info> InteractWithMultiOutcomeVault<'info> {
+    pub fn validate_redeem_partial_conditional_tokens(&self, outcome: u8) -> Result<()> {
+        require!(
+            self.vault.status != VaultStatus::Active,
+            VaultError::CantRedeemConditionalTokens
+        );
+
+        require_gt!(
+            self.vault.outcome_mints.len(),
+            outcome as usize,
+            VaultError::InvalidOutcomeIndex
+        );
+
+        Ok(())
+    }
+
+    /// Burns `amount` of the selected `outcome`'s tokens and pays out what
+    /// they're worth, leaving the user's other balances alone. The outcome's
+    /// mint and the user's token account for it are passed as remaining
+    /// accounts, so this works for two-outcome and multi-outcome vaults alike.
+    pub fn handle_redeem_partial_conditional_tokens(
+        ctx: Context<'_, '_, 'info, 'info, Self>,
+        outcome: u8,
+        amount: u64,
+    ) -> Result<()> {
+        let accs = &ctx.accounts;
+        let vault = &accs.vault;
+
+        let (mut conditional_mint, mut user_conditional_token_account) =
+            accs.load_outcome_account(ctx.remaining_accounts, outcome as usize)?;
+
+        require!(
+            user_conditional_token_account.amount >= amount,
+            VaultError::InsufficientConditionalTokens
+        );
+
+        // storing some numbers for later invariant checks
+        let pre_vault_underlying_balance = accs.vault_underlying_token_account.amount;
+        let pre_user_underlying_balance = accs.user_underlying_token_account.amount;
+        let pre_mint_supply = conditional_mint.supply;
+        let pre_conditional_balance = user_conditional_token_account.amount;
+
+        let seeds = generate_vault_seeds!(vault);
+        let signer = &[&seeds[..]];
+
+        token::burn(
+            CpiContext::new(
+                accs.token_program.to_account_info(),
+                Burn {
+                    mint: conditional_mint.to_account_info(),
+                    from: user_conditional_token_account.to_account_info(),
+                    authority: accs.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let redeemable = vault.redeemable(outcome as usize, amount);
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                accs.token_program.to_account_info(),
+                Transfer {
+                    from: accs.vault_underlying_token_account.to_account_info(),
+                    to: accs.user_underlying_token_account.to_account_info(),
+                    authority: accs.vault.to_account_info(),
+                },
+                signer,
+            ),
+            redeemable,
+        )?;
+
+        user_conditional_token_account.reload()?;
+        conditional_mint.reload()?;
+        ctx.accounts.vault_underlying_token_account.reload()?;
+        ctx.accounts.user_underlying_token_account.reload()?;
+
+        let post_vault_underlying_balance = ctx.accounts.vault_underlying_token_account.amount;
+        let post_user_underlying_balance = ctx.accounts.user_underlying_token_account.amount;
+
+        assert!(user_conditional_token_account.amount == pre_conditional_balance - amount);
+        assert!(conditional_mint.supply == pre_mint_supply - amount);
+        assert!(redeemable <= amount);
+        assert!(post_vault_underlying_balance == pre_vault_underlying_balance - redeemable);
+        assert!(post_user_underlying_balance == pre_user_underlying_balance + redeemable);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use super::*;
+
+/// Permissionless, so that depositors can get their underlying tokens back if
+/// the `settlement_authority` never settles the vault.
+#[derive(Accounts)]
+pub struct RevertExpiredVault<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ConditionalVault>,
+}
+
+impl RevertExpiredVault<'_> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.vault.status == VaultStatus::Active,
+            VaultError::VaultAlreadySettled
+        );
+
+        require!(
+            self.vault.is_past_settle_deadline(Clock::get()?.slot),
+            VaultError::SettleDeadlineNotReached
+        );
+
+        Ok(())
+    }
+
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        if vault.is_multi_outcome() {
+            // every outcome pays out equally, so a full set of outcome tokens
+            // is worth exactly the underlying token that minted it
+            vault.payout_numerators = vec![1; vault.outcome_mints.len()];
+            vault.payout_denominator = vault.outcome_mints.len() as u64;
+            vault.status = VaultStatus::Settled;
+        } else {
+            vault.status = VaultStatus::Reverted;
+        }
+
+        Ok(())
+    }
+}
@@ -11,12 +11,18 @@ pub struct SettleConditionalVault<'info> {
 }
 
 impl SettleConditionalVault<'_> {
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, new_status: VaultStatus) -> Result<()> {
         require!(
             self.vault.status == VaultStatus::Active,
             VaultError::VaultAlreadySettled
         );
 
+        // multi-outcome vaults have to be settled by naming an outcome
+        require!(
+            !self.vault.is_multi_outcome() && new_status != VaultStatus::Settled,
+            VaultError::InvalidSettlement
+        );
+
         Ok(())
     }
 
@@ -27,4 +33,64 @@ impl SettleConditionalVault<'_> {
 
         Ok(())
     }
+
+    pub fn validate_with_payouts(&self, payout_numerators: &[u64]) -> Result<()> {
+        require!(
+            self.vault.status == VaultStatus::Active,
+            VaultError::VaultAlreadySettled
+        );
+
+        require_eq!(
+            payout_numerators.len(),
+            self.vault.outcome_mints.len(),
+            VaultError::InvalidPayouts
+        );
+
+        Ok(())
+    }
+
+    /// Settles the vault so that each outcome's tokens pay out
+    /// `payout_numerators[outcome] / sum(payout_numerators)` underlying tokens,
+    /// for decisions that aren't all-or-nothing.
+    pub fn handle_with_payouts(ctx: Context<Self>, payout_numerators: Vec<u64>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        let payout_denominator = payout_numerators
+            .iter()
+            .try_fold(0u64, |sum, numerator| sum.checked_add(*numerator))
+            .ok_or(error!(VaultError::InvalidPayouts))?;
+
+        require_gt!(payout_denominator, 0, VaultError::InvalidPayouts);
+
+        vault.payout_numerators = payout_numerators;
+        vault.payout_denominator = payout_denominator;
+        vault.status = VaultStatus::Settled;
+
+        Ok(())
+    }
+
+    pub fn validate_multi_outcome(&self, winning_outcome: u8) -> Result<()> {
+        require!(
+            self.vault.status == VaultStatus::Active,
+            VaultError::VaultAlreadySettled
+        );
+
+        require!(self.vault.is_multi_outcome(), VaultError::InvalidSettlement);
+
+        require_gt!(
+            self.vault.outcome_mints.len(),
+            winning_outcome as usize,
+            VaultError::InvalidOutcomeIndex
+        );
+
+        Ok(())
+    }
+
+    pub fn handle_multi_outcome(ctx: Context<Self>, winning_outcome: u8) -> Result<()> {
+        ctx.accounts
+            .vault
+            .settle_with_winning_outcome(winning_outcome as usize);
+
+        Ok(())
+    }
 }
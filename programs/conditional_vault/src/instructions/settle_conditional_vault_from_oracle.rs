@@ -0,0 +1,51 @@
+use super::*;
+
+/// Permissionless, since the vault's `settlement_oracle` decides the outcome.
+#[derive(Accounts)]
+pub struct SettleConditionalVaultFromOracle<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ConditionalVault>,
+    /// CHECK: checked against the vault's `settlement_oracle` in `validate`
+    pub settlement_oracle: UncheckedAccount<'info>,
+}
+
+impl SettleConditionalVaultFromOracle<'_> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.vault.status == VaultStatus::Active,
+            VaultError::VaultAlreadySettled
+        );
+
+        let Some(settlement_oracle) = &self.vault.settlement_oracle else {
+            return err!(VaultError::InvalidSettlementOracle);
+        };
+
+        require_keys_eq!(
+            self.settlement_oracle.key(),
+            settlement_oracle.account,
+            VaultError::InvalidSettlementOracle
+        );
+        require_keys_eq!(
+            *self.settlement_oracle.owner,
+            settlement_oracle.owner_program,
+            VaultError::InvalidSettlementOracle
+        );
+
+        Ok(())
+    }
+
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        // checked in `validate`
+        let settlement_oracle = vault.settlement_oracle.as_ref().unwrap();
+
+        let winning_outcome = settlement_oracle
+            .winning_outcome(&ctx.accounts.settlement_oracle.try_borrow_data()?)
+            .ok_or(error!(VaultError::SettlementOracleNotResolved))?;
+
+        vault.settle_with_winning_outcome(winning_outcome);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use super::*;
+
+impl WithdrawToRecipient<'_> {
+    pub fn validate_merge_conditional_tokens_to_recipient(&self) -> Result<()> {
+        require!(
+            self.vault.status == VaultStatus::Active,
+            VaultError::VaultAlreadySettled
+        );
+
+        Ok(())
+    }
+
+    pub fn validate_redeem_conditional_tokens_to_recipient(&self) -> Result<()> {
+        require!(
+            self.vault.status != VaultStatus::Active,
+            VaultError::CantRedeemConditionalTokens
+        );
+
+        Ok(())
+    }
+
+    /// Burns `amount` of both of the authority's conditional tokens and sends
+    /// `amount` underlying tokens to the recipient.
+    pub fn handle_merge_conditional_tokens_to_recipient(
+        ctx: Context<Self>,
+        amount: u64,
+    ) -> Result<()> {
+        Self::burn_and_withdraw(ctx, amount, amount, amount)
+    }
+
+    /// Burns all of the authority's conditional tokens and sends whatever
+    /// they're worth to the recipient.
+    pub fn handle_redeem_conditional_tokens_to_recipient(ctx: Context<Self>) -> Result<()> {
+        let accs = &ctx.accounts;
+
+        let finalize_amount = accs.user_conditional_on_finalize_token_account.amount;
+        let revert_amount = accs.user_conditional_on_revert_token_account.amount;
+
+        let redeemable =
+            accs.vault.redeemable(0, finalize_amount) + accs.vault.redeemable(1, revert_amount);
+
+        Self::burn_and_withdraw(ctx, finalize_amount, revert_amount, redeemable)
+    }
+
+    fn burn_and_withdraw(
+        ctx: Context<Self>,
+        finalize_amount: u64,
+        revert_amount: u64,
+        underlying_amount: u64,
+    ) -> Result<()> {
+        let accs = &ctx.accounts;
+        let vault = &accs.vault;
+
+        // Store Pre-operation Balances
+        let pre_user_conditional_on_finalize_balance =
+            accs.user_conditional_on_finalize_token_account.amount;
+        let pre_user_conditional_on_revert_balance =
+            accs.user_conditional_on_revert_token_account.amount;
+        let pre_vault_underlying_balance = accs.vault_underlying_token_account.amount;
+        let pre_recipient_underlying_balance = accs.recipient_underlying_token_account.amount;
+        let pre_finalize_mint_supply = accs.conditional_on_finalize_token_mint.supply;
+        let pre_revert_mint_supply = accs.conditional_on_revert_token_mint.supply;
+
+        let seeds = generate_vault_seeds!(vault);
+        let signer = &[&seeds[..]];
+
+        for (conditional_mint, user_conditional_token_account, amount) in [
+            (
+                &accs.conditional_on_finalize_token_mint,
+                &accs.user_conditional_on_finalize_token_account,
+                finalize_amount,
+            ),
+            (
+                &accs.conditional_on_revert_token_mint,
+                &accs.user_conditional_on_revert_token_account,
+                revert_amount,
+            ),
+        ] {
+            token::burn(
+                CpiContext::new(
+                    accs.token_program.to_account_info(),
+                    Burn {
+                        mint: conditional_mint.to_account_info(),
+                        from: user_conditional_token_account.to_account_info(),
+                        authority: accs.authority.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                accs.token_program.to_account_info(),
+                Transfer {
+                    from: accs.vault_underlying_token_account.to_account_info(),
+                    to: accs.recipient_underlying_token_account.to_account_info(),
+                    authority: accs.vault.to_account_info(),
+                },
+                signer,
+            ),
+            underlying_amount,
+        )?;
+
+        // Reload Accounts to Reflect Changes
+        ctx.accounts
+            .user_conditional_on_finalize_token_account
+            .reload()?;
+        ctx.accounts
+            .user_conditional_on_revert_token_account
+            .reload()?;
+        ctx.accounts.vault_underlying_token_account.reload()?;
+        ctx.accounts.recipient_underlying_token_account.reload()?;
+        ctx.accounts.conditional_on_finalize_token_mint.reload()?;
+        ctx.accounts.conditional_on_revert_token_mint.reload()?;
+
+        let accs = &ctx.accounts;
+
+        // Only the paranoid survive ;)
+        assert_eq!(
+            accs.user_conditional_on_finalize_token_account.amount,
+            pre_user_conditional_on_finalize_balance - finalize_amount
+        );
+        assert_eq!(
+            accs.user_conditional_on_revert_token_account.amount,
+            pre_user_conditional_on_revert_balance - revert_amount
+        );
+        assert_eq!(
+            accs.conditional_on_finalize_token_mint.supply,
+            pre_finalize_mint_supply - finalize_amount
+        );
+        assert_eq!(
+            accs.conditional_on_revert_token_mint.supply,
+            pre_revert_mint_supply - revert_amount
+        );
+        assert_eq!(
+            accs.vault_underlying_token_account.amount,
+            pre_vault_underlying_balance - underlying_amount
+        );
+        assert_eq!(
+            accs.recipient_underlying_token_account.amount,
+            pre_recipient_underlying_balance + underlying_amount
+        );
+
+        Ok(())
+    }
+}
@@ -38,6 +38,7 @@ declare_id!("F3RzPAtBQvUAAdDHD8AsPKFhDB1H38SULNKTW39dYrw8");
 pub mod conditional_vault {
     use super::*;
 
+    #[access_control(ctx.accounts.validate(&args))]
     pub fn initialize_conditional_vault(
         ctx: Context<InitializeConditionalVault>,
         args: InitializeConditionalVaultArgs,
@@ -53,7 +54,15 @@ pub mod conditional_vault {
         AddMetadataToConditionalTokens::handle(ctx, args)
     }
 
-    #[access_control(ctx.accounts.validate())]
+    #[access_control(ctx.accounts.validate(&args))]
+    pub fn initialize_multi_outcome_vault<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeMultiOutcomeVault<'info>>,
+        args: InitializeMultiOutcomeVaultArgs,
+    ) -> Result<()> {
+        InitializeMultiOutcomeVault::handle(ctx, args)
+    }
+
+    #[access_control(ctx.accounts.validate(new_status))]
     pub fn settle_conditional_vault(
         ctx: Context<SettleConditionalVault>,
         new_status: VaultStatus,
@@ -79,4 +88,99 @@ pub mod conditional_vault {
     ) -> Result<()> {
         InteractWithVault::handle_redeem_conditional_tokens(ctx)
     }
+
+    pub fn mint_conditional_tokens_to_recipient(
+        ctx: Context<MintToRecipient>,
+        amount: u64,
+    ) -> Result<()> {
+        MintToRecipient::handle_mint_conditional_tokens_to_recipient(ctx, amount)
+    }
+
+    #[access_control(ctx.accounts.validate_merge_conditional_tokens_to_recipient())]
+    pub fn merge_conditional_tokens_to_recipient(
+        ctx: Context<WithdrawToRecipient>,
+        amount: u64,
+    ) -> Result<()> {
+        WithdrawToRecipient::handle_merge_conditional_tokens_to_recipient(ctx, amount)
+    }
+
+    #[access_control(ctx.accounts.validate_redeem_conditional_tokens_to_recipient())]
+    pub fn redeem_conditional_tokens_to_recipient(ctx: Context<WithdrawToRecipient>) -> Result<()> {
+        WithdrawToRecipient::handle_redeem_conditional_tokens_to_recipient(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn redeem_conditional_tokens_through_parent(
+        ctx: Context<RedeemThroughParent>,
+    ) -> Result<()> {
+        RedeemThroughParent::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate_redeem_partial_conditional_tokens(outcome))]
+    pub fn redeem_partial_conditional_tokens_for_underlying_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InteractWithMultiOutcomeVault<'info>>,
+        outcome: u8,
+        amount: u64,
+    ) -> Result<()> {
+        InteractWithMultiOutcomeVault::handle_redeem_partial_conditional_tokens(
+            ctx, outcome, amount,
+        )
+    }
+
+    #[access_control(ctx.accounts.validate_with_payouts(&payout_numerators))]
+    pub fn settle_conditional_vault_with_payouts(
+        ctx: Context<SettleConditionalVault>,
+        payout_numerators: Vec<u64>,
+    ) -> Result<()> {
+        SettleConditionalVault::handle_with_payouts(ctx, payout_numerators)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn settle_conditional_vault_from_oracle(
+        ctx: Context<SettleConditionalVaultFromOracle>,
+    ) -> Result<()> {
+        SettleConditionalVaultFromOracle::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn revert_expired_vault(ctx: Context<RevertExpiredVault>) -> Result<()> {
+        RevertExpiredVault::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn close_conditional_vault<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseConditionalVault<'info>>,
+    ) -> Result<()> {
+        CloseConditionalVault::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate_multi_outcome(winning_outcome))]
+    pub fn settle_multi_outcome_vault(
+        ctx: Context<SettleConditionalVault>,
+        winning_outcome: u8,
+    ) -> Result<()> {
+        SettleConditionalVault::handle_multi_outcome(ctx, winning_outcome)
+    }
+
+    pub fn mint_multi_outcome_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InteractWithMultiOutcomeVault<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        InteractWithMultiOutcomeVault::handle_mint_multi_outcome_tokens(ctx, amount)
+    }
+
+    #[access_control(ctx.accounts.validate_merge_multi_outcome_tokens())]
+    pub fn merge_multi_outcome_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InteractWithMultiOutcomeVault<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        InteractWithMultiOutcomeVault::handle_merge_multi_outcome_tokens(ctx, amount)
+    }
+
+    #[access_control(ctx.accounts.validate_redeem_multi_outcome_tokens())]
+    pub fn redeem_multi_outcome_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InteractWithMultiOutcomeVault<'info>>,
+    ) -> Result<()> {
+        InteractWithMultiOutcomeVault::handle_redeem_multi_outcome_tokens(ctx)
+    }
 }
@@ -1,10 +1,84 @@
 use super::*;
 
+/// The most outcomes that a single vault can have.
+pub const MAX_OUTCOMES: usize = 8;
+
+/// The most values that a settlement oracle can map to outcomes.
+pub const MAX_ORACLE_RESOLUTIONS: usize = 8;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum VaultStatus {
     Active,
     Finalized,
     Reverted,
+    /// The vault has been settled by naming a winning outcome or by giving
+    /// fractional payouts, and `payout_numerators` says what each outcome's
+    /// tokens are worth.
+    Settled,
+}
+
+/// An account that anyone can settle a vault from, by reading the byte at
+/// `outcome_offset` in its data.
+///
+/// To settle from an autocrat `Proposal`, `owner_program` is autocrat and
+/// `outcome_offset` is where `state` ends up after the proposal's description
+/// URL. To settle the same way as the proposal's own vaults, the resolutions
+/// should be:
+/// - `Passed` (1), `Executed` (3) and `Expired` (4) to outcome 0 (finalize),
+///   since an expired proposal still passed.
+/// - `Failed` (2) and `Withdrawn` (5) to outcome 1 (revert).
+///
+/// `Pending` (0) must be left out, so that the vault can't be settled before
+/// the proposal is.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct SettlementOracle {
+    pub account: Pubkey,
+    /// The program that must own `account`, since otherwise anyone could
+    /// write whatever outcome they wanted into it.
+    pub owner_program: Pubkey,
+    pub outcome_offset: u32,
+    /// Any value that isn't listed here means the oracle hasn't resolved yet.
+    pub resolutions: Vec<OracleResolution>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct OracleResolution {
+    pub value: u8,
+    pub winning_outcome: u8,
+}
+
+impl SettlementOracle {
+    pub const SPACE: usize = 32 + // account
+        32 + // owner program
+        4 + // outcome offset
+        4 + 2 * MAX_ORACLE_RESOLUTIONS; // resolutions
+
+    pub fn validate(&self, num_outcomes: usize) -> Result<()> {
+        require!(
+            !self.resolutions.is_empty() && self.resolutions.len() <= MAX_ORACLE_RESOLUTIONS,
+            VaultError::InvalidSettlementOracle
+        );
+
+        for resolution in &self.resolutions {
+            require_gt!(
+                num_outcomes,
+                resolution.winning_outcome as usize,
+                VaultError::InvalidSettlementOracle
+            );
+        }
+
+        Ok(())
+    }
+
+    /// The winning outcome that `oracle_data` says, if it has resolved.
+    pub fn winning_outcome(&self, oracle_data: &[u8]) -> Option<usize> {
+        let value = *oracle_data.get(self.outcome_offset as usize)?;
+
+        self.resolutions
+            .iter()
+            .find(|resolution| resolution.value == value)
+            .map(|resolution| resolution.winning_outcome as usize)
+    }
 }
 
 #[account]
@@ -21,10 +95,106 @@ pub struct ConditionalVault {
     pub proposal: Pubkey,
     /// The vault's storage account for deposited funds.
     pub underlying_token_account: Pubkey,
+    /// For multi-outcome vaults, these are both the default pubkey so that the
+    /// two-outcome instructions can't be used with them.
     pub conditional_on_finalize_token_mint: Pubkey,
     pub conditional_on_revert_token_mint: Pubkey,
     pub pda_bump: u8,
     pub decimals: u8,
+    /// One mint per outcome. For two-outcome vaults, these are
+    /// `conditional_on_finalize_token_mint` and `conditional_on_revert_token_mint`.
+    pub outcome_mints: Vec<Pubkey>,
+    /// How much underlying each outcome's tokens are worth once the vault is
+    /// `Settled`, as a fraction of `payout_denominator`. Empty until then.
+    pub payout_numerators: Vec<u64>,
+    pub payout_denominator: u64,
+    /// The slot after which anyone can revert the vault if it still hasn't
+    /// been settled, so that funds can't be locked forever.
+    pub settle_deadline: Option<u64>,
+    /// Who paid for the vault's accounts, and gets the rent back on
+    /// `close_conditional_vault`.
+    pub rent_payer: Pubkey,
+    /// If the underlying token is one of another vault's conditional tokens,
+    /// that vault. This lets conditions be combined, such as "proposal A
+    /// passes and proposal B fails". Only two-outcome vaults can be nested,
+    /// but their parent can have any number of outcomes.
+    pub parent_vault: Option<Pubkey>,
+    /// If set, anyone can settle the vault once this oracle resolves, without
+    /// needing a signature from `settlement_authority`.
+    pub settlement_oracle: Option<SettlementOracle>,
+}
+
+impl ConditionalVault {
+    pub fn space(num_outcomes: usize) -> usize {
+        8 + // discriminator
+        1 + // status
+        32 * 6 + // settlement authority, underlying mint, proposal, underlying token account, finalize & revert mints
+        1 + // pda bump
+        1 + // decimals
+        4 + 32 * num_outcomes + // outcome mints
+        4 + 8 * num_outcomes + // payout numerators
+        8 + // payout denominator
+        1 + 8 + // settle deadline
+        32 + // rent payer
+        1 + 32 + // parent vault
+        1 + SettlementOracle::SPACE // settlement oracle
+    }
+
+    pub fn is_past_settle_deadline(&self, current_slot: u64) -> bool {
+        self.settle_deadline
+            .is_some_and(|settle_deadline| current_slot > settle_deadline)
+    }
+
+    /// The outcome that `mint` is the conditional token of, if it's one of
+    /// this vault's.
+    pub fn outcome_index(&self, mint: Pubkey) -> Option<usize> {
+        self.outcome_mints
+            .iter()
+            .position(|outcome_mint| *outcome_mint == mint)
+    }
+
+    pub fn is_multi_outcome(&self) -> bool {
+        self.conditional_on_finalize_token_mint == Pubkey::default()
+    }
+
+    /// Settles the vault so that only `winning_outcome`'s tokens pay out.
+    pub fn settle_with_winning_outcome(&mut self, winning_outcome: usize) {
+        if self.is_multi_outcome() {
+            // the winning outcome's tokens are worth 1 underlying token each and
+            // every other outcome's are worth nothing
+            self.payout_numerators = (0..self.outcome_mints.len())
+                .map(|outcome| (outcome == winning_outcome) as u64)
+                .collect();
+            self.payout_denominator = 1;
+            self.status = VaultStatus::Settled;
+        } else if winning_outcome == 0 {
+            self.status = VaultStatus::Finalized;
+        } else {
+            self.status = VaultStatus::Reverted;
+        }
+    }
+
+    /// How much underlying `amount` tokens of `outcome` can be redeemed for in
+    /// a settled vault. For two-outcome vaults, outcome 0 is the
+    /// conditional-on-finalize token and outcome 1 is the conditional-on-revert
+    /// token.
+    pub fn redeemable(&self, outcome: usize, amount: u64) -> u64 {
+        match self.status {
+            VaultStatus::Finalized if outcome == 0 => amount,
+            VaultStatus::Reverted if outcome == 1 => amount,
+            VaultStatus::Settled => self.payout(outcome, amount),
+            _ => 0,
+        }
+    }
+
+    /// How much underlying `amount` tokens of `outcome` pay out once the vault
+    /// is `Settled`. Rounds down, so that the vault can never pay out more than
+    /// it holds.
+    pub fn payout(&self, outcome: usize, amount: u64) -> u64 {
+        // numerators are at most the denominator, so this fits back into a u64
+        ((amount as u128 * self.payout_numerators[outcome] as u128)
+            / self.payout_denominator as u128) as u64
+    }
 }
 
 #[macro_export]
@@ -39,3 +209,121 @@ macro_rules! generate_vault_seeds {
         ]
     }};
 }
+
+#[cfg(test)]
+mod conditional_vault_tests {
+    use super::*;
+
+    fn settled_vault(payout_numerators: Vec<u64>) -> ConditionalVault {
+        ConditionalVault {
+            status: VaultStatus::Settled,
+            settlement_authority: Pubkey::default(),
+            underlying_token_mint: Pubkey::default(),
+            proposal: Pubkey::default(),
+            underlying_token_account: Pubkey::default(),
+            conditional_on_finalize_token_mint: Pubkey::default(),
+            conditional_on_revert_token_mint: Pubkey::default(),
+            pda_bump: 0,
+            decimals: 0,
+            outcome_mints: vec![Pubkey::default(); payout_numerators.len()],
+            payout_denominator: payout_numerators.iter().sum(),
+            payout_numerators,
+            settle_deadline: None,
+            rent_payer: Pubkey::default(),
+            parent_vault: None,
+            settlement_oracle: None,
+        }
+    }
+
+    #[test]
+    fn winner_takes_all_payouts() {
+        let vault = settled_vault(vec![0, 1, 0]);
+
+        assert_eq!(vault.payout(0, 1_000), 0);
+        assert_eq!(vault.payout(1, 1_000), 1_000);
+        assert_eq!(vault.payout(2, 1_000), 0);
+    }
+
+    #[test]
+    fn scalar_payouts_never_overpay() {
+        let vault = settled_vault(vec![1, 2]);
+
+        assert_eq!(vault.payout(0, 3_000), 1_000);
+        assert_eq!(vault.payout(1, 3_000), 2_000);
+
+        // every deposit mints one of each outcome, so holders redeeming in odd
+        // lots can't take out more than was deposited
+        let lots = [1, 2, 5, 7, 11, 13, 998, 1_963];
+        let deposited: u64 = lots.iter().sum();
+
+        let paid_out: u64 = lots
+            .iter()
+            .map(|lot| vault.payout(0, *lot) + vault.payout(1, *lot))
+            .sum();
+
+        assert!(paid_out <= deposited);
+    }
+
+    #[test]
+    fn partial_redemptions() {
+        let mut vault = settled_vault(vec![1, 2]);
+        vault.status = VaultStatus::Finalized;
+
+        assert_eq!(vault.redeemable(0, 500), 500);
+        assert_eq!(vault.redeemable(1, 500), 0);
+
+        vault.status = VaultStatus::Active;
+
+        assert_eq!(vault.redeemable(0, 500), 0);
+
+        // splitting a redemption up can only ever round down more
+        vault.status = VaultStatus::Settled;
+
+        let in_one_go = vault.redeemable(0, 3_000);
+        let in_pieces: u64 = (0..3_000).map(|_| vault.redeemable(0, 1)).sum();
+
+        assert_eq!(in_one_go, 1_000);
+        assert!(in_pieces <= in_one_go);
+    }
+
+    #[test]
+    fn settlement_oracle_resolutions() {
+        let settlement_oracle = SettlementOracle {
+            account: Pubkey::new_unique(),
+            owner_program: Pubkey::new_unique(),
+            outcome_offset: 3,
+            resolutions: vec![
+                OracleResolution {
+                    value: 1,
+                    winning_outcome: 0,
+                },
+                OracleResolution {
+                    value: 3,
+                    winning_outcome: 0,
+                },
+                OracleResolution {
+                    value: 2,
+                    winning_outcome: 1,
+                },
+            ],
+        };
+
+        assert!(settlement_oracle.validate(2).is_ok());
+        assert!(settlement_oracle.validate(1).is_err());
+
+        assert_eq!(settlement_oracle.winning_outcome(&[9, 9, 9, 0]), None);
+        assert_eq!(settlement_oracle.winning_outcome(&[9, 9, 9, 1]), Some(0));
+        assert_eq!(settlement_oracle.winning_outcome(&[9, 9, 9, 2]), Some(1));
+        assert_eq!(settlement_oracle.winning_outcome(&[9, 9, 9, 3]), Some(0));
+        // data that's too short hasn't resolved, rather than panicking
+        assert_eq!(settlement_oracle.winning_outcome(&[1, 1, 1]), None);
+
+        let mut vault = settled_vault(vec![0, 0]);
+        vault.status = VaultStatus::Active;
+        vault.conditional_on_finalize_token_mint = Pubkey::new_unique();
+
+        vault.settle_with_winning_outcome(1);
+
+        assert!(vault.status == VaultStatus::Reverted);
+    }
+}
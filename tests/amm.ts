@@ -16,6 +16,17 @@ import {
   getAccount,
   getMint,
 } from "spl-token-bankrun";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMintToInstruction,
+  getMintLen,
+  unpackAccount,
+} from "@solana/spl-token";
 
 import {
   getAmmAddr,
@@ -23,13 +34,21 @@ import {
   PriceMath,
   getATA,
   getAmmLpMintAddr,
-} from "@metadaoproject/futarchy-ts";
-import { Keypair, PublicKey } from "@solana/web3.js";
+  MINIMUM_LIQUIDITY,
+} from "../futarchy-ts/src";
+import {
+  ComputeBudgetProgram,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
 import { assert } from "chai";
 import { expectError, fastForward } from "./utils/utils";
 
 const META_DECIMALS = 9;
 const USDC_DECIMALS = 6;
+const TWAP_CHECKPOINT_INTERVAL_SLOTS = 150n * 15n;
 
 describe("amm", async function () {
   let provider: BankrunProvider,
@@ -128,6 +147,11 @@ describe("amm", async function () {
       assert.equal(ammAcc.quoteMintDecimals, 6);
       assert.isTrue(ammAcc.baseAmount.eqn(0));
       assert.isTrue(ammAcc.quoteAmount.eqn(0));
+      assert.isTrue(ammAcc.swapFeeBps.eqn(100));
+      assert.isTrue(ammAcc.protocolFeeBps.eqn(0));
+      assert.ok(ammAcc.protocolFeeRecipient.equals(payer.publicKey));
+      assert.isTrue(ammAcc.baseProtocolFees.eqn(0));
+      assert.isTrue(ammAcc.quoteProtocolFees.eqn(0));
       assert.isTrue(
         ammAcc.oracle.lastObservation.eq(expectedInitialObservation)
       );
@@ -140,6 +164,7 @@ describe("amm", async function () {
       assert.isTrue(
         ammAcc.oracle.initialObservation.eq(expectedInitialObservation)
       );
+      assert.exists(ammAcc.oracle.observationClampMode.absolute);
     });
 
     it("fails to create an amm with two identical mints", async function () {
@@ -166,6 +191,86 @@ describe("amm", async function () {
         .rpc()
         .then(callbacks[0], callbacks[1]);
     });
+
+    it("creates an amm with a custom swap fee", async function () {
+      let proposal = Keypair.generate().publicKey;
+      let amm = await ammClient.createAmm(proposal, META, USDC, 500, 10, 30);
+
+      const ammAcc = await ammClient.getAmm(amm);
+
+      assert.isTrue(ammAcc.swapFeeBps.eqn(30));
+    });
+
+    it("fails to create an amm with a swap fee of 100% or more", async function () {
+      let [
+        twapFirstObservationScaled,
+        twapMaxObservationChangePerUpdateScaled,
+      ] = PriceMath.getAmmPrices(META_DECIMALS, USDC_DECIMALS, 100, 1);
+
+      const callbacks = expectError(
+        "InvalidSwapFee",
+        "create AMM succeeded despite a swap fee of 100%"
+      );
+
+      await ammClient
+        .createAmmIx(
+          META,
+          USDC,
+          twapFirstObservationScaled,
+          twapMaxObservationChangePerUpdateScaled,
+          Keypair.generate().publicKey,
+          new BN(10_000)
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+    it("creates an amm with a bps observation clamp", async function () {
+      let amm = await ammClient.createAmm(
+        Keypair.generate().publicKey,
+        META,
+        USDC,
+        500,
+        200,
+        undefined,
+        undefined,
+        undefined,
+        { bps: {} }
+      );
+
+      const ammAcc = await ammClient.getAmm(amm);
+
+      assert.exists(ammAcc.oracle.observationClampMode.bps);
+      assert.isTrue(ammAcc.oracle.maxObservationChangePerUpdate.eqn(200));
+    });
+
+    it("fails to create an amm with a bps clamp above 100%", async function () {
+      let [twapFirstObservationScaled] = PriceMath.getAmmPrices(
+        META_DECIMALS,
+        USDC_DECIMALS,
+        100,
+        1
+      );
+
+      const callbacks = expectError(
+        "InvalidObservationClamp",
+        "create AMM succeeded despite a bps clamp above 100%"
+      );
+
+      await ammClient
+        .createAmmIx(
+          META,
+          USDC,
+          twapFirstObservationScaled,
+          new BN(10_001),
+          Keypair.generate().publicKey,
+          undefined,
+          undefined,
+          undefined,
+          { bps: {} }
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
   });
 
   describe("#add_liquidity", async function () {
@@ -194,15 +299,20 @@ describe("amm", async function () {
 
       const storedAmm = await ammClient.getAmm(amm);
 
+      const userLpAccount = await getAccount(
+        banksClient,
+        getATA(storedAmm.lpMint, payer.publicKey)[0]
+      );
+      const vaultLpAccount = await getAccount(
+        banksClient,
+        getATA(storedAmm.lpMint, amm)[0]
+      );
+
       assert.equal(
-        (
-          await getAccount(
-            banksClient,
-            getATA(storedAmm.lpMint, payer.publicKey)[0]
-          )
-        ).amount,
-        BigInt(5000 * 10 ** 6)
+        userLpAccount.amount,
+        BigInt(5000 * 10 ** 6 - MINIMUM_LIQUIDITY)
       );
+      assert.equal(vaultLpAccount.amount, BigInt(MINIMUM_LIQUIDITY));
     });
 
     it("adds liquidity after it's already been added", async function () {
@@ -258,6 +368,112 @@ describe("amm", async function () {
         expectedLpSupply: 10000 * 10 ** 6,
       });
     });
+
+    it("prices later deposits off reserves, not donations", async function () {
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(5000 * 10 ** 6),
+          new BN(5 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
+
+      // send tokens straight to the vaults, bypassing add_liquidity
+      await mintTo(
+        banksClient,
+        payer,
+        META,
+        getATA(META, amm)[0],
+        payer.publicKey,
+        1_000 * 10 ** 9
+      );
+      await mintTo(
+        banksClient,
+        payer,
+        USDC,
+        getATA(USDC, amm)[0],
+        payer.publicKey,
+        100_000 * 10 ** 6
+      );
+
+      let storedAmm = await ammClient.getAmm(amm);
+      assert.equal(storedAmm.baseAmount.toString(), (5 * 10 ** 9).toString());
+      assert.equal(
+        storedAmm.quoteAmount.toString(),
+        (5000 * 10 ** 6).toString()
+      );
+
+      const userLpAccount = getATA(lpMint, payer.publicKey)[0];
+      const lpBalanceStart = (await getAccount(banksClient, userLpAccount))
+        .amount;
+
+      // the next depositor gets the same share of the pool as if there had
+      // been no donation
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(1000 * 10 ** 6),
+          new BN(1 * 10 ** 9),
+          new BN(1000 * 10 ** 6)
+        )
+        .rpc();
+
+      const lpBalanceEnd = (await getAccount(banksClient, userLpAccount))
+        .amount;
+      assert.equal(lpBalanceEnd - lpBalanceStart, BigInt(1000 * 10 ** 6));
+
+      storedAmm = await ammClient.getAmm(amm);
+      assert.equal(storedAmm.baseAmount.toString(), (6 * 10 ** 9).toString());
+      assert.equal(
+        storedAmm.quoteAmount.toString(),
+        (6000 * 10 ** 6).toString()
+      );
+    });
+
+    it("can't be drained back to empty by the first depositor", async function () {
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(5000 * 10 ** 6),
+          new BN(5 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
+
+      const userLpAccount = await getAccount(
+        banksClient,
+        getATA(lpMint, payer.publicKey)[0]
+      );
+
+      await ammClient
+        .removeLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(userLpAccount.amount.toString()),
+          new BN(0),
+          new BN(0)
+        )
+        .rpc();
+
+      await validateAmmState({
+        banksClient,
+        ammClient,
+        amm,
+        base: META,
+        quote: USDC,
+        expectedBaseAmount: MINIMUM_LIQUIDITY,
+        expectedQuoteAmount: MINIMUM_LIQUIDITY,
+        expectedLpSupply: MINIMUM_LIQUIDITY,
+      });
+    });
   });
 
   describe("#swap", async function () {
@@ -446,111 +662,1117 @@ describe("amm", async function () {
       assert.isBelow(quoteReceived, startingQuoteSwapAmount);
       assert.isAbove(quoteReceived, startingQuoteSwapAmount * 0.98);
     });
-  });
-
-  describe("#remove_liquidity", async function () {
-    beforeEach(async function () {
-      await ammClient.addLiquidity(amm, 1000, 2);
-    });
 
-    it("can't remove 0 liquidity", async function () {
-      const callbacks = expectError(
-        "ZeroLiquidityRemove",
-        "was able to remove 0 liquidity"
+    it("charges the amm's own swap fee", async function () {
+      let proposal = Keypair.generate().publicKey;
+      let cheapAmm = await ammClient.createAmm(
+        proposal,
+        META,
+        USDC,
+        500,
+        10,
+        30
       );
-
       await ammClient
-        .removeLiquidityIx(amm, META, USDC, new BN(0), new BN(0), new BN(0))
-        .rpc()
-        .then(callbacks[0], callbacks[1]);
-    });
+        .addLiquidityIx(
+          cheapAmm,
+          META,
+          USDC,
+          new BN(10_000 * 10 ** 6),
+          new BN(10 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
 
-    it("remove some liquidity from an amm position", async function () {
-      const ammStart = await ammClient.getAmm(amm);
+      const storedAmm = await ammClient.getAmm(cheapAmm);
+      let sim = ammClient.simulateSwap(
+        new BN(1 * 10 ** 9),
+        { sell: {} },
+        storedAmm.baseAmount,
+        storedAmm.quoteAmount,
+        storedAmm.swapFeeBps
+      );
 
-      let userLpAccount = getATA(lpMint, payer.publicKey)[0];
+      // same trade as in "sells" above, but with a 0.3% fee instead of 1%
+      assert.isAbove(sim.expectedOut.toNumber(), 900.818926 * 10 ** 6);
 
-      const userLpAccountStart = await getAccount(banksClient, userLpAccount);
-      const lpMintStart = await getMint(banksClient, lpMint);
+      await ammClient
+        .swapIx(
+          cheapAmm,
+          META,
+          USDC,
+          { sell: {} },
+          new BN(1 * 10 ** 9),
+          sim.expectedOut
+        )
+        .rpc();
+
+      await validateAmmState({
+        banksClient,
+        ammClient,
+        amm: cheapAmm,
+        base: META,
+        quote: USDC,
+        expectedBaseAmount: 11 * 10 ** 9,
+        expectedQuoteAmount: 10_000 * 10 ** 6 - sim.expectedOut.toNumber(),
+        expectedLpSupply: 10_000 * 10 ** 6,
+      });
+    });
+  });
 
+  describe("#swap_exact_output", async function () {
+    beforeEach(async function () {
       await ammClient
-        .removeLiquidityIx(
+        .addLiquidityIx(
           amm,
           META,
           USDC,
-          new BN(userLpAccountStart.amount.toString()).divn(2),
-          new BN(0),
+          new BN(10_000 * 10 ** 6),
+          new BN(10 * 10 ** 9),
           new BN(0)
         )
         .rpc();
+    });
 
-      const userLpAccountEnd = await getAccount(banksClient, userLpAccount);
-      const lpMintEnd = await getMint(banksClient, lpMint);
-
-      const ammEnd = await ammClient.getAmm(amm);
+    it("buys an exact amount of base", async function () {
+      const outputAmount = new BN(100_000_000);
 
-      assert.isBelow(Number(lpMintEnd.supply), Number(lpMintStart.supply));
-      assert.isBelow(
-        Number(userLpAccountEnd.amount),
-        Number(userLpAccountStart.amount)
+      const ammStart = await ammClient.getAmm(amm);
+      let sim = ammClient.simulateSwapExactOutput(
+        outputAmount,
+        { buy: {} },
+        ammStart.baseAmount,
+        ammStart.quoteAmount,
+        ammStart.swapFeeBps
       );
 
-      assert.isBelow(
-        ammEnd.baseAmount.toNumber(),
-        ammStart.baseAmount.toNumber()
-      );
-      assert.isBelow(
-        ammEnd.quoteAmount.toNumber(),
-        ammStart.quoteAmount.toNumber()
+      // first, show that it fails when we're only willing to pay 1 less
+      let callbacks = expectError(
+        "SwapInputMaxExceeded",
+        "we paid less than the AMM needed for this output"
       );
-    });
-
-    it("remove all liquidity from an amm position", async function () {
-      const ammStart = await ammClient.getAmm(amm);
-
-      let userLpAccount = getATA(lpMint, payer.publicKey)[0];
 
-      const userLpAccountStart = await getAccount(banksClient, userLpAccount);
-      const lpMintStart = await getMint(banksClient, lpMint);
+      await ammClient
+        .swapExactOutputIx(
+          amm,
+          META,
+          USDC,
+          { buy: {} },
+          outputAmount,
+          sim.expectedIn.subn(1)
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
 
       await ammClient
-        .removeLiquidityIx(
+        .swapExactOutputIx(
           amm,
           META,
           USDC,
-          new BN(userLpAccountStart.amount.toString()),
-          new BN(1 * 10 ** 9),
-          new BN(10 * 10 ** 6)
+          { buy: {} },
+          outputAmount,
+          sim.expectedIn
         )
         .rpc();
 
-      const userLpAccountEnd = await getAccount(banksClient, userLpAccount);
-      const lpMintEnd = await getMint(banksClient, lpMint);
-
-      assert.isBelow(Number(lpMintEnd.supply), Number(lpMintStart.supply));
-      assert.isBelow(
-        Number(userLpAccountEnd.amount),
-        Number(userLpAccountStart.amount)
-      );
+      await validateAmmState({
+        banksClient,
+        ammClient,
+        amm,
+        base: META,
+        quote: USDC,
+        expectedBaseAmount: sim.newBaseReserves.toNumber(),
+        expectedQuoteAmount: sim.newQuoteReserves.toNumber(),
+        expectedLpSupply: 10_000 * 10 ** 6,
+      });
+    });
 
-      const ammEnd = await ammClient.getAmm(amm);
+    it("sells for an exact amount of quote", async function () {
+      const outputAmount = new BN(500 * 10 ** 6);
 
-      assert.isBelow(
-        ammEnd.baseAmount.toNumber(),
-        ammStart.baseAmount.toNumber()
-      );
-      assert.isBelow(
-        ammEnd.quoteAmount.toNumber(),
-        ammStart.quoteAmount.toNumber()
+      const ammStart = await ammClient.getAmm(amm);
+      let sim = ammClient.simulateSwapExactOutput(
+        outputAmount,
+        { sell: {} },
+        ammStart.baseAmount,
+        ammStart.quoteAmount,
+        ammStart.swapFeeBps
       );
-    });
-  });
-});
 
-async function validateAmmState({
-  banksClient,
-  ammClient,
-  amm,
+      await ammClient
+        .swapExactOutputIx(
+          amm,
+          META,
+          USDC,
+          { sell: {} },
+          outputAmount,
+          sim.expectedIn
+        )
+        .rpc();
+
+      await validateAmmState({
+        banksClient,
+        ammClient,
+        amm,
+        base: META,
+        quote: USDC,
+        expectedBaseAmount: sim.newBaseReserves.toNumber(),
+        expectedQuoteAmount: 9_500 * 10 ** 6,
+        expectedLpSupply: 10_000 * 10 ** 6,
+      });
+    });
+
+    it("checks the balance against what the swap actually costs", async function () {
+      // buying 9.99 of the pool's 10 META costs ~10M USDC, which is more than
+      // we have, even though we set `inputAmountMax` high enough to pay it
+      let callbacks = expectError(
+        "InsufficientBalance",
+        "we should have caught a user not having enough balance"
+      );
+
+      await ammClient
+        .swapExactOutputIx(
+          amm,
+          META,
+          USDC,
+          { buy: {} },
+          new BN(9_990_000_000),
+          new BN("18446744073709551615")
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("can't buy out the whole pool", async function () {
+      let callbacks = expectError(
+        "OutputAmountExceedsReserves",
+        "we were able to swap for the entire pool"
+      );
+
+      await ammClient
+        .swapExactOutputIx(
+          amm,
+          META,
+          USDC,
+          { buy: {} },
+          new BN(10 * 10 ** 9),
+          new BN("18446744073709551615")
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+  });
+
+  describe("#quote", async function () {
+    beforeEach(async function () {
+      await ammClient.addLiquidity(amm, 10_000, 10);
+    });
+
+    it("quotes a swap without changing the amm", async function () {
+      const ammStart = await ammClient.getAmm(amm);
+
+      const quote = await ammClient.quoteSwap(
+        amm,
+        { sell: {} },
+        new BN(1 * 10 ** 9)
+      );
+
+      const ammAfterQuote = await ammClient.getAmm(amm);
+      assert.equal(
+        ammAfterQuote.baseAmount.toString(),
+        ammStart.baseAmount.toString()
+      );
+      assert.equal(ammAfterQuote.seqNum.toString(), ammStart.seqNum.toString());
+
+      const sim = ammClient.simulateSwap(
+        new BN(1 * 10 ** 9),
+        { sell: {} },
+        ammStart.baseAmount,
+        ammStart.quoteAmount
+      );
+      assert.equal(quote.outputAmount.toString(), sim.expectedOut.toString());
+      assert.equal(quote.feeAmount.toString(), (1 * 10 ** 7).toString());
+
+      const userUsdc = getATA(USDC, payer.publicKey)[0];
+      const usdcStart = (await getAccount(banksClient, userUsdc)).amount;
+
+      await ammClient
+        .swapIx(
+          amm,
+          META,
+          USDC,
+          { sell: {} },
+          new BN(1 * 10 ** 9),
+          quote.outputAmount
+        )
+        .rpc();
+
+      const usdcEnd = (await getAccount(banksClient, userUsdc)).amount;
+      assert.equal(
+        (usdcEnd - usdcStart).toString(),
+        quote.outputAmount.toString()
+      );
+    });
+
+    it("quotes adding and removing liquidity", async function () {
+      const userLp = getATA(lpMint, payer.publicKey)[0];
+
+      const addQuote = await ammClient.quoteAddLiquidity(
+        amm,
+        new BN(1_000 * 10 ** 6),
+        new BN(2 * 10 ** 9)
+      );
+      // rounded up in the pool's favor
+      assert.equal(
+        addQuote.baseAmount.toString(),
+        (1 * 10 ** 9 + 1).toString()
+      );
+
+      const lpStart = (await getAccount(banksClient, userLp)).amount;
+
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(1_000 * 10 ** 6),
+          new BN(2 * 10 ** 9),
+          addQuote.lpTokensMinted
+        )
+        .rpc();
+
+      const lpEnd = (await getAccount(banksClient, userLp)).amount;
+      assert.equal(
+        (lpEnd - lpStart).toString(),
+        addQuote.lpTokensMinted.toString()
+      );
+
+      const ammStart = await ammClient.getAmm(amm);
+      const removeQuote = await ammClient.quoteRemoveLiquidity(
+        amm,
+        addQuote.lpTokensMinted
+      );
+
+      await ammClient
+        .removeLiquidityIx(
+          amm,
+          META,
+          USDC,
+          addQuote.lpTokensMinted,
+          removeQuote.baseAmount,
+          removeQuote.quoteAmount
+        )
+        .rpc();
+
+      const ammEnd = await ammClient.getAmm(amm);
+      assert.equal(
+        ammStart.baseAmount.sub(ammEnd.baseAmount).toString(),
+        removeQuote.baseAmount.toString()
+      );
+      assert.equal(
+        ammStart.quoteAmount.sub(ammEnd.quoteAmount).toString(),
+        removeQuote.quoteAmount.toString()
+      );
+    });
+  });
+
+  describe("#crank_that_twap", async function () {
+    beforeEach(async function () {
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(10_000 * 10 ** 6),
+          new BN(10 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
+    });
+
+    it("cranks without a checkpoints account", async function () {
+      await fastForward(context, 200n);
+
+      await ammClient.crankThatTwap(amm);
+
+      const storedAmm = await ammClient.getAmm(amm);
+      assert.isTrue(
+        storedAmm.oracle.lastUpdatedSlot.gt(storedAmm.createdAtSlot)
+      );
+    });
+
+    it("records checkpoints at most once per interval", async function () {
+      await ammClient.createTwapCheckpoints(amm);
+
+      let storedAmm = await ammClient.getAmm(amm);
+      let twapCheckpoints = await ammClient.getTwapCheckpoints(amm);
+
+      assert.isTrue(storedAmm.hasTwapCheckpoints);
+
+      // seeded with wherever the oracle was at creation
+      assert.ok(twapCheckpoints.amm.equals(amm));
+      assert.equal(twapCheckpoints.checkpoints.length, 1);
+      assert.isTrue(
+        twapCheckpoints.checkpoints[0].slot.eq(storedAmm.oracle.lastUpdatedSlot)
+      );
+
+      for (let [i, slots] of [100n, TWAP_CHECKPOINT_INTERVAL_SLOTS].entries()) {
+        await fastForward(context, slots);
+
+        await ammClient
+          .crankThatTwapIx(amm, true)
+          .preInstructions([
+            // this is to get around bankrun thinking we've processed the same transaction multiple times
+            ComputeBudgetProgram.setComputeUnitPrice({
+              microLamports: i,
+            }),
+          ])
+          .rpc();
+      }
+
+      storedAmm = await ammClient.getAmm(amm);
+      twapCheckpoints = await ammClient.getTwapCheckpoints(amm);
+
+      // the first crank was too soon after the seed, the second wasn't
+      assert.equal(twapCheckpoints.checkpoints.length, 2);
+      assert.isTrue(
+        twapCheckpoints.checkpoints[1].slot.eq(storedAmm.oracle.lastUpdatedSlot)
+      );
+      assert.isTrue(
+        twapCheckpoints.checkpoints[1].aggregator.eq(
+          storedAmm.oracle.aggregator
+        )
+      );
+    });
+
+    it("requires the checkpoints account once the amm has one", async function () {
+      await ammClient.createTwapCheckpoints(amm);
+      await fastForward(context, TWAP_CHECKPOINT_INTERVAL_SLOTS);
+
+      const callbacks = expectError(
+        "MissingTwapCheckpoints",
+        "cranked without recording into the amm's checkpoints"
+      );
+      await ammClient
+        .crankThatTwapIx(amm)
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+
+      await ammClient.crankThatTwap(amm);
+
+      const storedAmm = await ammClient.getAmm(amm);
+      const twapCheckpoints = await ammClient.getTwapCheckpoints(amm);
+      assert.equal(twapCheckpoints.checkpoints.length, 2);
+      assert.isTrue(
+        twapCheckpoints.checkpoints[1].slot.eq(storedAmm.oracle.lastUpdatedSlot)
+      );
+    });
+
+    it("clamps observations relative to the last one in bps mode", async function () {
+      let bpsAmm = await ammClient.createAmm(
+        Keypair.generate().publicKey,
+        META,
+        USDC,
+        500,
+        200,
+        undefined,
+        undefined,
+        undefined,
+        { bps: {} }
+      );
+
+      // the pool's price is 1000, way above the initial observation of 500
+      await ammClient
+        .addLiquidityIx(
+          bpsAmm,
+          META,
+          USDC,
+          new BN(10_000 * 10 ** 6),
+          new BN(10 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
+
+      const ammStart = await ammClient.getAmm(bpsAmm);
+      let expectedObservation = ammStart.oracle.lastObservation;
+
+      for (let i = 0; i < 2; i++) {
+        await fastForward(context, 150n);
+
+        await ammClient
+          .crankThatTwapIx(bpsAmm)
+          .preInstructions([
+            // this is to get around bankrun thinking we've processed the same transaction multiple times
+            ComputeBudgetProgram.setComputeUnitPrice({
+              microLamports: i,
+            }),
+          ])
+          .rpc();
+
+        // each update can only move 2% from the last observation
+        expectedObservation = expectedObservation.add(
+          expectedObservation.muln(200).divn(10_000)
+        );
+
+        const storedAmm = await ammClient.getAmm(bpsAmm);
+        assert.isTrue(storedAmm.oracle.lastObservation.eq(expectedObservation));
+      }
+    });
+  });
+
+  describe("#seq_num", async function () {
+    it("bumps seq_num on every state change", async function () {
+      assert.isTrue((await ammClient.getAmm(amm)).seqNum.eqn(0));
+
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(10_000 * 10 ** 6),
+          new BN(10 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
+      assert.isTrue((await ammClient.getAmm(amm)).seqNum.eqn(1));
+
+      await ammClient.swap(amm, { buy: {} }, 100, 0);
+      assert.isTrue((await ammClient.getAmm(amm)).seqNum.eqn(2));
+
+      await fastForward(context, 150n);
+      await ammClient.crankThatTwap(amm);
+      assert.isTrue((await ammClient.getAmm(amm)).seqNum.eqn(3));
+
+      await ammClient
+        .removeLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(1_000 * 10 ** 6),
+          new BN(0),
+          new BN(0)
+        )
+        .rpc();
+      assert.isTrue((await ammClient.getAmm(amm)).seqNum.eqn(4));
+    });
+
+    it("doesn't bump seq_num for cranks that don't update the oracle", async function () {
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(10_000 * 10 ** 6),
+          new BN(10 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
+
+      await fastForward(context, 150n);
+      await ammClient.crankThatTwap(amm);
+
+      const ammStart = await ammClient.getAmm(amm);
+      assert.isTrue(ammStart.seqNum.eqn(2));
+
+      // same slot, so the oracle can't update again
+      await ammClient
+        .crankThatTwapIx(amm)
+        .preInstructions([
+          ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 1 }),
+        ])
+        .rpc();
+
+      const ammEnd = await ammClient.getAmm(amm);
+      assert.isTrue(ammEnd.seqNum.eqn(2));
+      assert.isTrue(
+        ammEnd.oracle.lastUpdatedSlot.eq(ammStart.oracle.lastUpdatedSlot)
+      );
+    });
+  });
+
+  describe("#remove_liquidity", async function () {
+    beforeEach(async function () {
+      await ammClient.addLiquidity(amm, 1000, 2);
+    });
+
+    it("can't remove 0 liquidity", async function () {
+      const callbacks = expectError(
+        "ZeroLiquidityRemove",
+        "was able to remove 0 liquidity"
+      );
+
+      await ammClient
+        .removeLiquidityIx(amm, META, USDC, new BN(0), new BN(0), new BN(0))
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("remove some liquidity from an amm position", async function () {
+      const ammStart = await ammClient.getAmm(amm);
+
+      let userLpAccount = getATA(lpMint, payer.publicKey)[0];
+
+      const userLpAccountStart = await getAccount(banksClient, userLpAccount);
+      const lpMintStart = await getMint(banksClient, lpMint);
+
+      await ammClient
+        .removeLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(userLpAccountStart.amount.toString()).divn(2),
+          new BN(0),
+          new BN(0)
+        )
+        .rpc();
+
+      const userLpAccountEnd = await getAccount(banksClient, userLpAccount);
+      const lpMintEnd = await getMint(banksClient, lpMint);
+
+      const ammEnd = await ammClient.getAmm(amm);
+
+      assert.isBelow(Number(lpMintEnd.supply), Number(lpMintStart.supply));
+      assert.isBelow(
+        Number(userLpAccountEnd.amount),
+        Number(userLpAccountStart.amount)
+      );
+
+      assert.isBelow(
+        ammEnd.baseAmount.toNumber(),
+        ammStart.baseAmount.toNumber()
+      );
+      assert.isBelow(
+        ammEnd.quoteAmount.toNumber(),
+        ammStart.quoteAmount.toNumber()
+      );
+    });
+
+    it("remove all liquidity from an amm position", async function () {
+      const ammStart = await ammClient.getAmm(amm);
+
+      let userLpAccount = getATA(lpMint, payer.publicKey)[0];
+
+      const userLpAccountStart = await getAccount(banksClient, userLpAccount);
+      const lpMintStart = await getMint(banksClient, lpMint);
+
+      await ammClient
+        .removeLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(userLpAccountStart.amount.toString()),
+          new BN(1 * 10 ** 9),
+          new BN(10 * 10 ** 6)
+        )
+        .rpc();
+
+      const userLpAccountEnd = await getAccount(banksClient, userLpAccount);
+      const lpMintEnd = await getMint(banksClient, lpMint);
+
+      assert.isBelow(Number(lpMintEnd.supply), Number(lpMintStart.supply));
+      assert.isBelow(
+        Number(userLpAccountEnd.amount),
+        Number(userLpAccountStart.amount)
+      );
+
+      const ammEnd = await ammClient.getAmm(amm);
+
+      assert.isBelow(
+        ammEnd.baseAmount.toNumber(),
+        ammStart.baseAmount.toNumber()
+      );
+      assert.isBelow(
+        ammEnd.quoteAmount.toNumber(),
+        ammStart.quoteAmount.toNumber()
+      );
+    });
+  });
+  describe("#close_amm", async function () {
+    beforeEach(async function () {
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(5000 * 10 ** 6),
+          new BN(5 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
+    });
+
+    it("can't close an amm while users hold its LP", async function () {
+      const callbacks = expectError(
+        "AmmNotEmpty",
+        "closed an amm that still had liquidity in it"
+      );
+
+      await ammClient.closeAmm(amm).then(callbacks[0], callbacks[1]);
+    });
+
+    it("can't close an amm that has never had liquidity", async function () {
+      const freshAmm = await ammClient.createAmm(
+        Keypair.generate().publicKey,
+        META,
+        USDC,
+        500
+      );
+
+      const callbacks = expectError(
+        "AmmNeverFunded",
+        "closed an amm before its first deposit"
+      );
+
+      await ammClient.closeAmm(freshAmm).then(callbacks[0], callbacks[1]);
+    });
+
+    it("closes an amm and refunds its rent once all LP is removed", async function () {
+      const userLpAccount = await getAccount(
+        banksClient,
+        getATA(lpMint, payer.publicKey)[0]
+      );
+
+      await ammClient
+        .removeLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(userLpAccount.amount.toString()),
+          new BN(0),
+          new BN(0)
+        )
+        .rpc();
+
+      const storedAmm = await ammClient.getAmm(amm);
+      assert.isTrue(storedAmm.rentPayer.equals(payer.publicKey));
+
+      const lamportsStart = await banksClient.getBalance(payer.publicKey);
+
+      await ammClient.closeAmm(amm);
+
+      const lamportsEnd = await banksClient.getBalance(payer.publicKey);
+      assert.isAbove(Number(lamportsEnd), Number(lamportsStart));
+
+      assert.isNull(await banksClient.getAccount(amm));
+      assert.isNull(await banksClient.getAccount(getATA(META, amm)[0]));
+      assert.isNull(await banksClient.getAccount(getATA(USDC, amm)[0]));
+      assert.isNull(await banksClient.getAccount(getATA(lpMint, amm)[0]));
+    });
+
+    it("closes an amm's TWAP checkpoints along with it", async function () {
+      const twapCheckpoints = await ammClient.createTwapCheckpoints(amm);
+
+      const userLpAccount = await getAccount(
+        banksClient,
+        getATA(lpMint, payer.publicKey)[0]
+      );
+
+      await ammClient
+        .removeLiquidityIx(
+          amm,
+          META,
+          USDC,
+          new BN(userLpAccount.amount.toString()),
+          new BN(0),
+          new BN(0)
+        )
+        .rpc();
+
+      const callbacks = expectError(
+        "MissingTwapCheckpoints",
+        "closed an amm without closing its checkpoints"
+      );
+      await ammClient
+        .closeAmmIx(amm, META, USDC, payer.publicKey)
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+
+      await ammClient.closeAmm(amm);
+
+      assert.isNull(await banksClient.getAccount(amm));
+      assert.isNull(await banksClient.getAccount(twapCheckpoints));
+    });
+
+    it("can't close an amm with uncollected protocol fees", async function () {
+      const feeAmm = await ammClient.createAmm(
+        Keypair.generate().publicKey,
+        META,
+        USDC,
+        500,
+        10,
+        100,
+        2_500
+      );
+
+      await ammClient
+        .addLiquidityIx(
+          feeAmm,
+          META,
+          USDC,
+          new BN(10_000 * 10 ** 6),
+          new BN(10 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
+
+      await ammClient
+        .swapIx(
+          feeAmm,
+          META,
+          USDC,
+          { sell: {} },
+          new BN(1 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
+
+      const [feeLpMint] = getAmmLpMintAddr(ammClient.getProgramId(), feeAmm);
+      const userLpAccount = await getAccount(
+        banksClient,
+        getATA(feeLpMint, payer.publicKey)[0]
+      );
+
+      await ammClient
+        .removeLiquidityIx(
+          feeAmm,
+          META,
+          USDC,
+          new BN(userLpAccount.amount.toString()),
+          new BN(0),
+          new BN(0)
+        )
+        .rpc();
+
+      const callbacks = expectError(
+        "UncollectedProtocolFees",
+        "closed an amm before its protocol fees were collected"
+      );
+
+      await ammClient.closeAmm(feeAmm).then(callbacks[0], callbacks[1]);
+    });
+  });
+
+  describe("#collect_protocol_fees", async function () {
+    let feeRecipient: Keypair, feeAmm: PublicKey;
+
+    beforeEach(async function () {
+      feeRecipient = Keypair.generate();
+
+      // 1% swap fee, a quarter of which goes to the protocol
+      feeAmm = await ammClient.createAmm(
+        Keypair.generate().publicKey,
+        META,
+        USDC,
+        500,
+        10,
+        100,
+        2_500,
+        feeRecipient.publicKey
+      );
+
+      await ammClient
+        .addLiquidityIx(
+          feeAmm,
+          META,
+          USDC,
+          new BN(10_000 * 10 ** 6),
+          new BN(10 * 10 ** 9),
+          new BN(0)
+        )
+        .rpc();
+
+      await createAssociatedTokenAccount(
+        banksClient,
+        payer,
+        META,
+        feeRecipient.publicKey
+      );
+      await createAssociatedTokenAccount(
+        banksClient,
+        payer,
+        USDC,
+        feeRecipient.publicKey
+      );
+    });
+
+    it("fails to create an amm with a protocol fee share above 100%", async function () {
+      let [
+        twapFirstObservationScaled,
+        twapMaxObservationChangePerUpdateScaled,
+      ] = PriceMath.getAmmPrices(META_DECIMALS, USDC_DECIMALS, 100, 1);
+
+      const callbacks = expectError(
+        "InvalidProtocolFee",
+        "create AMM succeeded despite a protocol fee share above 100%"
+      );
+
+      await ammClient
+        .createAmmIx(
+          META,
+          USDC,
+          twapFirstObservationScaled,
+          twapMaxObservationChangePerUpdateScaled,
+          Keypair.generate().publicKey,
+          new BN(100),
+          new BN(10_001)
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("keeps protocol fees out of the reserves", async function () {
+      const ammStart = await ammClient.getAmm(feeAmm);
+
+      let sim = ammClient.simulateSwap(
+        new BN(100 * 10 ** 6),
+        { buy: {} },
+        ammStart.baseAmount,
+        ammStart.quoteAmount,
+        ammStart.swapFeeBps,
+        ammStart.protocolFeeBps
+      );
+
+      await ammClient
+        .swapIx(
+          feeAmm,
+          META,
+          USDC,
+          { buy: {} },
+          new BN(100 * 10 ** 6),
+          sim.expectedOut
+        )
+        .rpc();
+
+      const ammEnd = await ammClient.getAmm(feeAmm);
+
+      // 1% of 100 USDC is 1 USDC, a quarter of which is 0.25 USDC
+      assert.equal(ammEnd.quoteProtocolFees.toString(), "250000");
+      assert.isTrue(ammEnd.baseProtocolFees.eqn(0));
+      assert.isTrue(ammEnd.quoteAmount.eq(sim.newQuoteReserves));
+      assert.isTrue(ammEnd.baseAmount.eq(sim.newBaseReserves));
+
+      assert.equal(
+        (await getAccount(banksClient, getATA(USDC, feeAmm)[0])).amount,
+        BigInt(ammEnd.quoteAmount.add(ammEnd.quoteProtocolFees).toString())
+      );
+    });
+
+    it("pays accrued fees out to the fee recipient", async function () {
+      await ammClient
+        .swapIx(
+          feeAmm,
+          META,
+          USDC,
+          { buy: {} },
+          new BN(100 * 10 ** 6),
+          new BN(1)
+        )
+        .rpc();
+      await ammClient
+        .swapIx(
+          feeAmm,
+          META,
+          USDC,
+          { sell: {} },
+          new BN(1 * 10 ** 9),
+          new BN(1)
+        )
+        .rpc();
+
+      const ammStart = await ammClient.getAmm(feeAmm);
+
+      // anyone can crank this, since the fees can only go to the recipient
+      await ammClient.collectProtocolFees(feeAmm);
+
+      const ammEnd = await ammClient.getAmm(feeAmm);
+
+      assert.isTrue(ammEnd.baseProtocolFees.eqn(0));
+      assert.isTrue(ammEnd.quoteProtocolFees.eqn(0));
+      assert.isTrue(ammEnd.baseAmount.eq(ammStart.baseAmount));
+      assert.isTrue(ammEnd.quoteAmount.eq(ammStart.quoteAmount));
+
+      const recipientBase = await getAccount(
+        banksClient,
+        getATA(META, feeRecipient.publicKey)[0]
+      );
+      const recipientQuote = await getAccount(
+        banksClient,
+        getATA(USDC, feeRecipient.publicKey)[0]
+      );
+
+      assert.equal(
+        recipientBase.amount,
+        BigInt(ammStart.baseProtocolFees.toString())
+      );
+      assert.equal(
+        recipientQuote.amount,
+        BigInt(ammStart.quoteProtocolFees.toString())
+      );
+
+      await validateAmmState({
+        banksClient,
+        ammClient,
+        amm: feeAmm,
+        base: META,
+        quote: USDC,
+        expectedBaseAmount: ammEnd.baseAmount.toNumber(),
+        expectedQuoteAmount: ammEnd.quoteAmount.toNumber(),
+        expectedLpSupply: 10_000 * 10 ** 6,
+      });
+    });
+  });
+
+  describe("#token_2022", async function () {
+    let FEE_META: PublicKey;
+
+    beforeEach(async function () {
+      // a token-2022 base mint that charges 1% on every transfer
+      const mintKeypair = Keypair.generate();
+      FEE_META = mintKeypair.publicKey;
+      const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+      const [userAta] = getATA(
+        FEE_META,
+        payer.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      let tx = new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: payer.publicKey,
+          newAccountPubkey: FEE_META,
+          space: mintLen,
+          lamports: Number(
+            (await banksClient.getRent()).minimumBalance(BigInt(mintLen))
+          ),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          FEE_META,
+          payer.publicKey,
+          payer.publicKey,
+          100,
+          BigInt(1_000_000 * 10 ** 9),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+          FEE_META,
+          META_DECIMALS,
+          payer.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createAssociatedTokenAccountInstruction(
+          payer.publicKey,
+          userAta,
+          payer.publicKey,
+          FEE_META,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createMintToInstruction(
+          FEE_META,
+          userAta,
+          payer.publicKey,
+          10_000 * 10 ** 9,
+          [],
+          TOKEN_2022_PROGRAM_ID
+        )
+      );
+      [tx.recentBlockhash] = await banksClient.getLatestBlockhash();
+      tx.feePayer = payer.publicKey;
+      tx.sign(payer, mintKeypair);
+      await banksClient.processTransaction(tx);
+    });
+
+    it("tracks what the vaults actually receive from a fee-charging mint", async function () {
+      const proposal = Keypair.generate().publicKey;
+      const amm = await ammClient.createAmm(proposal, FEE_META, USDC, 500);
+
+      let storedAmm = await ammClient.getAmm(amm);
+      assert.isTrue(
+        (await ammClient.getTokenProgram(storedAmm.baseMint)).equals(
+          TOKEN_2022_PROGRAM_ID
+        )
+      );
+
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          FEE_META,
+          USDC,
+          new BN(5_000 * 10 ** 6),
+          new BN(10 * 10 ** 9),
+          new BN(0),
+          payer.publicKey,
+          TOKEN_2022_PROGRAM_ID,
+          TOKEN_PROGRAM_ID
+        )
+        .rpc();
+
+      // 1% of the deposit is withheld by the mint
+      storedAmm = await ammClient.getAmm(amm);
+      assert.equal(storedAmm.baseAmount.toString(), "9900000000");
+      await validateToken2022Vault(banksClient, amm, FEE_META, storedAmm);
+
+      await ammClient.swap(amm, { sell: {} }, 1, 0);
+
+      storedAmm = await ammClient.getAmm(amm);
+      assert.equal(storedAmm.baseAmount.toString(), "10890000000");
+      await validateToken2022Vault(banksClient, amm, FEE_META, storedAmm);
+    });
+
+    it("closes an amm whose vault is withholding transfer fees", async function () {
+      const proposal = Keypair.generate().publicKey;
+      const amm = await ammClient.createAmm(proposal, FEE_META, USDC, 500);
+      const [lpMint] = getAmmLpMintAddr(ammClient.getProgramId(), amm);
+
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          FEE_META,
+          USDC,
+          new BN(5_000 * 10 ** 6),
+          new BN(10 * 10 ** 9),
+          new BN(0),
+          payer.publicKey,
+          TOKEN_2022_PROGRAM_ID,
+          TOKEN_PROGRAM_ID
+        )
+        .rpc();
+
+      const userLpAccount = await getAccount(
+        banksClient,
+        getATA(lpMint, payer.publicKey)[0]
+      );
+
+      await ammClient
+        .removeLiquidityIx(
+          amm,
+          FEE_META,
+          USDC,
+          new BN(userLpAccount.amount.toString()),
+          new BN(0),
+          new BN(0),
+          TOKEN_2022_PROGRAM_ID,
+          TOKEN_PROGRAM_ID
+        )
+        .rpc();
+
+      await ammClient.closeAmm(amm);
+
+      assert.isNull(await banksClient.getAccount(amm));
+      assert.isNull(
+        await banksClient.getAccount(
+          getATA(FEE_META, amm, TOKEN_2022_PROGRAM_ID)[0]
+        )
+      );
+    });
+  });
+});
+
+async function validateAmmState({
+  banksClient,
+  ammClient,
+  amm,
   base,
   quote,
   expectedBaseAmount,
@@ -587,3 +1809,20 @@ async function validateAmmState({
     BigInt(expectedLpSupply)
   );
 }
+
+async function validateToken2022Vault(
+  banksClient: BanksClient,
+  amm: PublicKey,
+  base: PublicKey,
+  storedAmm: { baseAmount: BN }
+) {
+  const [vaultAtaBase] = getATA(base, amm, TOKEN_2022_PROGRAM_ID);
+  const vaultInfo = await banksClient.getAccount(vaultAtaBase);
+  const vaultAccount = unpackAccount(
+    vaultAtaBase,
+    { ...vaultInfo, data: Buffer.from(vaultInfo.data) },
+    TOKEN_2022_PROGRAM_ID
+  );
+
+  assert.equal(storedAmm.baseAmount.toString(), vaultAccount.amount.toString());
+}
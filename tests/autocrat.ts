@@ -35,14 +35,15 @@ import {
   AUTOCRAT_PROGRAM_ID,
   CONDITIONAL_VAULT_PROGRAM_ID,
   AmmClient,
+  MINIMUM_LIQUIDITY,
+  SettlementOracle,
   getATA,
   getAmmAddr,
   getAmmLpMintAddr,
   getVaultAddr,
 } from "../futarchy-ts/src";
 import { PriceMath } from "../futarchy-ts/src/utils/priceMath";
-// import { AutocratClient } from "../futarchy-ts/src/AutocratClient";
-import {AutocratClient} from "@metadaoproject/futarchy-ts";
+import { AutocratClient } from "../futarchy-ts/src/AutocratClient";
 import {
   ComputeBudgetInstruction,
   ComputeBudgetProgram,
@@ -169,6 +170,27 @@ describe("autocrat", async function () {
     );
   });
 
+  // stands in for an `update_dao` proposal, which would need to pass first
+  async function overrideDao(fields: object) {
+    const daoAccount = await banksClient.getAccount(dao);
+    const storedDao = autocrat.coder.accounts.decode(
+      "dao",
+      Buffer.from(daoAccount.data)
+    );
+    const data = await autocrat.coder.accounts.encode("dao", {
+      ...storedDao,
+      ...fields,
+    });
+
+    await context.setAccount(dao, {
+      ...daoAccount,
+      data: Buffer.concat([
+        data,
+        Buffer.alloc(daoAccount.data.length - data.length),
+      ]),
+    });
+  }
+
   describe("#initialize_dao", async function () {
     it("initializes the DAO", async function () {
       dao = await autocratClient.initializeDao(META, 400, 5, 5000, USDC);
@@ -186,6 +208,9 @@ describe("autocrat", async function () {
       assert(storedDao.usdcMint.equals(USDC));
       assert.equal(storedDao.proposalCount, 0);
       assert.equal(storedDao.passThresholdBps, 300);
+      assert.isTrue(storedDao.swapFeeBps.eqn(100));
+      assert.isTrue(storedDao.protocolFeeBps.eqn(0));
+      assert.exists(storedDao.twapObservationClampMode.absolute);
 
       treasuryMetaAccount = await createAssociatedTokenAccount(
         banksClient,
@@ -228,9 +253,171 @@ describe("autocrat", async function () {
         mertdDaoTreasury
       );
     });
+
+    it("rejects bps observation clamps that AMMs would reject", async function () {
+      const callbacks = expectError(
+        "InvalidObservationClamp",
+        "initialized a DAO with a bps clamp of more than 100%"
+      );
+
+      await autocratClient
+        .initializeDaoIx(
+          Keypair.generate(),
+          META,
+          {
+            twapInitialObservation: new BN(1_000),
+            twapMaxObservationChangePerUpdate: new BN(20_000),
+            twapObservationClampMode: { bps: {} },
+            minQuoteFutarchicLiquidity: new BN(1),
+            minBaseFutarchicLiquidity: new BN(1),
+            passThresholdBps: null,
+            slotsPerProposal: null,
+            swapFeeBps: null,
+            protocolFeeBps: null,
+            minSettleGraceSlots: null,
+            executionDelaySlots: null,
+            executionWindowSlots: null,
+            withdrawalWindowSlots: null,
+          },
+          USDC
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("rejects withdrawal windows that aren't shorter than a proposal", async function () {
+      const callbacks = expectError(
+        "WithdrawalWindowTooLong",
+        "initialized a DAO whose proposals could be withdrawn after they end"
+      );
+
+      await autocratClient
+        .initializeDaoIx(
+          Keypair.generate(),
+          META,
+          {
+            twapInitialObservation: new BN(1_000),
+            twapMaxObservationChangePerUpdate: new BN(100),
+            twapObservationClampMode: null,
+            minQuoteFutarchicLiquidity: new BN(1),
+            minBaseFutarchicLiquidity: new BN(1),
+            passThresholdBps: null,
+            slotsPerProposal: new BN(1_000),
+            swapFeeBps: null,
+            protocolFeeBps: null,
+            minSettleGraceSlots: null,
+            executionDelaySlots: null,
+            executionWindowSlots: null,
+            withdrawalWindowSlots: new BN(1_000),
+          },
+          USDC
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
   });
 
   describe("#initialize_proposal", async function () {
+    // sets up a proposal's vaults and AMMs by hand, so that tests can create
+    // ones that don't match what the DAO expects
+    async function initializeProposalIxWithAmms(
+      ammParams: {
+        swapFeeBps?: BN;
+        protocolFeeBps?: BN;
+        protocolFeeRecipient?: PublicKey;
+      },
+      baseVaultSettlementOracle: SettlementOracle | null = null
+    ) {
+      const proposalKeypair = Keypair.generate();
+      const proposal = proposalKeypair.publicKey;
+      const storedDao = await autocratClient.getDao(dao);
+
+      await vaultClient
+        .initializeVaultIx(
+          daoTreasury,
+          META,
+          proposal,
+          null,
+          null,
+          baseVaultSettlementOracle
+        )
+        .rpc();
+      await vaultClient.initializeVaultIx(daoTreasury, USDC, proposal).rpc();
+
+      const {
+        baseVault,
+        quoteVault,
+        passBaseMint,
+        passQuoteMint,
+        failBaseMint,
+        failQuoteMint,
+        passAmm,
+        failAmm,
+      } = autocratClient.getProposalPdas(proposal, META, USDC, dao);
+
+      await vaultClient
+        .mintConditionalTokensIx(baseVault, META, ONE_META.muln(5))
+        .rpc();
+      await vaultClient
+        .mintConditionalTokensIx(quoteVault, USDC, ONE_USDC.muln(5000))
+        .rpc();
+
+      for (const [amm, baseMint, quoteMint] of [
+        [passAmm, passBaseMint, passQuoteMint],
+        [failAmm, failBaseMint, failQuoteMint],
+      ]) {
+        await ammClient
+          .createAmmIx(
+            baseMint,
+            quoteMint,
+            storedDao.twapInitialObservation,
+            storedDao.twapMaxObservationChangePerUpdate,
+            proposal,
+            ammParams.swapFeeBps ?? storedDao.swapFeeBps,
+            ammParams.protocolFeeBps ?? storedDao.protocolFeeBps,
+            ammParams.protocolFeeRecipient ?? storedDao.treasury,
+            storedDao.twapObservationClampMode
+          )
+          .rpc();
+        await ammClient
+          .addLiquidityIx(
+            amm,
+            baseMint,
+            quoteMint,
+            ONE_USDC.muln(5000),
+            ONE_META.muln(5),
+            new BN(0)
+          )
+          .rpc();
+      }
+
+      const lpTokens = ONE_USDC.muln(5000).subn(MINIMUM_LIQUIDITY);
+
+      return autocratClient
+        .initializeProposalIx(
+          proposalKeypair,
+          "",
+          [
+            {
+              programId: MEMO_PROGRAM_ID,
+              accounts: [],
+              data: Buffer.from("hello, world"),
+            },
+          ],
+          dao,
+          META,
+          USDC,
+          lpTokens,
+          lpTokens
+        )
+        .preInstructions([
+          await autocrat.account.proposal.createInstruction(
+            proposalKeypair,
+            2500
+          ),
+        ]);
+    }
+
     it("initializes proposals", async function () {
       const accounts = [
         {
@@ -264,7 +451,7 @@ describe("autocrat", async function () {
       await autocratClient.initializeProposal(
         dao,
         "",
-        instruction,
+        [instruction],
         ONE_META.muln(5),
         ONE_USDC.muln(5000)
       );
@@ -279,6 +466,88 @@ describe("autocrat", async function () {
       assert.equal(postMetaBalance, preMetaBalance - BigInt(5 * 10 ** 9));
       assert.equal(postUsdcBalance, preUsdcBalance - BigInt(5000 * 10 ** 6));
     });
+
+    it("rejects proposals without instructions", async function () {
+      const callbacks = expectError(
+        "NoProposalInstructions",
+        "initialized a proposal that does nothing"
+      );
+
+      await autocratClient
+        .initializeProposal(dao, "", [], ONE_META.muln(5), ONE_USDC.muln(5000))
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("rejects AMMs whose swap fee doesn't match the DAO's", async function () {
+      const callbacks = expectError(
+        "InvalidSwapFee",
+        "initialized a proposal whose AMMs charge a different swap fee"
+      );
+      await (await initializeProposalIxWithAmms({ swapFeeBps: new BN(30) }))
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("rejects AMMs whose protocol fees don't go to the treasury", async function () {
+      const callbacks = expectError(
+        "InvalidProtocolFeeRecipient",
+        "initialized a proposal whose AMMs send protocol fees to the proposer"
+      );
+      await (
+        await initializeProposalIxWithAmms({
+          protocolFeeRecipient: payer.publicKey,
+        })
+      )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("rejects settle deadlines that leave no time to finalize", async function () {
+      const { slot } = await banksClient.getClock();
+      const storedDao = await autocratClient.getDao(dao);
+
+      const callbacks = expectError(
+        "SettleDeadlineTooEarly",
+        "initialized a proposal that anyone could fail by reverting its vaults"
+      );
+      await autocratClient
+        .initializeProposal(
+          dao,
+          "",
+          [
+            {
+              programId: MEMO_PROGRAM_ID,
+              accounts: [],
+              data: Buffer.from("hello, world"),
+            },
+          ],
+          ONE_META.muln(5),
+          ONE_USDC.muln(5000),
+          // one slot after the proposal could be finalized
+          new BN((slot + 1n).toString()).add(storedDao.slotsPerProposal)
+        )
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("rejects vaults that anyone could settle from an oracle", async function () {
+      const callbacks = expectError(
+        "InvalidSettlementAuthority",
+        "initialized a proposal whose base vault settles from an oracle"
+      );
+      await (
+        await initializeProposalIxWithAmms(
+          {},
+          {
+            account: Keypair.generate().publicKey,
+            ownerProgram: autocrat.programId,
+            outcomeOffset: 0,
+            resolutions: [{ value: 1, winningOutcome: 0 }],
+          }
+        )
+      )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
   });
 
   describe("#finalize_proposal", async function () {
@@ -323,7 +592,7 @@ describe("autocrat", async function () {
       proposal = await autocratClient.initializeProposal(
         dao,
         "",
-        instruction,
+        [instruction],
         ONE_META.muln(10),
         ONE_USDC.muln(5000)
       );
@@ -495,10 +764,77 @@ describe("autocrat", async function () {
       assert.exists(storedBaseVault.status.reverted);
       assert.exists(storedQuoteVault.status.reverted);
     });
+
+    it("fails proposals whose vaults were reverted after their settle deadline", async function () {
+      const { slot } = await banksClient.getClock();
+      const storedDao = await autocratClient.getDao(dao);
+
+      // the earliest deadline that the DAO allows
+      const settleDeadlineDelay =
+        BigInt(
+          storedDao.slotsPerProposal
+            .add(storedDao.minSettleGraceSlots)
+            .toString()
+        ) + 1n;
+
+      const expiringProposal = await autocratClient.initializeProposal(
+        dao,
+        "",
+        [
+          {
+            programId: MEMO_PROGRAM_ID,
+            accounts: [],
+            data: Buffer.from("hello, world"),
+          },
+        ],
+        ONE_META.muln(10),
+        ONE_USDC.muln(5000),
+        new BN((slot + settleDeadlineDelay).toString())
+      );
+
+      const { baseVault, quoteVault } = autocratClient.getProposalPdas(
+        expiringProposal,
+        META,
+        USDC,
+        dao
+      );
+
+      await advanceBySlots(context, settleDeadlineDelay + 1n);
+
+      // nobody finalized the proposal in time, so someone reverts a vault
+      await vaultClient.revertExpiredVaultIx(baseVault).rpc();
+
+      await autocratClient.finalizeProposal(expiringProposal);
+
+      const storedProposal = await autocratClient.getProposal(expiringProposal);
+      assert.exists(storedProposal.state.failed);
+
+      const storedBaseVault = await vaultClient.getVault(baseVault);
+      const storedQuoteVault = await vaultClient.getVault(quoteVault);
+
+      assert.exists(storedBaseVault.status.reverted);
+      assert.exists(storedQuoteVault.status.reverted);
+    });
   });
 
   describe("#execute_proposal", async function () {
-    let proposal, passAmm, failAmm, baseVault, quoteVault, instruction;
+    let proposal,
+      passAmm,
+      failAmm,
+      baseVault,
+      quoteVault,
+      instruction,
+      executionDelaySlots: BN,
+      executionWindowSlots: BN;
+
+    before(async function () {
+      ({ executionDelaySlots, executionWindowSlots } =
+        await autocratClient.getDao(dao));
+    });
+
+    afterEach(async function () {
+      await overrideDao({ executionDelaySlots, executionWindowSlots });
+    });
 
     beforeEach(async function () {
       await mintToOverride(context, treasuryMetaAccount, 1_000_000_000n);
@@ -513,7 +849,7 @@ describe("autocrat", async function () {
       proposal = await autocratClient.initializeProposal(
         dao,
         "",
-        instruction,
+        [instruction],
         ONE_META.muln(10),
         ONE_USDC.muln(6_000)
       );
@@ -524,66 +860,10 @@ describe("autocrat", async function () {
       await vaultClient.mintConditionalTokens(quoteVault, 10_000);
     });
 
-    it("doesn't allow pending proposals to be executed", async function () {
-      const callbacks = expectError(
-        "ProposalNotPassed",
-        "executed despite proposal still pending"
-      );
-
-      await autocratClient
-        .executeProposal(proposal)
-        .then(callbacks[0], callbacks[1]);
-    });
-
-    it("doesn't allow failed proposals to be executed", async function () {
-      let currentClock = await context.banksClient.getClock();
-      const newSlot = currentClock.slot + 10_000_000n;
-      context.setClock(
-        new Clock(
-          newSlot,
-          currentClock.epochStartTimestamp,
-          currentClock.epoch,
-          currentClock.leaderScheduleEpoch,
-          currentClock.unixTimestamp
-        )
-      );
-
-      await ammClient
-        .crankThatTwapIx(passAmm)
-        .preInstructions([
-          await ammClient.crankThatTwapIx(failAmm).instruction(),
-        ])
-        .rpc();
-
-      await autocratClient.finalizeProposal(proposal);
-
-      assert.exists(
-        (await autocrat.account.proposal.fetch(proposal)).state.failed
-      );
-
-      const callbacks = expectError(
-        "ProposalNotPassed",
-        "executed despite proposal proposal failed"
-      );
-
-      await autocratClient
-        .executeProposal(proposal)
-        .then(callbacks[0], callbacks[1]);
-    });
-
-    it("doesn't allow proposals to be executed twice", async function () {
-      let {
-        passAmm,
-        failAmm,
-        passBaseMint,
-        passQuoteMint,
-        failBaseMint,
-        failQuoteMint,
-        baseVault,
-        quoteVault,
-      } = autocratClient.getProposalPdas(proposal, META, USDC, dao);
+    async function passProposal(proposal: PublicKey) {
+      const { passAmm, failAmm, passBaseMint, passQuoteMint } =
+        autocratClient.getProposalPdas(proposal, META, USDC, dao);
 
-      // swap $500 in the pass market, make it pass
       await ammClient
         .swapIx(
           passAmm,
@@ -611,26 +891,965 @@ describe("autocrat", async function () {
       }
 
       await autocratClient.finalizeProposal(proposal);
+    }
+
+    it("doesn't allow proposals to be executed before the execution delay", async function () {
+      await overrideDao({ executionDelaySlots: new BN(1_000) });
+      await passProposal(proposal);
+      // the delay is fixed when the proposal passes, so lowering it now
+      // doesn't let the proposal skip it
+      await overrideDao({ executionDelaySlots: new BN(0) });
+
+      let storedProposal = await autocratClient.getProposal(proposal);
+      assert.equal(
+        storedProposal.executableAtSlot.toString(),
+        storedProposal.passedAtSlot.addn(1_000).toString()
+      );
 
-      const storedProposal = await autocrat.account.proposal.fetch(proposal);
-      assert.exists(storedProposal.state.passed);
+      const callbacks = expectError(
+        "ExecutionDelayNotElapsed",
+        "executed before the execution delay elapsed"
+      );
 
-      await autocratClient.executeProposal(proposal);
+      await autocratClient
+        .executeProposal(proposal)
+        .then(callbacks[0], callbacks[1]);
+
+      await advanceBySlots(context, 1_000n);
+
+      await autocratClient
+        .executeProposalIx(proposal, dao, storedProposal.instructions)
+        .preInstructions([
+          ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 1 }),
+        ])
+        .rpc();
+
+      storedProposal = await autocratClient.getProposal(proposal);
+      assert.exists(storedProposal.state.executed);
+    });
+
+    it("doesn't allow proposals to be executed after the execution window", async function () {
+      await overrideDao({ executionWindowSlots: new BN(1_000) });
+      await passProposal(proposal);
+
+      const storedProposal = await autocratClient.getProposal(proposal);
+      assert.equal(
+        storedProposal.expiresAtSlot.toString(),
+        storedProposal.executableAtSlot.addn(1_000).toString()
+      );
+
+      await advanceBySlots(context, 1_000n);
 
       const callbacks = expectError(
-        "ProposalNotPassed",
-        "executed despite already being executed"
+        "ProposalExpired",
+        "executed after the execution window"
+      );
+
+      await autocratClient
+        .executeProposal(proposal)
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("expires proposals once the execution window is over", async function () {
+      await overrideDao({ executionWindowSlots: new BN(1_000) });
+      await passProposal(proposal);
+
+      let callbacks = expectError(
+        "ProposalNotExpired",
+        "expired a proposal that could still be executed"
       );
 
       await autocratClient
-        .executeProposalIx(proposal, dao, storedProposal.instruction)
+        .expireProposal(proposal)
+        .then(callbacks[0], callbacks[1]);
+
+      await advanceBySlots(context, 1_000n);
+
+      await autocratClient
+        .expireProposalIx(proposal)
         .preInstructions([
-          // add a pre-instruction so it doesn't think it's already processed it
-          anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
-            units: 100_000,
-          }),
+          ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 1 }),
         ])
-        .rpc()
+        .rpc();
+
+      const storedProposal = await autocratClient.getProposal(proposal);
+      assert.exists(storedProposal.state.expired);
+
+      callbacks = expectError(
+        "ProposalNotPassed",
+        "executed an expired proposal"
+      );
+
+      await autocratClient
+        .executeProposal(proposal)
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("doesn't expire proposals when the execution window is u64::MAX", async function () {
+      await overrideDao({
+        executionDelaySlots: new BN(1_000),
+        executionWindowSlots: new BN("18446744073709551615"),
+      });
+      await passProposal(proposal);
+
+      const storedProposal = await autocratClient.getProposal(proposal);
+      assert.equal(
+        storedProposal.expiresAtSlot.toString(),
+        "18446744073709551615"
+      );
+
+      await advanceBySlots(context, 10_000_000n);
+
+      const callbacks = expectError(
+        "ProposalNotExpired",
+        "expired a proposal that should never expire"
+      );
+
+      await autocratClient
+        .expireProposal(proposal)
+        .then(callbacks[0], callbacks[1]);
+
+      await autocratClient.executeProposal(proposal);
+
+      assert.exists(
+        (await autocratClient.getProposal(proposal)).state.executed
+      );
+    });
+
+    it("executes every instruction in order", async function () {
+      const receiver = Keypair.generate();
+      const staging = await createAccount(
+        banksClient,
+        payer,
+        USDC,
+        daoTreasury,
+        Keypair.generate()
+      );
+      const receiverAccount = await createAccount(
+        banksClient,
+        payer,
+        USDC,
+        receiver.publicKey
+      );
+
+      // the second transfer only works if the first one ran before it
+      const instructions = [
+        token.createTransferInstruction(
+          treasuryUsdcAccount,
+          staging,
+          daoTreasury,
+          100
+        ),
+        token.createTransferInstruction(
+          staging,
+          receiverAccount,
+          daoTreasury,
+          100
+        ),
+      ].map((ix) => ({
+        programId: ix.programId,
+        accounts: ix.keys,
+        data: ix.data,
+      }));
+
+      const orderedProposal = await autocratClient.initializeProposal(
+        dao,
+        "",
+        instructions,
+        ONE_META.muln(10),
+        ONE_USDC.muln(6_000)
+      );
+      const { baseVault, quoteVault } = autocratClient.getProposalPdas(
+        orderedProposal,
+        META,
+        USDC,
+        dao
+      );
+      await vaultClient.mintConditionalTokens(baseVault, 10);
+      await vaultClient.mintConditionalTokens(quoteVault, 10_000);
+
+      await passProposal(orderedProposal);
+
+      await autocratClient.executeProposal(orderedProposal);
+
+      const storedProposal = await autocratClient.getProposal(orderedProposal);
+      assert.exists(storedProposal.state.executed);
+
+      const receiverTokenAccount = await getAccount(
+        banksClient,
+        receiverAccount
+      );
+      assert.equal(receiverTokenAccount.amount, 100n);
+    });
+
+    it("executes instructions across several calls", async function () {
+      const receiver = Keypair.generate();
+      const receiverAccount = await createAccount(
+        banksClient,
+        payer,
+        USDC,
+        receiver.publicKey
+      );
+
+      const instructions = [10, 20, 30].map((amount) => {
+        const ix = token.createTransferInstruction(
+          treasuryUsdcAccount,
+          receiverAccount,
+          daoTreasury,
+          amount
+        );
+        return {
+          programId: ix.programId,
+          accounts: ix.keys,
+          data: ix.data,
+        };
+      });
+
+      const chunkedProposal = await autocratClient.initializeProposal(
+        dao,
+        "",
+        instructions,
+        ONE_META.muln(10),
+        ONE_USDC.muln(6_000)
+      );
+      const { baseVault, quoteVault } = autocratClient.getProposalPdas(
+        chunkedProposal,
+        META,
+        USDC,
+        dao
+      );
+      await vaultClient.mintConditionalTokens(baseVault, 10);
+      await vaultClient.mintConditionalTokens(quoteVault, 10_000);
+
+      await passProposal(chunkedProposal);
+
+      await autocratClient.executeProposal(chunkedProposal, 2);
+
+      let storedProposal = await autocratClient.getProposal(chunkedProposal);
+      assert.exists(storedProposal.state.passed);
+      assert.equal(storedProposal.executedInstructionIndex, 2);
+      let receiverTokenAccount = await getAccount(banksClient, receiverAccount);
+      assert.equal(receiverTokenAccount.amount, 30n);
+
+      // asking for more than what's left just executes the rest
+      await autocratClient
+        .executeProposalIx(
+          chunkedProposal,
+          dao,
+          storedProposal.instructions.slice(2),
+          5
+        )
+        .rpc();
+
+      storedProposal = await autocratClient.getProposal(chunkedProposal);
+      assert.exists(storedProposal.state.executed);
+      assert.equal(storedProposal.executedInstructionIndex, 3);
+      receiverTokenAccount = await getAccount(banksClient, receiverAccount);
+      assert.equal(receiverTokenAccount.amount, 60n);
+    });
+
+    it("executes instructions appended after initialization", async function () {
+      const receiver = Keypair.generate();
+      const receiverAccount = await createAccount(
+        banksClient,
+        payer,
+        USDC,
+        receiver.publicKey
+      );
+
+      const transfers = (amounts: number[]) =>
+        amounts.map((amount) => {
+          const ix = token.createTransferInstruction(
+            treasuryUsdcAccount,
+            receiverAccount,
+            daoTreasury,
+            amount
+          );
+          return {
+            programId: ix.programId,
+            accounts: ix.keys,
+            data: ix.data,
+          };
+        });
+
+      const extendedProposal = await autocratClient.initializeProposal(
+        dao,
+        "",
+        transfers([1]),
+        ONE_META.muln(10),
+        ONE_USDC.muln(6_000)
+      );
+
+      // more than fits in the proposal account as it was created
+      for (let i = 0; i < 4; i++) {
+        await autocratClient.extendProposalInstructions(
+          extendedProposal,
+          transfers([2, 3, 4, 5, 6].map((n) => n + i * 5))
+        );
+      }
+
+      let storedProposal = await autocratClient.getProposal(extendedProposal);
+      assert.equal(storedProposal.instructions.length, 21);
+
+      const { baseVault, quoteVault } = autocratClient.getProposalPdas(
+        extendedProposal,
+        META,
+        USDC,
+        dao
+      );
+      await vaultClient.mintConditionalTokens(baseVault, 10);
+      await vaultClient.mintConditionalTokens(quoteVault, 10_000);
+
+      await passProposal(extendedProposal);
+
+      for (let i = 0; i < 3; i++) {
+        await autocratClient.executeProposal(extendedProposal, 7);
+      }
+
+      storedProposal = await autocratClient.getProposal(extendedProposal);
+      assert.exists(storedProposal.state.executed);
+
+      // 1 + 2 + ... + 21
+      const receiverTokenAccount = await getAccount(
+        banksClient,
+        receiverAccount
+      );
+      assert.equal(receiverTokenAccount.amount, 231n);
+    });
+
+    it("doesn't allow appending instructions once the markets are used", async function () {
+      const { passBaseMint, passQuoteMint } = autocratClient.getProposalPdas(
+        proposal,
+        META,
+        USDC,
+        dao
+      );
+
+      await ammClient
+        .swapIx(
+          passAmm,
+          passBaseMint,
+          passQuoteMint,
+          { buy: {} },
+          new BN(10).muln(1_000_000),
+          new BN(0)
+        )
+        .rpc();
+
+      const callbacks = expectError(
+        "ProposalMarketsAlreadyActive",
+        "appended instructions after a swap"
+      );
+
+      await autocratClient
+        .extendProposalInstructions(proposal, [instruction])
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("doesn't allow executing zero instructions", async function () {
+      await passProposal(proposal);
+
+      const storedProposal = await autocratClient.getProposal(proposal);
+
+      const callbacks = expectError(
+        "ZeroInstructionsToExecute",
+        "executed zero instructions"
+      );
+
+      await autocratClient
+        .executeProposalIx(proposal, dao, storedProposal.instructions, 0)
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("doesn't allow pending proposals to be executed", async function () {
+      const callbacks = expectError(
+        "ProposalNotPassed",
+        "executed despite proposal still pending"
+      );
+
+      await autocratClient
+        .executeProposal(proposal)
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("doesn't allow failed proposals to be executed", async function () {
+      let currentClock = await context.banksClient.getClock();
+      const newSlot = currentClock.slot + 10_000_000n;
+      context.setClock(
+        new Clock(
+          newSlot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          currentClock.unixTimestamp
+        )
+      );
+
+      await ammClient
+        .crankThatTwapIx(passAmm)
+        .preInstructions([
+          await ammClient.crankThatTwapIx(failAmm).instruction(),
+        ])
+        .rpc();
+
+      await autocratClient.finalizeProposal(proposal);
+
+      assert.exists(
+        (await autocrat.account.proposal.fetch(proposal)).state.failed
+      );
+
+      const callbacks = expectError(
+        "ProposalNotPassed",
+        "executed despite proposal proposal failed"
+      );
+
+      await autocratClient
+        .executeProposal(proposal)
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("doesn't allow proposals to be executed twice", async function () {
+      let {
+        passAmm,
+        failAmm,
+        passBaseMint,
+        passQuoteMint,
+        failBaseMint,
+        failQuoteMint,
+        baseVault,
+        quoteVault,
+      } = autocratClient.getProposalPdas(proposal, META, USDC, dao);
+
+      // swap $500 in the pass market, make it pass
+      await ammClient
+        .swapIx(
+          passAmm,
+          passBaseMint,
+          passQuoteMint,
+          { buy: {} },
+          new BN(1000).muln(1_000_000),
+          new BN(0)
+        )
+        .rpc();
+
+      for (let i = 0; i < 50; i++) {
+        await advanceBySlots(context, 20_000n);
+
+        await ammClient
+          .crankThatTwapIx(passAmm)
+          .preInstructions([
+            // this is to get around bankrun thinking we've processed the same transaction multiple times
+            ComputeBudgetProgram.setComputeUnitPrice({
+              microLamports: i,
+            }),
+            await ammClient.crankThatTwapIx(failAmm).instruction(),
+          ])
+          .rpc();
+      }
+
+      await autocratClient.finalizeProposal(proposal);
+
+      const storedProposal = await autocrat.account.proposal.fetch(proposal);
+      assert.exists(storedProposal.state.passed);
+
+      await autocratClient.executeProposal(proposal);
+
+      const callbacks = expectError(
+        "ProposalNotPassed",
+        "executed despite already being executed"
+      );
+
+      await autocratClient
+        .executeProposalIx(proposal, dao, storedProposal.instructions)
+        .preInstructions([
+          // add a pre-instruction so it doesn't think it's already processed it
+          anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+            units: 100_000,
+          }),
+        ])
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("doesn't let proposals give the DAO an invalid observation clamp", async function () {
+      const ix = await autocrat.methods
+        .updateDao({
+          passThresholdBps: null,
+          slotsPerProposal: null,
+          twapInitialObservation: null,
+          twapMaxObservationChangePerUpdate: new BN(0),
+          twapObservationClampMode: { bps: {} },
+          swapFeeBps: null,
+          protocolFeeBps: null,
+          minSettleGraceSlots: null,
+          executionDelaySlots: null,
+          executionWindowSlots: null,
+          withdrawalWindowSlots: null,
+        })
+        .accounts({ dao, treasury: daoTreasury })
+        .instruction();
+
+      const updateProposal = await autocratClient.initializeProposal(
+        dao,
+        "",
+        [{ programId: ix.programId, accounts: ix.keys, data: ix.data }],
+        ONE_META.muln(10),
+        ONE_USDC.muln(6_000)
+      );
+      const { baseVault, quoteVault } = autocratClient.getProposalPdas(
+        updateProposal,
+        META,
+        USDC,
+        dao
+      );
+      await vaultClient.mintConditionalTokens(baseVault, 10);
+      await vaultClient.mintConditionalTokens(quoteVault, 10_000);
+
+      await passProposal(updateProposal);
+
+      const callbacks = expectError(
+        "InvalidObservationClamp",
+        "set a bps clamp that doesn't let the observation move"
+      );
+
+      await autocratClient
+        .executeProposal(updateProposal)
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("doesn't let proposals shorten themselves below the withdrawal window", async function () {
+      const { withdrawalWindowSlots } = await autocratClient.getDao(dao);
+
+      const ix = await autocrat.methods
+        .updateDao({
+          passThresholdBps: null,
+          slotsPerProposal: withdrawalWindowSlots,
+          twapInitialObservation: null,
+          twapMaxObservationChangePerUpdate: null,
+          twapObservationClampMode: null,
+          swapFeeBps: null,
+          protocolFeeBps: null,
+          minSettleGraceSlots: null,
+          executionDelaySlots: null,
+          executionWindowSlots: null,
+          withdrawalWindowSlots: null,
+        })
+        .accounts({ dao, treasury: daoTreasury })
+        .instruction();
+
+      const updateProposal = await autocratClient.initializeProposal(
+        dao,
+        "",
+        [{ programId: ix.programId, accounts: ix.keys, data: ix.data }],
+        ONE_META.muln(10),
+        ONE_USDC.muln(6_000)
+      );
+      const { baseVault, quoteVault } = autocratClient.getProposalPdas(
+        updateProposal,
+        META,
+        USDC,
+        dao
+      );
+      await vaultClient.mintConditionalTokens(baseVault, 10);
+      await vaultClient.mintConditionalTokens(quoteVault, 10_000);
+
+      await passProposal(updateProposal);
+
+      const callbacks = expectError(
+        "WithdrawalWindowTooLong",
+        "set a proposal length that isn't longer than the withdrawal window"
+      );
+
+      await autocratClient
+        .executeProposal(updateProposal)
+        .then(callbacks[0], callbacks[1]);
+    });
+  });
+
+  describe("#withdraw_proposal", async function () {
+    let proposal: PublicKey;
+
+    beforeEach(async function () {
+      proposal = await autocratClient.initializeProposal(
+        dao,
+        "",
+        [
+          {
+            programId: MEMO_PROGRAM_ID,
+            accounts: [],
+            data: Buffer.from("hello, world"),
+          },
+        ],
+        ONE_META.muln(10),
+        ONE_USDC.muln(6_000)
+      );
+    });
+
+    it("lets the proposer withdraw their proposal", async function () {
+      const { baseVault, quoteVault, passLp, failLp } =
+        autocratClient.getProposalPdas(proposal, META, USDC, dao);
+      const passLpAccount = getATA(passLp, payer.publicKey)[0];
+      const failLpAccount = getATA(failLp, payer.publicKey)[0];
+
+      const prePassLpBalance = (await getAccount(banksClient, passLpAccount))
+        .amount;
+      const preFailLpBalance = (await getAccount(banksClient, failLpAccount))
+        .amount;
+
+      await autocratClient.withdrawProposal(proposal);
+
+      const storedProposal = await autocratClient.getProposal(proposal);
+      assert.exists(storedProposal.state.withdrawn);
+
+      const storedBaseVault = await vaultClient.getVault(baseVault);
+      const storedQuoteVault = await vaultClient.getVault(quoteVault);
+      assert.exists(storedBaseVault.status.reverted);
+      assert.exists(storedQuoteVault.status.reverted);
+
+      const postPassLpBalance = (await getAccount(banksClient, passLpAccount))
+        .amount;
+      const postFailLpBalance = (await getAccount(banksClient, failLpAccount))
+        .amount;
+      assert.equal(
+        postPassLpBalance - prePassLpBalance,
+        BigInt(storedProposal.passLpTokensLocked.toString())
+      );
+      assert.equal(
+        postFailLpBalance - preFailLpBalance,
+        BigInt(storedProposal.failLpTokensLocked.toString())
+      );
+    });
+
+    it("doesn't allow proposals to be withdrawn after the withdrawal window", async function () {
+      const { withdrawalWindowSlots } = await autocratClient.getDao(dao);
+
+      await advanceBySlots(context, BigInt(withdrawalWindowSlots.toString()));
+
+      const callbacks = expectError(
+        "ProposalWithdrawalWindowOver",
+        "withdrew a proposal after the withdrawal window"
+      );
+
+      await autocratClient
+        .withdrawProposal(proposal)
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("uses the DAO's withdrawal window", async function () {
+      const { withdrawalWindowSlots } = await autocratClient.getDao(dao);
+
+      await overrideDao({ withdrawalWindowSlots: new BN(0) });
+
+      const callbacks = expectError(
+        "ProposalWithdrawalWindowOver",
+        "withdrew a proposal when the DAO doesn't allow withdrawals"
+      );
+
+      await autocratClient
+        .withdrawProposal(proposal)
+        .then(callbacks[0], callbacks[1]);
+
+      await overrideDao({ withdrawalWindowSlots });
+    });
+  });
+
+  describe("#split_and_swap", async function () {
+    let proposal: PublicKey,
+      baseVault: PublicKey,
+      quoteVault: PublicKey,
+      passAmm: PublicKey,
+      passBaseMint: PublicKey,
+      passQuoteMint: PublicKey,
+      failQuoteMint: PublicKey;
+
+    beforeEach(async function () {
+      proposal = await autocratClient.initializeProposal(
+        dao,
+        "",
+        [
+          {
+            programId: MEMO_PROGRAM_ID,
+            accounts: [],
+            data: Buffer.from("hello, world"),
+          },
+        ],
+        ONE_META.muln(10),
+        ONE_USDC.muln(10_000)
+      );
+
+      ({
+        baseVault,
+        quoteVault,
+        passAmm,
+        passBaseMint,
+        passQuoteMint,
+        failQuoteMint,
+      } = autocratClient.getProposalPdas(proposal, META, USDC, dao));
+    });
+
+    it("buys pass META with USDC in one instruction", async function () {
+      const usdcStart = await getAccount(
+        banksClient,
+        getATA(USDC, payer.publicKey)[0]
+      );
+      const pMetaStart = await getAccount(
+        banksClient,
+        getATA(passBaseMint, payer.publicKey)[0]
+      );
+      const pUsdcStart = await getAccount(
+        banksClient,
+        getATA(passQuoteMint, payer.publicKey)[0]
+      );
+      const fUsdcStart = await getAccount(
+        banksClient,
+        getATA(failQuoteMint, payer.publicKey)[0]
+      );
+
+      await autocratClient.splitAndSwap(
+        proposal,
+        quoteVault,
+        passAmm,
+        ONE_USDC.muln(100),
+        new BN(1)
+      );
+
+      const usdcEnd = await getAccount(
+        banksClient,
+        getATA(USDC, payer.publicKey)[0]
+      );
+      const pMetaEnd = await getAccount(
+        banksClient,
+        getATA(passBaseMint, payer.publicKey)[0]
+      );
+      const pUsdcEnd = await getAccount(
+        banksClient,
+        getATA(passQuoteMint, payer.publicKey)[0]
+      );
+      const fUsdcEnd = await getAccount(
+        banksClient,
+        getATA(failQuoteMint, payer.publicKey)[0]
+      );
+
+      assert.equal(usdcStart.amount - usdcEnd.amount, 100n * 1_000_000n);
+      assert.isTrue(pMetaEnd.amount > pMetaStart.amount);
+      // all of the pUSDC gets swapped, and the fUSDC stays with the user
+      assert.equal(pUsdcEnd.amount, pUsdcStart.amount);
+      assert.equal(fUsdcEnd.amount - fUsdcStart.amount, 100n * 1_000_000n);
+    });
+
+    it("sells fail META for fail USDC", async function () {
+      const { failAmm, failBaseMint } = autocratClient.getProposalPdas(
+        proposal,
+        META,
+        USDC,
+        dao
+      );
+
+      const fUsdcStart = await getAccount(
+        banksClient,
+        getATA(failQuoteMint, payer.publicKey)[0]
+      );
+      const fMetaStart = await getAccount(
+        banksClient,
+        getATA(failBaseMint, payer.publicKey)[0]
+      );
+
+      await autocratClient.splitAndSwap(
+        proposal,
+        baseVault,
+        failAmm,
+        ONE_META,
+        new BN(1)
+      );
+
+      const fUsdcEnd = await getAccount(
+        banksClient,
+        getATA(failQuoteMint, payer.publicKey)[0]
+      );
+      const fMetaEnd = await getAccount(
+        banksClient,
+        getATA(failBaseMint, payer.publicKey)[0]
+      );
+
+      assert.isTrue(fUsdcEnd.amount > fUsdcStart.amount);
+      assert.equal(fMetaEnd.amount, fMetaStart.amount);
+    });
+
+    it("reverts when the route gives less than the minimum output", async function () {
+      const callbacks = expectError(
+        "RouteSlippageExceeded",
+        "split and swapped despite getting less than the minimum output"
+      );
+
+      await autocratClient
+        .splitAndSwap(
+          proposal,
+          quoteVault,
+          passAmm,
+          ONE_USDC.muln(100),
+          ONE_META.muln(100)
+        )
+        .then(callbacks[0], callbacks[1]);
+    });
+  });
+
+  describe("#merge_and_exit", async function () {
+    let proposal: PublicKey,
+      quoteVault: PublicKey,
+      passAmm: PublicKey,
+      passBaseMint: PublicKey,
+      passQuoteMint: PublicKey,
+      failQuoteMint: PublicKey;
+
+    beforeEach(async function () {
+      proposal = await autocratClient.initializeProposal(
+        dao,
+        "",
+        [
+          {
+            programId: MEMO_PROGRAM_ID,
+            accounts: [],
+            data: Buffer.from("hello, world"),
+          },
+        ],
+        ONE_META.muln(10),
+        ONE_USDC.muln(10_000)
+      );
+
+      ({ quoteVault, passAmm, passBaseMint, passQuoteMint, failQuoteMint } =
+        autocratClient.getProposalPdas(proposal, META, USDC, dao));
+
+      // leaves the user with pass META and fail USDC, plus a pair of
+      // conditional USDC that the exit shouldn't touch
+      await autocratClient.splitAndSwap(
+        proposal,
+        quoteVault,
+        passAmm,
+        ONE_USDC.muln(100),
+        new BN(1)
+      );
+      await vaultClient.mintConditionalTokens(quoteVault, 10);
+    });
+
+    it("swaps back and merges only what the swap produced", async function () {
+      const pMetaStart = await getAccount(
+        banksClient,
+        getATA(passBaseMint, payer.publicKey)[0]
+      );
+      const pUsdcStart = await getAccount(
+        banksClient,
+        getATA(passQuoteMint, payer.publicKey)[0]
+      );
+      const fUsdcStart = await getAccount(
+        banksClient,
+        getATA(failQuoteMint, payer.publicKey)[0]
+      );
+      const usdcStart = await getAccount(
+        banksClient,
+        getATA(USDC, payer.publicKey)[0]
+      );
+
+      await autocratClient.mergeAndExit(
+        proposal,
+        quoteVault,
+        passAmm,
+        new BN(pMetaStart.amount.toString()),
+        new BN(0),
+        new BN(1)
+      );
+
+      const pMetaEnd = await getAccount(
+        banksClient,
+        getATA(passBaseMint, payer.publicKey)[0]
+      );
+      const pUsdcEnd = await getAccount(
+        banksClient,
+        getATA(passQuoteMint, payer.publicKey)[0]
+      );
+      const fUsdcEnd = await getAccount(
+        banksClient,
+        getATA(failQuoteMint, payer.publicKey)[0]
+      );
+      const usdcEnd = await getAccount(
+        banksClient,
+        getATA(USDC, payer.publicKey)[0]
+      );
+
+      const merged = usdcEnd.amount - usdcStart.amount;
+
+      assert.equal(pMetaEnd.amount, 0n);
+      assert.isTrue(merged > 0n);
+      assert.isTrue(merged < 100n * 1_000_000n);
+      assert.equal(pUsdcEnd.amount, pUsdcStart.amount);
+      assert.equal(fUsdcStart.amount - fUsdcEnd.amount, merged);
+    });
+
+    it("merges pairs the user already holds without swapping", async function () {
+      const usdcStart = await getAccount(
+        banksClient,
+        getATA(USDC, payer.publicKey)[0]
+      );
+      const pUsdcStart = await getAccount(
+        banksClient,
+        getATA(passQuoteMint, payer.publicKey)[0]
+      );
+
+      await autocratClient.mergeAndExit(
+        proposal,
+        quoteVault,
+        passAmm,
+        new BN(0),
+        ONE_USDC.muln(5),
+        ONE_USDC.muln(5)
+      );
+
+      const usdcEnd = await getAccount(
+        banksClient,
+        getATA(USDC, payer.publicKey)[0]
+      );
+      const pUsdcEnd = await getAccount(
+        banksClient,
+        getATA(passQuoteMint, payer.publicKey)[0]
+      );
+
+      assert.equal(usdcEnd.amount - usdcStart.amount, 5n * 1_000_000n);
+      assert.equal(pUsdcStart.amount - pUsdcEnd.amount, 5n * 1_000_000n);
+    });
+
+    it("can't merge nothing", async function () {
+      const callbacks = expectError(
+        "ZeroRouteAmount",
+        "merged and exited without merging anything"
+      );
+
+      await autocratClient
+        .mergeAndExit(
+          proposal,
+          quoteVault,
+          passAmm,
+          new BN(0),
+          new BN(0),
+          new BN(0)
+        )
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("reverts when the exit gives less than the minimum underlying", async function () {
+      const callbacks = expectError(
+        "RouteSlippageExceeded",
+        "merged and exited despite getting less than the minimum underlying"
+      );
+
+      await autocratClient
+        .mergeAndExit(
+          proposal,
+          quoteVault,
+          passAmm,
+          new BN(0),
+          ONE_USDC,
+          ONE_USDC.muln(2)
+        )
         .then(callbacks[0], callbacks[1]);
     });
   });
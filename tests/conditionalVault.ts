@@ -18,7 +18,11 @@ import {
   toWeb3JsPublicKey,
 } from "@metaplex-foundation/umi-web3js-adapters";
 import * as token from "@solana/spl-token";
-import { SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import {
+  SYSVAR_RENT_PUBKEY,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
 import { BankrunProvider } from "anchor-bankrun";
 import { assert } from "chai";
 import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
@@ -33,11 +37,13 @@ import {
 const { PublicKey, Keypair } = web3;
 
 import { ConditionalVault } from "../target/types/conditional_vault";
-import { expectError } from "./utils/utils";
+import { advanceBySlots, expectError } from "./utils/utils";
 import {
   CONDITIONAL_VAULT_PROGRAM_ID,
   getVaultAddr,
+  getATA,
   getVaultFinalizeMintAddr,
+  getVaultOutcomeMintAddr,
   getVaultRevertMintAddr,
 } from "../futarchy-ts/src";
 import { ConditionalVaultClient } from "../futarchy-ts/src/ConditionalVaultClient";
@@ -554,6 +560,139 @@ describe("conditional_vault", async function () {
     });
   });
 
+  describe("#revert_expired_vault", async function () {
+    let expiringSettlementAuthority: Keypair;
+    let expiringUnderlyingMint: PublicKey;
+
+    beforeEach(async function () {
+      expiringSettlementAuthority = Keypair.generate();
+      expiringUnderlyingMint = await createMint(
+        banksClient,
+        payer,
+        payer.publicKey,
+        null,
+        8
+      );
+    });
+
+    async function initializeExpiringVault(numOutcomes?: number) {
+      const expiringProposal = Keypair.generate().publicKey;
+      const [expiringVault] = getVaultAddr(
+        vaultProgram.programId,
+        expiringSettlementAuthority.publicKey,
+        expiringUnderlyingMint,
+        expiringProposal
+      );
+
+      const { slot } = await banksClient.getClock();
+      const settleDeadline = new BN((slot + 100n).toString());
+
+      if (numOutcomes) {
+        await vaultClient
+          .initializeMultiOutcomeVaultIx(
+            expiringSettlementAuthority.publicKey,
+            expiringUnderlyingMint,
+            expiringProposal,
+            numOutcomes,
+            settleDeadline
+          )
+          .rpc();
+      } else {
+        await vaultClient
+          .initializeVaultIx(
+            expiringSettlementAuthority.publicKey,
+            expiringUnderlyingMint,
+            expiringProposal,
+            settleDeadline
+          )
+          .rpc();
+      }
+
+      return expiringVault;
+    }
+
+    it("reverts vaults once their settle deadline has passed", async function () {
+      const expiringVault = await initializeExpiringVault();
+
+      const callbacks = expectError(
+        "SettleDeadlineNotReached",
+        "reverted a vault before its settle deadline"
+      );
+      await vaultClient
+        .revertExpiredVaultIx(expiringVault)
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+
+      await advanceBySlots(context, 101n);
+
+      await vaultClient.revertExpiredVaultIx(expiringVault).rpc();
+
+      const storedVault = await vaultClient.getVault(expiringVault);
+      assert.exists(storedVault.status.reverted);
+    });
+
+    it("pays every outcome equally when a multi-outcome vault expires", async function () {
+      const expiringVault = await initializeExpiringVault(3);
+
+      await advanceBySlots(context, 101n);
+
+      await vaultClient.revertExpiredVaultIx(expiringVault).rpc();
+
+      const storedVault = await vaultClient.getVault(expiringVault);
+      assert.exists(storedVault.status.settled);
+      assert.deepEqual(
+        storedVault.payoutNumerators.map((numerator) => numerator.toNumber()),
+        [1, 1, 1]
+      );
+      assert.equal(storedVault.payoutDenominator.toNumber(), 3);
+    });
+
+    it("doesn't revert vaults that were already settled", async function () {
+      const expiringVault = await initializeExpiringVault();
+
+      await vaultProgram.methods
+        .settleConditionalVault({ finalized: {} })
+        .accounts({
+          settlementAuthority: expiringSettlementAuthority.publicKey,
+          vault: expiringVault,
+        })
+        .signers([expiringSettlementAuthority])
+        .rpc();
+
+      await advanceBySlots(context, 101n);
+
+      const callbacks = expectError(
+        "VaultAlreadySettled",
+        "reverted a vault that was already finalized"
+      );
+      await vaultClient
+        .revertExpiredVaultIx(expiringVault)
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("doesn't revert vaults without a settle deadline", async function () {
+      let [vault] = await generateRandomVault(
+        vaultProgram,
+        vaultClient,
+        payer,
+        banksClient,
+        umi
+      );
+
+      await advanceBySlots(context, 1_000n);
+
+      const callbacks = expectError(
+        "SettleDeadlineNotReached",
+        "reverted a vault that has no settle deadline"
+      );
+      await vaultClient
+        .revertExpiredVaultIx(vault)
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+  });
+
   describe("#redeem_and_merge_conditional_tokens_for_underlying_tokens", async function () {
     let bob: Keypair;
     let amount = 1000;
@@ -715,6 +854,231 @@ describe("conditional_vault", async function () {
       );
     });
 
+    it("pays out scalar settlements pro rata, rounding down", async function () {
+      await vaultClient
+        .settleConditionalVaultWithPayoutsIx(
+          vault,
+          [new BN(1), new BN(2)],
+          settlementAuthority.publicKey
+        )
+        .signers([settlementAuthority])
+        .rpc();
+
+      await vaultProgram.methods
+        .redeemConditionalTokensForUnderlyingTokens()
+        .accounts({
+          authority: bob.publicKey,
+          userConditionalOnFinalizeTokenAccount:
+            bobConditionalOnFinalizeTokenAccount,
+          userConditionalOnRevertTokenAccount:
+            bobConditionalOnRevertTokenAccount,
+          userUnderlyingTokenAccount: bobUnderlyingTokenAccount,
+          vaultUnderlyingTokenAccount,
+          vault,
+          conditionalOnFinalizeTokenMint: conditionalOnFinalizeMint,
+          conditionalOnRevertTokenMint: conditionalOnRevertMint,
+          tokenProgram: token.TOKEN_PROGRAM_ID,
+        })
+        .signers([bob])
+        .rpc();
+
+      // 1000 * 1/3 + 1000 * 2/3, with each side rounded down
+      const bobUnderlyingAccount = await getAccount(
+        banksClient,
+        bobUnderlyingTokenAccount
+      );
+      assert.equal(bobUnderlyingAccount.amount, 999n);
+
+      const vaultUnderlyingAccount = await getAccount(
+        banksClient,
+        vaultUnderlyingTokenAccount
+      );
+      assert.equal(vaultUnderlyingAccount.amount, 1n);
+    });
+
+    it("redeems part of one side's conditional tokens", async function () {
+      await vaultProgram.methods
+        .settleConditionalVault({ finalized: {} })
+        .accounts({
+          settlementAuthority: settlementAuthority.publicKey,
+          vault,
+        })
+        .signers([settlementAuthority])
+        .rpc();
+
+      await vaultClient
+        .redeemPartialConditionalTokensIx(
+          vault,
+          underlyingTokenMint,
+          conditionalOnFinalizeMint,
+          0,
+          new BN(400),
+          bob.publicKey
+        )
+        .signers([bob])
+        .rpc();
+
+      const bobUnderlyingAccount = await getAccount(
+        banksClient,
+        bobUnderlyingTokenAccount
+      );
+      assert.equal(bobUnderlyingAccount.amount, 400n);
+
+      const bobConditionalOnFinalizeAccount = await getAccount(
+        banksClient,
+        bobConditionalOnFinalizeTokenAccount
+      );
+      assert.equal(bobConditionalOnFinalizeAccount.amount, 600n);
+
+      const bobConditionalOnRevertAccount = await getAccount(
+        banksClient,
+        bobConditionalOnRevertTokenAccount
+      );
+      assert.equal(bobConditionalOnRevertAccount.amount, 1_000n);
+    });
+
+    it("mints conditional tokens into another user's accounts", async function () {
+      const recipient = Keypair.generate();
+
+      await mintTo(
+        banksClient,
+        payer,
+        underlyingTokenMint,
+        bobUnderlyingTokenAccount,
+        underlyingMintAuthority,
+        100
+      );
+
+      await vaultClient
+        .mintConditionalTokensToRecipientIx(
+          vault,
+          underlyingTokenMint,
+          new BN(100),
+          recipient.publicKey,
+          bob.publicKey
+        )
+        .signers([bob])
+        .rpc();
+
+      for (const mint of [conditionalOnFinalizeMint, conditionalOnRevertMint]) {
+        const recipientAccount = await getAccount(
+          banksClient,
+          getATA(mint, recipient.publicKey)[0]
+        );
+        assert.equal(recipientAccount.amount, 100n);
+      }
+
+      const bobUnderlyingAccount = await getAccount(
+        banksClient,
+        bobUnderlyingTokenAccount
+      );
+      assert.equal(bobUnderlyingAccount.amount, 0n);
+    });
+
+    it("merges conditional tokens into another user's account", async function () {
+      const holder = Keypair.generate();
+      const recipient = Keypair.generate();
+
+      await mintTo(
+        banksClient,
+        payer,
+        underlyingTokenMint,
+        bobUnderlyingTokenAccount,
+        underlyingMintAuthority,
+        100
+      );
+
+      await vaultClient
+        .mintConditionalTokensToRecipientIx(
+          vault,
+          underlyingTokenMint,
+          new BN(100),
+          holder.publicKey,
+          bob.publicKey
+        )
+        .signers([bob])
+        .rpc();
+
+      await vaultClient
+        .mergeConditionalTokensToRecipientIx(
+          vault,
+          underlyingTokenMint,
+          new BN(mergeAmount),
+          recipient.publicKey,
+          holder.publicKey
+        )
+        .signers([holder])
+        .rpc();
+
+      const recipientUnderlyingAccount = await getAccount(
+        banksClient,
+        getATA(underlyingTokenMint, recipient.publicKey)[0]
+      );
+      assert.equal(recipientUnderlyingAccount.amount, 10n);
+
+      const holderConditionalOnFinalizeAccount = await getAccount(
+        banksClient,
+        getATA(conditionalOnFinalizeMint, holder.publicKey)[0]
+      );
+      assert.equal(holderConditionalOnFinalizeAccount.amount, 90n);
+    });
+
+    it("redeems conditional tokens into another user's account", async function () {
+      const holder = Keypair.generate();
+      const recipient = Keypair.generate();
+
+      await mintTo(
+        banksClient,
+        payer,
+        underlyingTokenMint,
+        bobUnderlyingTokenAccount,
+        underlyingMintAuthority,
+        100
+      );
+
+      await vaultClient
+        .mintConditionalTokensToRecipientIx(
+          vault,
+          underlyingTokenMint,
+          new BN(100),
+          holder.publicKey,
+          bob.publicKey
+        )
+        .signers([bob])
+        .rpc();
+
+      await vaultProgram.methods
+        .settleConditionalVault({ reverted: {} })
+        .accounts({
+          settlementAuthority: settlementAuthority.publicKey,
+          vault,
+        })
+        .signers([settlementAuthority])
+        .rpc();
+
+      await vaultClient
+        .redeemConditionalTokensToRecipientIx(
+          vault,
+          underlyingTokenMint,
+          recipient.publicKey,
+          holder.publicKey
+        )
+        .signers([holder])
+        .rpc();
+
+      const recipientUnderlyingAccount = await getAccount(
+        banksClient,
+        getATA(underlyingTokenMint, recipient.publicKey)[0]
+      );
+      assert.equal(recipientUnderlyingAccount.amount, 100n);
+
+      const holderConditionalOnRevertAccount = await getAccount(
+        banksClient,
+        getATA(conditionalOnRevertMint, holder.publicKey)[0]
+      );
+      assert.equal(holderConditionalOnRevertAccount.amount, 0n);
+    });
+
     it("prevents users from redeeming conditional tokens while a vault is still active", async function () {
       const callbacks = expectError(
         "CantRedeemConditionalTokens",
@@ -768,6 +1132,883 @@ describe("conditional_vault", async function () {
       ).then(callbacks[0], callbacks[1]);
     });
   });
+
+  describe("#multi_outcome_vaults", async function () {
+    let multiVault: PublicKey,
+      multiUnderlyingMint: PublicKey,
+      multiSettlementAuthority: Keypair;
+
+    beforeEach(async function () {
+      multiSettlementAuthority = Keypair.generate();
+      multiUnderlyingMint = await createMint(
+        banksClient,
+        payer,
+        payer.publicKey,
+        null,
+        8
+      );
+      const userUnderlyingTokenAccount = await createAssociatedTokenAccount(
+        banksClient,
+        payer,
+        multiUnderlyingMint,
+        payer.publicKey
+      );
+      await mintTo(
+        banksClient,
+        payer,
+        multiUnderlyingMint,
+        userUnderlyingTokenAccount,
+        payer,
+        1_000
+      );
+
+      const multiProposal = Keypair.generate().publicKey;
+      [multiVault] = getVaultAddr(
+        vaultProgram.programId,
+        multiSettlementAuthority.publicKey,
+        multiUnderlyingMint,
+        multiProposal
+      );
+
+      await vaultClient
+        .initializeMultiOutcomeVaultIx(
+          multiSettlementAuthority.publicKey,
+          multiUnderlyingMint,
+          multiProposal,
+          3
+        )
+        .rpc();
+    });
+
+    it("initializes a vault with one mint per outcome", async function () {
+      const storedVault = await vaultClient.getVault(multiVault);
+
+      assert.equal(storedVault.outcomeMints.length, 3);
+      assert.deepEqual(
+        storedVault.outcomeMints.map((mint) => mint.toBase58()),
+        vaultClient
+          .getOutcomeMints(multiVault, 3)
+          .map((mint) => mint.toBase58())
+      );
+      assert.ok(
+        storedVault.conditionalOnFinalizeTokenMint.equals(PublicKey.default)
+      );
+    });
+
+    it("initializes even if an outcome mint's address was funded first", async function () {
+      const proposal = Keypair.generate().publicKey;
+      const [vault] = getVaultAddr(
+        vaultProgram.programId,
+        multiSettlementAuthority.publicKey,
+        multiUnderlyingMint,
+        proposal
+      );
+      const [outcomeMint] = getVaultOutcomeMintAddr(
+        vaultProgram.programId,
+        vault,
+        1
+      );
+
+      let tx = new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: payer.publicKey,
+          toPubkey: outcomeMint,
+          lamports: 1_000_000,
+        })
+      );
+      [tx.recentBlockhash] = await banksClient.getLatestBlockhash();
+      tx.feePayer = payer.publicKey;
+      tx.sign(payer);
+      await banksClient.processTransaction(tx);
+
+      await vaultClient
+        .initializeMultiOutcomeVaultIx(
+          multiSettlementAuthority.publicKey,
+          multiUnderlyingMint,
+          proposal,
+          3
+        )
+        .rpc();
+
+      const storedVault = await vaultClient.getVault(vault);
+      assert.equal(storedVault.outcomeMints.length, 3);
+    });
+
+    it("mints, merges, and redeems outcome tokens", async function () {
+      const outcomeAccounts = vaultClient
+        .getOutcomeMints(multiVault, 3)
+        .map((mint) => getATA(mint, payer.publicKey)[0]);
+      const userUnderlyingTokenAccount = getATA(
+        multiUnderlyingMint,
+        payer.publicKey
+      )[0];
+
+      await vaultClient
+        .mintMultiOutcomeTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          3,
+          new BN(100)
+        )
+        .rpc();
+
+      for (const account of outcomeAccounts) {
+        const outcomeAccount = await getAccount(banksClient, account);
+        assert.equal(outcomeAccount.amount, 100n);
+      }
+
+      await vaultClient
+        .mergeMultiOutcomeTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          3,
+          new BN(40)
+        )
+        .rpc();
+
+      for (const account of outcomeAccounts) {
+        const outcomeAccount = await getAccount(banksClient, account);
+        assert.equal(outcomeAccount.amount, 60n);
+      }
+
+      await vaultClient
+        .settleMultiOutcomeVaultIx(
+          multiVault,
+          1,
+          multiSettlementAuthority.publicKey
+        )
+        .signers([multiSettlementAuthority])
+        .rpc();
+
+      await vaultClient
+        .redeemMultiOutcomeTokensIx(multiVault, multiUnderlyingMint, 3)
+        .rpc();
+
+      for (const account of outcomeAccounts) {
+        const outcomeAccount = await getAccount(banksClient, account);
+        assert.equal(outcomeAccount.amount, 0n);
+      }
+
+      // 40 merged back, then the 60 winning tokens redeemed
+      const underlyingAccount = await getAccount(
+        banksClient,
+        userUnderlyingTokenAccount
+      );
+      assert.equal(underlyingAccount.amount, 1_000n);
+    });
+
+    it("redeems scalar payouts across every outcome", async function () {
+      const userUnderlyingTokenAccount = getATA(
+        multiUnderlyingMint,
+        payer.publicKey
+      )[0];
+
+      await vaultClient
+        .mintMultiOutcomeTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          3,
+          new BN(100)
+        )
+        .rpc();
+
+      await vaultClient
+        .settleConditionalVaultWithPayoutsIx(
+          multiVault,
+          [new BN(1), new BN(1), new BN(2)],
+          multiSettlementAuthority.publicKey
+        )
+        .signers([multiSettlementAuthority])
+        .rpc();
+
+      const storedVault = await vaultClient.getVault(multiVault);
+      assert.exists(storedVault.status.settled);
+      assert.equal(storedVault.payoutDenominator.toNumber(), 4);
+
+      await vaultClient
+        .redeemMultiOutcomeTokensIx(multiVault, multiUnderlyingMint, 3)
+        .rpc();
+
+      // 25 + 25 + 50
+      const underlyingAccount = await getAccount(
+        banksClient,
+        userUnderlyingTokenAccount
+      );
+      assert.equal(underlyingAccount.amount, 1_000n);
+    });
+
+    it("can't be settled with payouts that don't match its outcomes", async function () {
+      const callbacks = expectError(
+        "InvalidPayouts",
+        "settled a three-outcome vault with two payouts"
+      );
+
+      await vaultClient
+        .settleConditionalVaultWithPayoutsIx(
+          multiVault,
+          [new BN(1), new BN(1)],
+          multiSettlementAuthority.publicKey
+        )
+        .signers([multiSettlementAuthority])
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("can't be settled with payouts that are all zero", async function () {
+      const callbacks = expectError(
+        "InvalidPayouts",
+        "settled a vault where nothing pays out"
+      );
+
+      await vaultClient
+        .settleConditionalVaultWithPayoutsIx(
+          multiVault,
+          [new BN(0), new BN(0), new BN(0)],
+          multiSettlementAuthority.publicKey
+        )
+        .signers([multiSettlementAuthority])
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("redeems part of any one outcome", async function () {
+      const outcomeMints = vaultClient.getOutcomeMints(multiVault, 3);
+      const userUnderlyingTokenAccount = getATA(
+        multiUnderlyingMint,
+        payer.publicKey
+      )[0];
+
+      await vaultClient
+        .mintMultiOutcomeTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          3,
+          new BN(100)
+        )
+        .rpc();
+
+      await vaultClient
+        .settleMultiOutcomeVaultIx(
+          multiVault,
+          2,
+          multiSettlementAuthority.publicKey
+        )
+        .signers([multiSettlementAuthority])
+        .rpc();
+
+      await vaultClient
+        .redeemPartialConditionalTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          outcomeMints[2],
+          2,
+          new BN(30)
+        )
+        .rpc();
+
+      // losing outcomes burn for nothing
+      await vaultClient
+        .redeemPartialConditionalTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          outcomeMints[0],
+          0,
+          new BN(100)
+        )
+        .rpc();
+
+      const underlyingAccount = await getAccount(
+        banksClient,
+        userUnderlyingTokenAccount
+      );
+      assert.equal(underlyingAccount.amount, 930n);
+
+      const winningAccount = await getAccount(
+        banksClient,
+        getATA(outcomeMints[2], payer.publicKey)[0]
+      );
+      assert.equal(winningAccount.amount, 70n);
+
+      const untouchedAccount = await getAccount(
+        banksClient,
+        getATA(outcomeMints[1], payer.publicKey)[0]
+      );
+      assert.equal(untouchedAccount.amount, 100n);
+    });
+
+    it("can't partially redeem an outcome it doesn't have", async function () {
+      const outcomeMints = vaultClient.getOutcomeMints(multiVault, 3);
+
+      await vaultClient
+        .mintMultiOutcomeTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          3,
+          new BN(100)
+        )
+        .rpc();
+
+      await vaultClient
+        .settleMultiOutcomeVaultIx(
+          multiVault,
+          0,
+          multiSettlementAuthority.publicKey
+        )
+        .signers([multiSettlementAuthority])
+        .rpc();
+
+      const callbacks = expectError(
+        "InvalidOutcomeIndex",
+        "redeemed an outcome the vault doesn't have"
+      );
+
+      await vaultClient
+        .redeemPartialConditionalTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          outcomeMints[0],
+          3,
+          new BN(10)
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("can't partially redeem with another outcome's mint", async function () {
+      const outcomeMints = vaultClient.getOutcomeMints(multiVault, 3);
+
+      await vaultClient
+        .mintMultiOutcomeTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          3,
+          new BN(100)
+        )
+        .rpc();
+
+      await vaultClient
+        .settleMultiOutcomeVaultIx(
+          multiVault,
+          0,
+          multiSettlementAuthority.publicKey
+        )
+        .signers([multiSettlementAuthority])
+        .rpc();
+
+      const callbacks = expectError(
+        "InvalidConditionalTokenMint",
+        "redeemed a losing outcome's tokens as the winning outcome"
+      );
+
+      await vaultClient
+        .redeemPartialConditionalTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          outcomeMints[1],
+          0,
+          new BN(10)
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("closes once every outcome token has been redeemed", async function () {
+      await vaultClient
+        .mintMultiOutcomeTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          3,
+          new BN(100)
+        )
+        .rpc();
+
+      await vaultClient
+        .settleMultiOutcomeVaultIx(
+          multiVault,
+          1,
+          multiSettlementAuthority.publicKey
+        )
+        .signers([multiSettlementAuthority])
+        .rpc();
+
+      const callbacks = expectError(
+        "ConditionalTokensOutstanding",
+        "closed a vault while outcome tokens were still out"
+      );
+      await vaultClient
+        .closeConditionalVault(multiVault)
+        .then(callbacks[0], callbacks[1]);
+
+      await vaultClient
+        .redeemMultiOutcomeTokensIx(multiVault, multiUnderlyingMint, 3)
+        .rpc();
+
+      await vaultClient.closeConditionalVault(multiVault);
+
+      assert.isNull(await banksClient.getAccount(multiVault));
+      assert.isNull(
+        await banksClient.getAccount(getATA(multiUnderlyingMint, multiVault)[0])
+      );
+    });
+
+    it("sweeps scalar rounding dust to the rent payer when closed", async function () {
+      const userUnderlyingTokenAccount = getATA(
+        multiUnderlyingMint,
+        payer.publicKey
+      )[0];
+
+      await vaultClient
+        .mintMultiOutcomeTokensIx(
+          multiVault,
+          multiUnderlyingMint,
+          3,
+          new BN(100)
+        )
+        .rpc();
+
+      await vaultClient
+        .settleConditionalVaultWithPayoutsIx(
+          multiVault,
+          [new BN(1), new BN(1), new BN(1)],
+          multiSettlementAuthority.publicKey
+        )
+        .signers([multiSettlementAuthority])
+        .rpc();
+
+      await vaultClient
+        .redeemMultiOutcomeTokensIx(multiVault, multiUnderlyingMint, 3)
+        .rpc();
+
+      // 33 per outcome, so 1 is left in the vault
+      let underlyingAccount = await getAccount(
+        banksClient,
+        userUnderlyingTokenAccount
+      );
+      assert.equal(underlyingAccount.amount, 999n);
+
+      // the payer also paid the vault's rent
+      await vaultClient.closeConditionalVault(multiVault);
+
+      underlyingAccount = await getAccount(
+        banksClient,
+        userUnderlyingTokenAccount
+      );
+      assert.equal(underlyingAccount.amount, 1_000n);
+    });
+
+    it("can't be closed before it's settled", async function () {
+      const callbacks = expectError(
+        "VaultNotSettled",
+        "closed a vault that was still active"
+      );
+
+      await vaultClient
+        .closeConditionalVault(multiVault)
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("can't be settled as finalized or reverted", async function () {
+      const callbacks = expectError(
+        "InvalidSettlement",
+        "finalized a multi-outcome vault"
+      );
+
+      await vaultProgram.methods
+        .settleConditionalVault({ finalized: {} })
+        .accounts({
+          settlementAuthority: multiSettlementAuthority.publicKey,
+          vault: multiVault,
+        })
+        .signers([multiSettlementAuthority])
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("can't be settled to an outcome it doesn't have", async function () {
+      const callbacks = expectError(
+        "InvalidOutcomeIndex",
+        "settled a vault to an outcome it doesn't have"
+      );
+
+      await vaultClient
+        .settleMultiOutcomeVaultIx(
+          multiVault,
+          3,
+          multiSettlementAuthority.publicKey
+        )
+        .signers([multiSettlementAuthority])
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+  });
+
+  describe("#redeem_conditional_tokens_through_parent", async function () {
+    let parentVault: PublicKey,
+      parentSettlementAuthority: Keypair,
+      parentUnderlyingMint: PublicKey,
+      parentPassMint: PublicKey,
+      nestedVault: PublicKey,
+      nestedSettlementAuthority: Keypair;
+
+    beforeEach(async function () {
+      parentSettlementAuthority = Keypair.generate();
+      nestedSettlementAuthority = Keypair.generate();
+
+      parentUnderlyingMint = await createMint(
+        banksClient,
+        payer,
+        payer.publicKey,
+        null,
+        8
+      );
+      const userUnderlyingTokenAccount = await createAssociatedTokenAccount(
+        banksClient,
+        payer,
+        parentUnderlyingMint,
+        payer.publicKey
+      );
+      await mintTo(
+        banksClient,
+        payer,
+        parentUnderlyingMint,
+        userUnderlyingTokenAccount,
+        payer,
+        1_000
+      );
+
+      const parentProposal = Keypair.generate().publicKey;
+      [parentVault] = getVaultAddr(
+        vaultProgram.programId,
+        parentSettlementAuthority.publicKey,
+        parentUnderlyingMint,
+        parentProposal
+      );
+      await vaultClient
+        .initializeVaultIx(
+          parentSettlementAuthority.publicKey,
+          parentUnderlyingMint,
+          parentProposal
+        )
+        .rpc();
+      [parentPassMint] = getVaultFinalizeMintAddr(
+        vaultProgram.programId,
+        parentVault
+      );
+
+      // conditional on the parent vault being finalized
+      const nestedProposal = Keypair.generate().publicKey;
+      [nestedVault] = getVaultAddr(
+        vaultProgram.programId,
+        nestedSettlementAuthority.publicKey,
+        parentPassMint,
+        nestedProposal
+      );
+      await vaultClient
+        .initializeVaultIx(
+          nestedSettlementAuthority.publicKey,
+          parentPassMint,
+          nestedProposal,
+          null,
+          parentVault
+        )
+        .rpc();
+
+      await vaultClient
+        .mintConditionalTokensIx(parentVault, parentUnderlyingMint, new BN(100))
+        .rpc();
+      await vaultClient
+        .mintConditionalTokensIx(nestedVault, parentPassMint, new BN(100))
+        .rpc();
+    });
+
+    async function settle(
+      vault: PublicKey,
+      settlementAuthority: Keypair,
+      status: "finalized" | "reverted"
+    ) {
+      await vaultProgram.methods
+        .settleConditionalVault({ [status]: {} })
+        .accounts({
+          settlementAuthority: settlementAuthority.publicKey,
+          vault,
+        })
+        .signers([settlementAuthority])
+        .rpc();
+    }
+
+    async function redeemThroughParent() {
+      await vaultClient
+        .redeemConditionalTokensThroughParentIx(
+          nestedVault,
+          parentVault,
+          parentPassMint,
+          parentUnderlyingMint
+        )
+        .rpc();
+    }
+
+    async function underlyingBalance() {
+      const underlyingAccount = await getAccount(
+        banksClient,
+        getATA(parentUnderlyingMint, payer.publicKey)[0]
+      );
+      return underlyingAccount.amount;
+    }
+
+    it("redeems when the nested vault settles before its parent", async function () {
+      await settle(nestedVault, nestedSettlementAuthority, "finalized");
+
+      const callbacks = expectError(
+        "CantRedeemConditionalTokens",
+        "redeemed through a parent that wasn't settled yet"
+      );
+      await redeemThroughParent().then(callbacks[0], callbacks[1]);
+
+      await settle(parentVault, parentSettlementAuthority, "finalized");
+
+      await redeemThroughParent();
+
+      // the 100 parent fail tokens that were never nested are left over
+      assert.equal(await underlyingBalance(), 1_000n);
+
+      const { conditionalOnFinalizeTokenMint } =
+        vaultClient.getConditionalTokenMints(nestedVault);
+      const nestedPassAccount = await getAccount(
+        banksClient,
+        getATA(conditionalOnFinalizeTokenMint, payer.publicKey)[0]
+      );
+      assert.equal(nestedPassAccount.amount, 0n);
+    });
+
+    it("redeems when the parent settles before the nested vault", async function () {
+      await settle(parentVault, parentSettlementAuthority, "finalized");
+      await settle(nestedVault, nestedSettlementAuthority, "reverted");
+
+      await redeemThroughParent();
+
+      // the nested fail tokens pay out parent pass tokens, which pay out
+      assert.equal(await underlyingBalance(), 1_000n);
+    });
+
+    it("pays nothing when the parent's outcome loses", async function () {
+      await settle(parentVault, parentSettlementAuthority, "reverted");
+      await settle(nestedVault, nestedSettlementAuthority, "finalized");
+
+      await redeemThroughParent();
+
+      assert.equal(await underlyingBalance(), 900n);
+    });
+
+    it("can't redeem through a vault that isn't the parent", async function () {
+      const otherAuthority = Keypair.generate();
+      const otherProposal = Keypair.generate().publicKey;
+      const [otherVault] = getVaultAddr(
+        vaultProgram.programId,
+        otherAuthority.publicKey,
+        parentUnderlyingMint,
+        otherProposal
+      );
+      await vaultClient
+        .initializeVaultIx(
+          otherAuthority.publicKey,
+          parentUnderlyingMint,
+          otherProposal
+        )
+        .rpc();
+
+      await settle(nestedVault, nestedSettlementAuthority, "finalized");
+      await settle(otherVault, otherAuthority, "finalized");
+
+      const callbacks = expectError(
+        "InvalidParentVault",
+        "redeemed through a vault that isn't the parent"
+      );
+      await vaultClient
+        .redeemConditionalTokensThroughParentIx(
+          nestedVault,
+          otherVault,
+          parentPassMint,
+          parentUnderlyingMint
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+  });
+
+  describe("#settle_conditional_vault_from_oracle", async function () {
+    let oracle: PublicKey,
+      oracleOwner: PublicKey,
+      oracleSettlementAuthority: Keypair,
+      oracleUnderlyingMint: PublicKey;
+
+    beforeEach(async function () {
+      oracle = Keypair.generate().publicKey;
+      oracleOwner = Keypair.generate().publicKey;
+      oracleSettlementAuthority = Keypair.generate();
+      oracleUnderlyingMint = await createMint(
+        banksClient,
+        payer,
+        payer.publicKey,
+        null,
+        8
+      );
+
+      setOracleData(oracleOwner, [0, 0, 0, 0]);
+    });
+
+    function setOracleData(owner: PublicKey, data: number[]) {
+      context.setAccount(oracle, {
+        lamports: 1_000_000_000,
+        data: Buffer.from(data),
+        owner,
+        executable: false,
+      });
+    }
+
+    // reads the byte at offset 3: 1 resolves to outcome 0 and 2 to outcome 1
+    function settlementOracle(winningOutcomes = [0, 1]) {
+      return {
+        account: oracle,
+        ownerProgram: oracleOwner,
+        outcomeOffset: 3,
+        resolutions: winningOutcomes.map((winningOutcome, i) => ({
+          value: i + 1,
+          winningOutcome,
+        })),
+      };
+    }
+
+    async function initializeOracleVault(numOutcomes?: number) {
+      const oracleProposal = Keypair.generate().publicKey;
+      const [oracleVault] = getVaultAddr(
+        vaultProgram.programId,
+        oracleSettlementAuthority.publicKey,
+        oracleUnderlyingMint,
+        oracleProposal
+      );
+
+      if (numOutcomes) {
+        await vaultClient
+          .initializeMultiOutcomeVaultIx(
+            oracleSettlementAuthority.publicKey,
+            oracleUnderlyingMint,
+            oracleProposal,
+            numOutcomes,
+            null,
+            settlementOracle([0, 1, 2])
+          )
+          .rpc();
+      } else {
+        await vaultClient
+          .initializeVaultIx(
+            oracleSettlementAuthority.publicKey,
+            oracleUnderlyingMint,
+            oracleProposal,
+            null,
+            null,
+            settlementOracle()
+          )
+          .rpc();
+      }
+
+      return oracleVault;
+    }
+
+    it("settles vaults once their oracle resolves", async function () {
+      const oracleVault = await initializeOracleVault();
+
+      const callbacks = expectError(
+        "SettlementOracleNotResolved",
+        "settled a vault before its oracle resolved"
+      );
+      await vaultClient
+        .settleConditionalVaultFromOracleIx(oracleVault, oracle)
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+
+      setOracleData(oracleOwner, [0, 0, 0, 2]);
+
+      await vaultClient
+        .settleConditionalVaultFromOracleIx(oracleVault, oracle)
+        .rpc();
+
+      const storedVault = await vaultClient.getVault(oracleVault);
+      assert.exists(storedVault.status.reverted);
+    });
+
+    it("settles multi-outcome vaults to the outcome their oracle names", async function () {
+      const oracleVault = await initializeOracleVault(3);
+
+      setOracleData(oracleOwner, [0, 0, 0, 3]);
+
+      await vaultClient
+        .settleConditionalVaultFromOracleIx(oracleVault, oracle)
+        .rpc();
+
+      const storedVault = await vaultClient.getVault(oracleVault);
+      assert.exists(storedVault.status.settled);
+      assert.deepEqual(
+        storedVault.payoutNumerators.map((numerator) => numerator.toNumber()),
+        [0, 0, 1]
+      );
+    });
+
+    it("can't be settled from an oracle owned by another program", async function () {
+      const oracleVault = await initializeOracleVault();
+
+      setOracleData(Keypair.generate().publicKey, [0, 0, 0, 1]);
+
+      const callbacks = expectError(
+        "InvalidSettlementOracle",
+        "settled a vault from an oracle that anyone could have written"
+      );
+      await vaultClient
+        .settleConditionalVaultFromOracleIx(oracleVault, oracle)
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("can't be settled from an oracle when it doesn't have one", async function () {
+      let [vault] = await generateRandomVault(
+        vaultProgram,
+        vaultClient,
+        payer,
+        banksClient,
+        umi
+      );
+
+      setOracleData(oracleOwner, [0, 0, 0, 1]);
+
+      const callbacks = expectError(
+        "InvalidSettlementOracle",
+        "settled a vault from an oracle it never named"
+      );
+      await vaultClient
+        .settleConditionalVaultFromOracleIx(vault, oracle)
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+
+    it("can't be initialized with an oracle that names an outcome it doesn't have", async function () {
+      const oracleProposal = Keypair.generate().publicKey;
+
+      const callbacks = expectError(
+        "InvalidSettlementOracle",
+        "initialized a two-outcome vault with an oracle that resolves to outcome 2"
+      );
+      await vaultClient
+        .initializeVaultIx(
+          oracleSettlementAuthority.publicKey,
+          oracleUnderlyingMint,
+          oracleProposal,
+          null,
+          null,
+          settlementOracle([0, 2])
+        )
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+    });
+  });
 });
 
 async function generateRandomVault(